@@ -0,0 +1,129 @@
+#[cfg(not(feature = "library"))]
+use cosmwasm_std::entry_point;
+use cosmwasm_std::{to_json_binary, Binary, Deps, DepsMut, Env, MessageInfo, Response, StdError};
+use cw2::set_contract_version;
+use milky_way::events::MilkyWayEvent;
+use semver::Version;
+
+use crate::error::ContractResult;
+use crate::execute::{
+    execute_add_lst_contract, execute_post_rates, execute_remove_lst_contract,
+    execute_update_admin, validate_denom,
+};
+use crate::msg::{ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg};
+use crate::query::{
+    query_all_latest_rates, query_lst_contracts, query_purchase_rate, query_rates_history,
+    query_redemption_rate,
+};
+use crate::state::{ADMIN, LST_CONTRACTS};
+
+pub const CONTRACT_NAME: &str = env!("CARGO_PKG_NAME");
+const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn instantiate(
+    mut deps: DepsMut,
+    _env: Env,
+    _info: MessageInfo,
+    msg: InstantiateMsg,
+) -> ContractResult<Response> {
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    let admin = deps.api.addr_validate(&msg.admin_address)?;
+    ADMIN.set(deps.branch(), Some(admin.clone()))?;
+
+    for lst_contract in msg.lst_contracts.unwrap_or_default() {
+        let address = deps.api.addr_validate(&lst_contract.address)?;
+        validate_denom(&lst_contract.denom)?;
+        LST_CONTRACTS.save(deps.storage, &address, &lst_contract.denom)?;
+    }
+
+    Ok(Response::new()
+        .add_attribute("action", "instantiate")
+        .add_attribute("owner", admin.clone())
+        .add_event(
+            MilkyWayEvent::Instantiate {
+                admin: admin.to_string(),
+            }
+            .into(),
+        ))
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn execute(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: ExecuteMsg,
+) -> ContractResult<Response> {
+    match msg {
+        ExecuteMsg::PostRates {
+            denom,
+            purchase_rate,
+            redemption_rate,
+        } => execute_post_rates(deps, env, info, denom, purchase_rate, redemption_rate),
+        ExecuteMsg::AddLstContract { address, denom } => {
+            execute_add_lst_contract(deps, info, address, denom)
+        }
+        ExecuteMsg::RemoveLstContract { address } => {
+            execute_remove_lst_contract(deps, info, address)
+        }
+        ExecuteMsg::UpdateAdmin { admin } => execute_update_admin(deps, info, admin),
+    }
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> ContractResult<Binary> {
+    let res = match msg {
+        QueryMsg::Admin {} => to_json_binary(&ADMIN.query_admin(deps)?)?,
+        QueryMsg::LstContracts { start_after, limit } => {
+            to_json_binary(&query_lst_contracts(deps, start_after, limit)?)?
+        }
+        QueryMsg::RedemptionRate { denom } => to_json_binary(&query_redemption_rate(deps, denom)?)?,
+        QueryMsg::PurchaseRate { denom } => to_json_binary(&query_purchase_rate(deps, denom)?)?,
+        QueryMsg::AllLatestRates { start_after, limit } => {
+            to_json_binary(&query_all_latest_rates(deps, start_after, limit)?)?
+        }
+        QueryMsg::RatesHistory {
+            denom,
+            start_before,
+            limit,
+        } => to_json_binary(&query_rates_history(deps, denom, start_before, limit)?)?,
+    };
+    Ok(res)
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn migrate(deps: DepsMut, _env: Env, _msg: MigrateMsg) -> ContractResult<Response> {
+    let current_version = cw2::get_contract_version(deps.storage)?;
+    if CONTRACT_NAME != current_version.contract.as_str() {
+        return Err(StdError::generic_err("Cannot upgrade to a different contract").into());
+    }
+
+    let version: Version = current_version
+        .version
+        .parse()
+        .map_err(|_| StdError::generic_err("Invalid contract version"))?;
+    let new_version: Version = CONTRACT_VERSION
+        .parse()
+        .map_err(|_| StdError::generic_err("Invalid contract version"))?;
+
+    // Prevent downgrade
+    if version > new_version {
+        return Err(StdError::generic_err("Cannot upgrade to a previous contract version").into());
+    }
+    // if same version return
+    if version == new_version {
+        return Err(StdError::generic_err("Cannot migrate to the same version.").into());
+    }
+
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    Ok(Response::new().add_event(
+        MilkyWayEvent::Migrate {
+            from_version: current_version.version,
+            to_version: CONTRACT_VERSION.to_string(),
+        }
+        .into(),
+    ))
+}
@@ -0,0 +1,36 @@
+use cosmwasm_std::StdError;
+use cw2::VersionError;
+use cw_controllers::AdminError;
+use thiserror::Error;
+
+pub type ContractResult<T> = core::result::Result<T, ContractError>;
+
+#[derive(Error, Debug, PartialEq)]
+pub enum ContractError {
+    #[error("{0}")]
+    Std(#[from] StdError),
+
+    #[error("{0}")]
+    Version(#[from] VersionError),
+
+    #[error("Admin error: {0}")]
+    Admin(#[from] AdminError),
+
+    #[error("Unauthorized: {sender}")]
+    Unauthorized { sender: String },
+
+    #[error("Invalid {field}: {value}")]
+    InvalidRate { field: String, value: String },
+
+    #[error("{sender} is not allowed to post rates for {denom}")]
+    DenomNotAllowed { sender: String, denom: String },
+
+    #[error("Liquid stake token contract {address} not found")]
+    LstContractNotFound { address: String },
+
+    #[error("Invalid denom: {denom}")]
+    InvalidDenom { denom: String },
+
+    #[error("No rates posted for {denom}")]
+    RatesNotFound { denom: String },
+}
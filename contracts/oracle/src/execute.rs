@@ -0,0 +1,180 @@
+use std::str::FromStr;
+
+use cosmwasm_std::{Decimal, DepsMut, Empty, Env, MessageInfo, Order, Response, StdResult};
+use cw_storage_plus::Bound;
+use milky_way::events::MilkyWayEvent;
+
+use crate::error::{ContractError, ContractResult};
+use crate::state::{
+    Rates, ADMIN, LATEST_RATES, LST_CONTRACTS, MAX_PRUNED_RATES, RATES_HISTORY,
+    RATES_HISTORY_HEIGHTS,
+};
+
+fn parse_rate(field: &str, value: &str) -> ContractResult<Decimal> {
+    match Decimal::from_str(value) {
+        Ok(rate) if !rate.is_zero() => Ok(rate),
+        _ => Err(ContractError::InvalidRate {
+            field: field.to_string(),
+            value: value.to_string(),
+        }),
+    }
+}
+
+/// Checks the denom follows the format of the Cosmos SDK bank module.
+pub fn validate_denom(denom: &str) -> ContractResult<()> {
+    let valid = (3..=128).contains(&denom.len())
+        && denom.starts_with(|c: char| c.is_ascii_alphabetic())
+        && denom
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || "/:._-".contains(c));
+    if !valid {
+        return Err(ContractError::InvalidDenom {
+            denom: denom.to_string(),
+        });
+    }
+    Ok(())
+}
+
+pub fn execute_post_rates(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    denom: String,
+    purchase_rate: String,
+    redemption_rate: String,
+) -> ContractResult<Response> {
+    if !ADMIN.is_admin(deps.as_ref(), &info.sender)? {
+        // Each contract only posts the rates of its own liquid stake token
+        match LST_CONTRACTS.may_load(deps.storage, &info.sender)? {
+            Some(allowed_denom) if allowed_denom == denom => {}
+            Some(_) => {
+                return Err(ContractError::DenomNotAllowed {
+                    sender: info.sender.to_string(),
+                    denom,
+                })
+            }
+            None => {
+                return Err(ContractError::Unauthorized {
+                    sender: info.sender.to_string(),
+                })
+            }
+        }
+    }
+
+    // Rates posted in the same block are kept in order of arrival
+    let index = LATEST_RATES
+        .may_load(deps.storage, &denom)?
+        .filter(|latest| latest.update_height == env.block.height)
+        .map_or(0, |latest| latest.index + 1);
+    let rates = Rates {
+        denom: denom.clone(),
+        purchase_rate: parse_rate("purchase_rate", &purchase_rate)?,
+        redemption_rate: parse_rate("redemption_rate", &redemption_rate)?,
+        update_time: env.block.time.seconds(),
+        update_height: env.block.height,
+        index,
+        poster: info.sender,
+    };
+    LATEST_RATES.save(deps.storage, &denom, &rates)?;
+    RATES_HISTORY.save(deps.storage, (&denom, rates.update_height, index), &rates)?;
+
+    // Only the rates of the last blocks are kept in the history
+    if let Some(min_height) = env.block.height.checked_sub(RATES_HISTORY_HEIGHTS) {
+        let expired = RATES_HISTORY
+            .sub_prefix(&denom)
+            .keys(
+                deps.storage,
+                None,
+                Some(Bound::exclusive((min_height, 0))),
+                Order::Ascending,
+            )
+            .take(MAX_PRUNED_RATES)
+            .collect::<StdResult<Vec<_>>>()?;
+        for (height, index) in expired {
+            RATES_HISTORY.remove(deps.storage, (&denom, height, index));
+        }
+    }
+
+    Ok(Response::new()
+        .add_attribute("action", "post_rates")
+        .add_attribute("denom", denom.clone())
+        .add_attribute("purchase_rate", rates.purchase_rate.to_string())
+        .add_attribute("redemption_rate", rates.redemption_rate.to_string())
+        .add_event(
+            MilkyWayEvent::PostRates {
+                denom,
+                purchase_rate: rates.purchase_rate,
+                redemption_rate: rates.redemption_rate,
+            }
+            .into(),
+        ))
+}
+
+pub fn execute_add_lst_contract(
+    deps: DepsMut,
+    info: MessageInfo,
+    address: String,
+    denom: String,
+) -> ContractResult<Response> {
+    ADMIN.assert_admin(deps.as_ref(), &info.sender)?;
+
+    let address = deps.api.addr_validate(&address)?;
+    validate_denom(&denom)?;
+    LST_CONTRACTS.save(deps.storage, &address, &denom)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "add_lst_contract")
+        .add_attribute("address", address.clone())
+        .add_attribute("denom", denom)
+        .add_event(
+            MilkyWayEvent::AddLstContract {
+                address: address.to_string(),
+            }
+            .into(),
+        ))
+}
+
+pub fn execute_remove_lst_contract(
+    deps: DepsMut,
+    info: MessageInfo,
+    address: String,
+) -> ContractResult<Response> {
+    ADMIN.assert_admin(deps.as_ref(), &info.sender)?;
+
+    let address = deps.api.addr_validate(&address)?;
+    if !LST_CONTRACTS.has(deps.storage, &address) {
+        return Err(ContractError::LstContractNotFound {
+            address: address.to_string(),
+        });
+    }
+    LST_CONTRACTS.remove(deps.storage, &address);
+
+    Ok(Response::new()
+        .add_attribute("action", "remove_lst_contract")
+        .add_attribute("address", address.clone())
+        .add_event(
+            MilkyWayEvent::RemoveLstContract {
+                address: address.to_string(),
+            }
+            .into(),
+        ))
+}
+
+pub fn execute_update_admin(
+    deps: DepsMut,
+    info: MessageInfo,
+    admin: String,
+) -> ContractResult<Response> {
+    let admin = deps.api.addr_validate(&admin)?;
+    ADMIN.execute_update_admin::<Empty, Empty>(deps, info, Some(admin.clone()))?;
+
+    Ok(Response::new()
+        .add_attribute("action", "update_admin")
+        .add_attribute("admin", admin.clone())
+        .add_event(
+            MilkyWayEvent::UpdateAdmin {
+                admin: admin.to_string(),
+            }
+            .into(),
+        ))
+}
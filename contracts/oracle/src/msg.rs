@@ -0,0 +1,112 @@
+use cosmwasm_schema::{cw_serde, QueryResponses};
+use cosmwasm_std::{Addr, Decimal};
+
+use crate::state::Rates;
+
+/// Compatible with the message sent by the staking contract when it
+/// instantiates its oracle.
+#[cw_serde]
+pub struct InstantiateMsg {
+    /// Admin of the oracle, also allowed to post rates.
+    pub admin_address: String,
+
+    /// Liquid stake token contracts allowed to post rates.
+    pub lst_contracts: Option<Vec<UnsafeLstContract>>,
+}
+
+/// Liquid stake token contract allowed to post the rates of one denom.
+#[cw_serde]
+pub struct UnsafeLstContract {
+    pub address: String,
+    pub denom: String,
+}
+
+#[cw_serde]
+pub enum ExecuteMsg {
+    /// Posts the rates of a liquid stake token; callable by the admin
+    /// and the authorised liquid stake token contracts.
+    PostRates {
+        denom: String,
+        purchase_rate: String,
+        redemption_rate: String,
+    },
+    /// Allows a liquid stake token contract to post the rates of `denom`;
+    /// callable by the admin.
+    AddLstContract { address: String, denom: String },
+    /// Revokes the authorisation of a liquid stake token contract; callable by the admin.
+    RemoveLstContract { address: String },
+    /// Sets a new admin; callable by the admin.
+    UpdateAdmin { admin: String },
+}
+
+#[cw_serde]
+#[derive(QueryResponses)]
+pub enum QueryMsg {
+    /// Queries the admin of the oracle.
+    #[returns(cw_controllers::AdminResponse)]
+    Admin {},
+
+    /// Queries the liquid stake token contracts allowed to post rates.
+    #[returns(Vec<LstContract>)]
+    LstContracts {
+        /// If provided, starts listing contracts after this address.
+        start_after: Option<String>,
+
+        /// Maximum number of contracts to return.
+        limit: Option<u32>,
+    },
+
+    /// Queries the latest redemption rate of a liquid stake token.
+    #[returns(RedemptionRateResponse)]
+    RedemptionRate { denom: String },
+
+    /// Queries the latest purchase rate of a liquid stake token.
+    #[returns(PurchaseRateResponse)]
+    PurchaseRate { denom: String },
+
+    /// Queries the latest rates of all the liquid stake tokens.
+    #[returns(Vec<Rates>)]
+    AllLatestRates {
+        /// If provided, starts listing rates after this denom.
+        start_after: Option<String>,
+
+        /// Maximum number of rates to return.
+        limit: Option<u32>,
+    },
+
+    /// Queries the rates posted for a liquid stake token, most recent first.
+    #[returns(Vec<Rates>)]
+    RatesHistory {
+        denom: String,
+
+        /// If provided, starts listing rates posted before this height and index.
+        start_before: Option<(u64, u32)>,
+
+        /// Maximum number of rates to return.
+        limit: Option<u32>,
+    },
+}
+
+#[cw_serde]
+pub struct LstContract {
+    pub address: Addr,
+    /// Denom for which the contract posts rates.
+    pub denom: String,
+}
+
+#[cw_serde]
+pub struct RedemptionRateResponse {
+    pub redemption_rate: Decimal,
+    /// Time in seconds when the rate has been posted.
+    pub update_time: u64,
+}
+
+#[cw_serde]
+pub struct PurchaseRateResponse {
+    pub purchase_rate: Decimal,
+    /// Time in seconds when the rate has been posted.
+    pub update_time: u64,
+}
+
+#[cw_serde]
+pub struct MigrateMsg {}
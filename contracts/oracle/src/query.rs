@@ -0,0 +1,87 @@
+use cosmwasm_std::{Deps, Order, StdResult};
+use cw_storage_plus::Bound;
+
+use crate::error::{ContractError, ContractResult};
+use crate::msg::{LstContract, PurchaseRateResponse, RedemptionRateResponse};
+use crate::state::{Rates, LATEST_RATES, LST_CONTRACTS, RATES_HISTORY};
+
+fn latest_rates(deps: Deps, denom: String) -> ContractResult<Rates> {
+    LATEST_RATES
+        .may_load(deps.storage, &denom)?
+        .ok_or(ContractError::RatesNotFound { denom })
+}
+
+pub fn query_redemption_rate(deps: Deps, denom: String) -> ContractResult<RedemptionRateResponse> {
+    let rates = latest_rates(deps, denom)?;
+    Ok(RedemptionRateResponse {
+        redemption_rate: rates.redemption_rate,
+        update_time: rates.update_time,
+    })
+}
+
+pub fn query_purchase_rate(deps: Deps, denom: String) -> ContractResult<PurchaseRateResponse> {
+    let rates = latest_rates(deps, denom)?;
+    Ok(PurchaseRateResponse {
+        purchase_rate: rates.purchase_rate,
+        update_time: rates.update_time,
+    })
+}
+
+pub fn query_lst_contracts(
+    deps: Deps,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> ContractResult<Vec<LstContract>> {
+    let start_after = start_after
+        .map(|address| deps.api.addr_validate(&address))
+        .transpose()?;
+    let contracts = LST_CONTRACTS
+        .range(
+            deps.storage,
+            start_after.as_ref().map(Bound::exclusive),
+            None,
+            Order::Ascending,
+        )
+        .take(limit.unwrap_or(u32::MAX) as usize)
+        .map(|item| item.map(|(address, denom)| LstContract { address, denom }))
+        .collect::<StdResult<_>>()?;
+    Ok(contracts)
+}
+
+pub fn query_all_latest_rates(
+    deps: Deps,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> ContractResult<Vec<Rates>> {
+    let rates = LATEST_RATES
+        .range(
+            deps.storage,
+            start_after.as_deref().map(Bound::exclusive),
+            None,
+            Order::Ascending,
+        )
+        .take(limit.unwrap_or(u32::MAX) as usize)
+        .map(|item| item.map(|(_, rates)| rates))
+        .collect::<StdResult<_>>()?;
+    Ok(rates)
+}
+
+pub fn query_rates_history(
+    deps: Deps,
+    denom: String,
+    start_before: Option<(u64, u32)>,
+    limit: Option<u32>,
+) -> ContractResult<Vec<Rates>> {
+    let rates = RATES_HISTORY
+        .sub_prefix(&denom)
+        .range(
+            deps.storage,
+            None,
+            start_before.map(Bound::exclusive),
+            Order::Descending,
+        )
+        .take(limit.unwrap_or(u32::MAX) as usize)
+        .map(|item| item.map(|(_, rates)| rates))
+        .collect::<StdResult<_>>()?;
+    Ok(rates)
+}
@@ -0,0 +1,48 @@
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{Addr, Decimal};
+use cw_controllers::Admin;
+use cw_storage_plus::Map;
+
+/// Rates of a liquid stake token posted by its contract.
+#[cw_serde]
+pub struct Rates {
+    /// Denom of the liquid stake token.
+    pub denom: String,
+
+    /// Amount of liquid stake tokens minted for one native token.
+    pub purchase_rate: Decimal,
+
+    /// Amount of native tokens redeemed for one liquid stake token.
+    pub redemption_rate: Decimal,
+
+    /// Time in seconds when the rates have been posted.
+    pub update_time: u64,
+
+    /// Block height at which the rates have been posted.
+    pub update_height: u64,
+
+    /// Position of these rates among the ones posted for the denom
+    /// at the same height.
+    pub index: u32,
+
+    /// Contract that posted the rates.
+    pub poster: Addr,
+}
+
+pub const ADMIN: Admin = Admin::new("admin");
+
+/// Contracts allowed to post rates, with the denom each of them posts for.
+pub const LST_CONTRACTS: Map<&Addr, String> = Map::new("lst_contracts");
+
+/// Latest rates by denom.
+pub const LATEST_RATES: Map<&str, Rates> = Map::new("latest_rates");
+
+/// Number of blocks the rates are kept in the history, about a week with 6s blocks.
+pub const RATES_HISTORY_HEIGHTS: u64 = 100_000;
+
+/// Maximum number of expired rates removed from the history of a denom on each post,
+/// greater than one so the history shrinks back to the window.
+pub const MAX_PRUNED_RATES: usize = 10;
+
+/// Rates posted for each denom, by denom, height and index within the block.
+pub const RATES_HISTORY: Map<(&str, u64, u32), Rates> = Map::new("rates_history");
@@ -0,0 +1,155 @@
+use crate::contract::{execute, query};
+use crate::error::ContractError;
+use crate::msg::{ExecuteMsg, LstContract, QueryMsg};
+use crate::tests::test_helper::{
+    init, ADMIN, DENOM, LST_CONTRACT, OTHER_DENOM, OTHER_LST_CONTRACT,
+};
+
+use cosmwasm_std::testing::{mock_env, mock_info};
+use cosmwasm_std::{from_json, Addr, Deps, Event};
+use cw_controllers::{AdminError, AdminResponse};
+use milky_way::events::MilkyWayEvent;
+
+fn lst_contract(address: &str, denom: &str) -> LstContract {
+    LstContract {
+        address: Addr::unchecked(address),
+        denom: denom.to_string(),
+    }
+}
+
+fn lst_contracts(deps: Deps) -> Vec<LstContract> {
+    from_json(
+        query(
+            deps,
+            mock_env(),
+            QueryMsg::LstContracts {
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap()
+}
+
+#[test]
+fn add_and_remove_lst_contract() {
+    let mut deps = init();
+    assert_eq!(
+        vec![lst_contract(LST_CONTRACT, DENOM)],
+        lst_contracts(deps.as_ref())
+    );
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(LST_CONTRACT, &[]),
+        ExecuteMsg::AddLstContract {
+            address: OTHER_LST_CONTRACT.to_string(),
+            denom: OTHER_DENOM.to_string(),
+        },
+    )
+    .unwrap_err();
+    assert_eq!(ContractError::Admin(AdminError::NotAdmin {}), err);
+
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(ADMIN, &[]),
+        ExecuteMsg::AddLstContract {
+            address: OTHER_LST_CONTRACT.to_string(),
+            denom: OTHER_DENOM.to_string(),
+        },
+    )
+    .unwrap();
+    assert_eq!(
+        vec![
+            lst_contract(LST_CONTRACT, DENOM),
+            lst_contract(OTHER_LST_CONTRACT, OTHER_DENOM)
+        ],
+        lst_contracts(deps.as_ref())
+    );
+
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(ADMIN, &[]),
+        ExecuteMsg::RemoveLstContract {
+            address: LST_CONTRACT.to_string(),
+        },
+    )
+    .unwrap();
+    assert_eq!(
+        vec![lst_contract(OTHER_LST_CONTRACT, OTHER_DENOM)],
+        lst_contracts(deps.as_ref())
+    );
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(ADMIN, &[]),
+        ExecuteMsg::RemoveLstContract {
+            address: LST_CONTRACT.to_string(),
+        },
+    )
+    .unwrap_err();
+    assert!(matches!(err, ContractError::LstContractNotFound { .. }));
+}
+
+#[test]
+fn add_lst_contract_with_invalid_denom_fails() {
+    let mut deps = init();
+    for denom in ["", "1denom", "factory/lst_contract/umilk TIA"] {
+        let err = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(ADMIN, &[]),
+            ExecuteMsg::AddLstContract {
+                address: OTHER_LST_CONTRACT.to_string(),
+                denom: denom.to_string(),
+            },
+        )
+        .unwrap_err();
+        assert_eq!(
+            ContractError::InvalidDenom {
+                denom: denom.to_string()
+            },
+            err
+        );
+    }
+}
+
+#[test]
+fn update_admin() {
+    let mut deps = init();
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(ADMIN, &[]),
+        ExecuteMsg::UpdateAdmin {
+            admin: "new_admin".to_string(),
+        },
+    )
+    .unwrap();
+    assert_eq!(
+        vec![Event::from(MilkyWayEvent::UpdateAdmin {
+            admin: "new_admin".to_string(),
+        })],
+        res.events
+    );
+
+    let res: AdminResponse =
+        from_json(query(deps.as_ref(), mock_env(), QueryMsg::Admin {}).unwrap()).unwrap();
+    assert_eq!(Some("new_admin".to_string()), res.admin);
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(ADMIN, &[]),
+        ExecuteMsg::UpdateAdmin {
+            admin: ADMIN.to_string(),
+        },
+    )
+    .unwrap_err();
+    assert_eq!(ContractError::Admin(AdminError::NotAdmin {}), err);
+}
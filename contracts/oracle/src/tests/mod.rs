@@ -0,0 +1,4 @@
+mod lst_contracts_tests;
+mod post_rates_tests;
+mod query_tests;
+mod test_helper;
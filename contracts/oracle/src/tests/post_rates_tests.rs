@@ -0,0 +1,195 @@
+use crate::contract::execute;
+use crate::error::ContractError;
+use crate::msg::ExecuteMsg;
+use crate::state::{Rates, LATEST_RATES, MAX_PRUNED_RATES, RATES_HISTORY, RATES_HISTORY_HEIGHTS};
+use crate::tests::test_helper::{
+    init, post_rates, ADMIN, DENOM, LST_CONTRACT, OTHER_DENOM, OTHER_LST_CONTRACT,
+};
+
+use cosmwasm_std::testing::{mock_env, mock_info};
+use cosmwasm_std::{Addr, Decimal, Order, OwnedDeps};
+
+#[test]
+fn post_rates_stores_latest_and_history() {
+    let mut deps = init();
+    let mut env = mock_env();
+    post_rates(&mut deps, env.clone(), "0.9", "1.1");
+
+    env.block.time = env.block.time.plus_seconds(3_600);
+    env.block.height += 600;
+    post_rates(&mut deps, env.clone(), "0.8", "1.25");
+
+    let latest = Rates {
+        denom: DENOM.to_string(),
+        purchase_rate: Decimal::percent(80),
+        redemption_rate: Decimal::percent(125),
+        update_time: env.block.time.seconds(),
+        update_height: env.block.height,
+        index: 0,
+        poster: Addr::unchecked(LST_CONTRACT),
+    };
+    assert_eq!(latest, LATEST_RATES.load(&deps.storage, DENOM).unwrap());
+    assert_eq!(
+        latest,
+        RATES_HISTORY
+            .load(&deps.storage, (DENOM, env.block.height, 0))
+            .unwrap()
+    );
+    assert_eq!(
+        Decimal::percent(110),
+        RATES_HISTORY
+            .load(&deps.storage, (DENOM, mock_env().block.height, 0))
+            .unwrap()
+            .redemption_rate
+    );
+}
+
+#[test]
+fn expired_rates_are_pruned() {
+    let mut deps = init();
+    let mut env = mock_env();
+    let first_height = env.block.height;
+    for _ in 0..MAX_PRUNED_RATES + 2 {
+        post_rates(&mut deps, env.clone(), "0.9", "1.1");
+        env.block.height += 1;
+    }
+
+    let history_len = |deps: &OwnedDeps<_, _, _>| {
+        RATES_HISTORY
+            .sub_prefix(DENOM)
+            .keys(&deps.storage, None, None, Order::Ascending)
+            .count()
+    };
+    assert_eq!(MAX_PRUNED_RATES + 2, history_len(&deps));
+
+    // At most MAX_PRUNED_RATES expired rates are removed on each post
+    env.block.height = first_height + RATES_HISTORY_HEIGHTS + MAX_PRUNED_RATES as u64 + 1;
+    post_rates(&mut deps, env.clone(), "0.9", "1.1");
+    assert_eq!(3, history_len(&deps));
+    assert!(!RATES_HISTORY.has(&deps.storage, (DENOM, first_height, 0)));
+
+    // The remaining expired rates are removed on the next post
+    env.block.height += 1;
+    post_rates(&mut deps, env.clone(), "0.9", "1.1");
+    assert_eq!(2, history_len(&deps));
+    assert!(RATES_HISTORY.has(&deps.storage, (DENOM, env.block.height - 1, 0)));
+}
+
+#[test]
+fn admin_can_post_rates() {
+    let mut deps = init();
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(ADMIN, &[]),
+        ExecuteMsg::PostRates {
+            denom: DENOM.to_string(),
+            purchase_rate: "1".to_string(),
+            redemption_rate: "1".to_string(),
+        },
+    )
+    .unwrap();
+    assert!(LATEST_RATES.has(&deps.storage, DENOM));
+}
+
+#[test]
+fn unauthorized_post_rates_fails() {
+    let mut deps = init();
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("bob", &[]),
+        ExecuteMsg::PostRates {
+            denom: DENOM.to_string(),
+            purchase_rate: "1".to_string(),
+            redemption_rate: "1".to_string(),
+        },
+    )
+    .unwrap_err();
+    assert!(matches!(err, ContractError::Unauthorized { .. }));
+}
+
+#[test]
+fn invalid_rates_fail() {
+    let mut deps = init();
+    for (purchase_rate, redemption_rate) in [("abc", "1"), ("1", "0"), ("1", "-1")] {
+        let err = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(LST_CONTRACT, &[]),
+            ExecuteMsg::PostRates {
+                denom: DENOM.to_string(),
+                purchase_rate: purchase_rate.to_string(),
+                redemption_rate: redemption_rate.to_string(),
+            },
+        )
+        .unwrap_err();
+        assert!(matches!(err, ContractError::InvalidRate { .. }));
+    }
+}
+
+#[test]
+fn rates_posted_in_the_same_block_are_all_recorded() {
+    let mut deps = init();
+    post_rates(&mut deps, mock_env(), "0.9", "1.1");
+    post_rates(&mut deps, mock_env(), "0.8", "1.25");
+
+    let height = mock_env().block.height;
+    let first = RATES_HISTORY
+        .load(&deps.storage, (DENOM, height, 0))
+        .unwrap();
+    assert_eq!(Decimal::percent(110), first.redemption_rate);
+    let second = RATES_HISTORY
+        .load(&deps.storage, (DENOM, height, 1))
+        .unwrap();
+    assert_eq!(Decimal::percent(125), second.redemption_rate);
+    assert_eq!(second, LATEST_RATES.load(&deps.storage, DENOM).unwrap());
+}
+
+#[test]
+fn lst_contract_can_only_post_its_denom() {
+    let mut deps = init();
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(ADMIN, &[]),
+        ExecuteMsg::AddLstContract {
+            address: OTHER_LST_CONTRACT.to_string(),
+            denom: OTHER_DENOM.to_string(),
+        },
+    )
+    .unwrap();
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(OTHER_LST_CONTRACT, &[]),
+        ExecuteMsg::PostRates {
+            denom: DENOM.to_string(),
+            purchase_rate: "1".to_string(),
+            redemption_rate: "1".to_string(),
+        },
+    )
+    .unwrap_err();
+    assert_eq!(
+        ContractError::DenomNotAllowed {
+            sender: OTHER_LST_CONTRACT.to_string(),
+            denom: DENOM.to_string(),
+        },
+        err
+    );
+    assert!(!LATEST_RATES.has(&deps.storage, DENOM));
+
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(OTHER_LST_CONTRACT, &[]),
+        ExecuteMsg::PostRates {
+            denom: OTHER_DENOM.to_string(),
+            purchase_rate: "1".to_string(),
+            redemption_rate: "1".to_string(),
+        },
+    )
+    .unwrap();
+    assert!(LATEST_RATES.has(&deps.storage, OTHER_DENOM));
+}
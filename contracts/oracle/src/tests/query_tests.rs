@@ -0,0 +1,114 @@
+use crate::contract::query;
+use crate::error::ContractError;
+use crate::msg::{PurchaseRateResponse, QueryMsg, RedemptionRateResponse};
+use crate::state::Rates;
+use crate::tests::test_helper::{init, post_rates, DENOM};
+
+use cosmwasm_std::testing::mock_env;
+use cosmwasm_std::{from_json, Decimal};
+
+#[test]
+fn query_rates() {
+    let mut deps = init();
+
+    let err = query(
+        deps.as_ref(),
+        mock_env(),
+        QueryMsg::RedemptionRate {
+            denom: DENOM.to_string(),
+        },
+    )
+    .unwrap_err();
+    assert!(matches!(err, ContractError::RatesNotFound { .. }));
+
+    post_rates(&mut deps, mock_env(), "0.8", "1.25");
+
+    let res: RedemptionRateResponse = from_json(
+        query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::RedemptionRate {
+                denom: DENOM.to_string(),
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(
+        RedemptionRateResponse {
+            redemption_rate: Decimal::percent(125),
+            update_time: mock_env().block.time.seconds(),
+        },
+        res
+    );
+
+    let res: PurchaseRateResponse = from_json(
+        query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::PurchaseRate {
+                denom: DENOM.to_string(),
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(Decimal::percent(80), res.purchase_rate);
+
+    let res: Vec<Rates> = from_json(
+        query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::AllLatestRates {
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(1, res.len());
+    assert_eq!(DENOM, res[0].denom);
+}
+
+#[test]
+fn query_rates_history() {
+    let mut deps = init();
+    let mut env = mock_env();
+    for redemption_rate in ["1.1", "1.2", "1.3"] {
+        post_rates(&mut deps, env.clone(), "1", redemption_rate);
+        env.block.time = env.block.time.plus_seconds(100);
+        env.block.height += 20;
+    }
+
+    let history = |start_before: Option<(u64, u32)>, limit: Option<u32>| -> Vec<Decimal> {
+        let res: Vec<Rates> = from_json(
+            query(
+                deps.as_ref(),
+                mock_env(),
+                QueryMsg::RatesHistory {
+                    denom: DENOM.to_string(),
+                    start_before,
+                    limit,
+                },
+            )
+            .unwrap(),
+        )
+        .unwrap();
+        res.into_iter().map(|rates| rates.redemption_rate).collect()
+    };
+
+    // Most recent first
+    assert_eq!(
+        vec![
+            Decimal::percent(130),
+            Decimal::percent(120),
+            Decimal::percent(110)
+        ],
+        history(None, None)
+    );
+    assert_eq!(
+        vec![Decimal::percent(120)],
+        history(Some((mock_env().block.height + 40, 0)), Some(1))
+    );
+}
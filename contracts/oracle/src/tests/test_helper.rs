@@ -0,0 +1,47 @@
+use crate::contract::{execute, instantiate};
+use crate::msg::{ExecuteMsg, InstantiateMsg, UnsafeLstContract};
+
+use cosmwasm_std::testing::{
+    mock_dependencies, mock_env, mock_info, MockApi, MockQuerier, MockStorage,
+};
+use cosmwasm_std::{Env, OwnedDeps};
+
+pub static ADMIN: &str = "admin";
+pub static LST_CONTRACT: &str = "lst_contract";
+pub static OTHER_LST_CONTRACT: &str = "other_lst_contract";
+pub static DENOM: &str = "factory/lst_contract/umilkTIA";
+pub static OTHER_DENOM: &str = "factory/other_lst_contract/umilkINIT";
+
+pub fn init() -> OwnedDeps<MockStorage, MockApi, MockQuerier> {
+    let mut deps = mock_dependencies();
+
+    let msg = InstantiateMsg {
+        admin_address: ADMIN.to_string(),
+        lst_contracts: Some(vec![UnsafeLstContract {
+            address: LST_CONTRACT.to_string(),
+            denom: DENOM.to_string(),
+        }]),
+    };
+    instantiate(deps.as_mut(), mock_env(), mock_info(ADMIN, &[]), msg).unwrap();
+
+    deps
+}
+
+pub fn post_rates(
+    deps: &mut OwnedDeps<MockStorage, MockApi, MockQuerier>,
+    env: Env,
+    purchase_rate: &str,
+    redemption_rate: &str,
+) {
+    execute(
+        deps.as_mut(),
+        env,
+        mock_info(LST_CONTRACT, &[]),
+        ExecuteMsg::PostRates {
+            denom: DENOM.to_string(),
+            purchase_rate: purchase_rate.to_string(),
+            redemption_rate: redemption_rate.to_string(),
+        },
+    )
+    .unwrap();
+}
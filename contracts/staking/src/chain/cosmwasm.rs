@@ -0,0 +1,54 @@
+use cosmwasm_std::{
+    from_json, BankMsg, Binary, Coin, CosmosMsg, IbcMsg, IbcTimeout, StdError, Timestamp, WasmMsg,
+};
+
+use super::{Chain, IbcTransferMsg};
+use crate::types::IbcTransferMemo;
+
+/// Chains without any module specific message, only the standard
+/// `cosmwasm_std` messages are used.
+pub struct CosmWasm;
+
+impl Chain for CosmWasm {
+    fn bank_send(
+        _from_address: String,
+        to_address: String,
+        amount: Vec<Coin>,
+    ) -> Result<CosmosMsg, StdError> {
+        Ok(BankMsg::Send { to_address, amount }.into())
+    }
+
+    // `IbcMsg::Transfer` has no memo in cosmwasm_std 1.5: the ibc-hooks callback
+    // isn't registered, so the chain must report the packet lifecycle through the
+    // `IBCLifecycleComplete` sudo message, and forwarded transfers are rejected.
+    fn ibc_transfer(msg: IbcTransferMsg) -> Result<CosmosMsg, StdError> {
+        let memo: IbcTransferMemo = from_json(msg.memo.as_bytes())?;
+        if memo.forward.is_some() {
+            return Err(StdError::generic_err(
+                "packet forwarding is not supported by IbcMsg::Transfer",
+            ));
+        }
+
+        Ok(IbcMsg::Transfer {
+            channel_id: msg.source_channel,
+            to_address: msg.receiver,
+            amount: msg.token,
+            timeout: IbcTimeout::with_timestamp(Timestamp::from_nanos(msg.timeout_timestamp)),
+        }
+        .into())
+    }
+
+    fn execute_contract(
+        _sender: String,
+        contract: String,
+        msg: Binary,
+        funds: Vec<Coin>,
+    ) -> Result<CosmosMsg, StdError> {
+        Ok(WasmMsg::Execute {
+            contract_addr: contract,
+            msg,
+            funds,
+        }
+        .into())
+    }
+}
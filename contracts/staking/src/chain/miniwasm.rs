@@ -0,0 +1,69 @@
+use cosmwasm_std::{Binary, Coin, CosmosMsg, StdError, WasmMsg};
+use initia_proto::cosmos::bank::v1beta1::MsgSend;
+use initia_proto::cosmos::base::v1beta1::Coin as InitiaCoin;
+use initia_proto::ibc::applications::transfer::v1::MsgTransfer;
+use initia_proto::traits::MessageExt;
+
+use super::{Chain, IbcTransferMsg};
+
+pub struct Miniwasm;
+
+fn initia_coin(coin: Coin) -> InitiaCoin {
+    InitiaCoin {
+        denom: coin.denom,
+        amount: coin.amount.to_string(),
+    }
+}
+
+impl Chain for Miniwasm {
+    fn bank_send(
+        from_address: String,
+        to_address: String,
+        amount: Vec<Coin>,
+    ) -> Result<CosmosMsg, StdError> {
+        let bytes = MsgSend {
+            from_address,
+            to_address,
+            amount: amount.into_iter().map(initia_coin).collect(),
+        }
+        .to_bytes()
+        .map_err(|_| StdError::generic_err("Failed to serialize MsgSend"))?;
+        Ok(CosmosMsg::Stargate {
+            type_url: "/cosmos.bank.v1beta1.MsgSend".to_string(),
+            value: Binary::from(bytes),
+        })
+    }
+
+    fn ibc_transfer(msg: IbcTransferMsg) -> Result<CosmosMsg, StdError> {
+        let bytes = MsgTransfer {
+            source_port: "transfer".to_string(),
+            source_channel: msg.source_channel,
+            token: Some(initia_coin(msg.token)),
+            sender: msg.sender,
+            receiver: msg.receiver,
+            timeout_height: None,
+            timeout_timestamp: msg.timeout_timestamp,
+            memo: msg.memo,
+        }
+        .to_bytes()
+        .map_err(|_| StdError::generic_err("Failed to serialize MsgTransfer"))?;
+        Ok(CosmosMsg::Stargate {
+            type_url: "/ibc.applications.transfer.v1.MsgTransfer".to_string(),
+            value: Binary::from(bytes),
+        })
+    }
+
+    fn execute_contract(
+        _sender: String,
+        contract: String,
+        msg: Binary,
+        funds: Vec<Coin>,
+    ) -> Result<CosmosMsg, StdError> {
+        Ok(WasmMsg::Execute {
+            contract_addr: contract,
+            msg,
+            funds,
+        }
+        .into())
+    }
+}
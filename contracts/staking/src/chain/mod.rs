@@ -0,0 +1,51 @@
+use cosmwasm_std::{Binary, Coin, CosmosMsg, StdError};
+
+#[cfg(feature = "cosmwasm-chain")]
+mod cosmwasm;
+#[cfg(all(feature = "miniwasm", not(feature = "cosmwasm-chain")))]
+mod miniwasm;
+#[cfg(not(any(feature = "miniwasm", feature = "cosmwasm-chain")))]
+mod osmosis;
+
+/// Chain where the contract is deployed, selected independently of the
+/// [`crate::tokenfactory`] backend: `cosmwasm-chain` takes precedence over
+/// `miniwasm`, Osmosis is used otherwise.
+#[cfg(feature = "cosmwasm-chain")]
+pub type Backend = cosmwasm::CosmWasm;
+#[cfg(all(feature = "miniwasm", not(feature = "cosmwasm-chain")))]
+pub type Backend = miniwasm::Miniwasm;
+#[cfg(not(any(feature = "miniwasm", feature = "cosmwasm-chain")))]
+pub type Backend = osmosis::Osmosis;
+
+/// ICS20 transfer sent from the contract.
+#[derive(Clone, Debug, PartialEq)]
+pub struct IbcTransferMsg {
+    pub source_channel: String,
+    pub token: Coin,
+    pub sender: String,
+    pub receiver: String,
+    /// Timeout as nanoseconds since the UNIX epoch.
+    pub timeout_timestamp: u64,
+    pub memo: String,
+}
+
+/// Builds the bank, IBC transfer and contract execute messages in the form
+/// supported by a chain.
+pub trait Chain {
+    fn bank_send(
+        from_address: String,
+        to_address: String,
+        amount: Vec<Coin>,
+    ) -> Result<CosmosMsg, StdError>;
+
+    /// The sequence of the packet must be returned in the reply data as a
+    /// `MsgTransferResponse`.
+    fn ibc_transfer(msg: IbcTransferMsg) -> Result<CosmosMsg, StdError>;
+
+    fn execute_contract(
+        sender: String,
+        contract: String,
+        msg: Binary,
+        funds: Vec<Coin>,
+    ) -> Result<CosmosMsg, StdError>;
+}
@@ -0,0 +1,53 @@
+use cosmwasm_std::{Binary, Coin, CosmosMsg, StdError};
+use osmosis_std::types::cosmos::bank::v1beta1::MsgSend;
+use osmosis_std::types::cosmos::base::v1beta1::Coin as OsmosisCoin;
+use osmosis_std::types::cosmwasm::wasm::v1::MsgExecuteContract;
+use osmosis_std::types::ibc::applications::transfer::v1::MsgTransfer;
+
+use super::{Chain, IbcTransferMsg};
+
+pub struct Osmosis;
+
+impl Chain for Osmosis {
+    fn bank_send(
+        from_address: String,
+        to_address: String,
+        amount: Vec<Coin>,
+    ) -> Result<CosmosMsg, StdError> {
+        Ok(MsgSend {
+            from_address,
+            to_address,
+            amount: amount.into_iter().map(OsmosisCoin::from).collect(),
+        }
+        .into())
+    }
+
+    fn ibc_transfer(msg: IbcTransferMsg) -> Result<CosmosMsg, StdError> {
+        Ok(MsgTransfer {
+            source_channel: msg.source_channel,
+            source_port: "transfer".to_string(),
+            token: Some(OsmosisCoin::from(msg.token)),
+            receiver: msg.receiver,
+            sender: msg.sender,
+            timeout_height: None,
+            timeout_timestamp: msg.timeout_timestamp,
+            memo: msg.memo,
+        }
+        .into())
+    }
+
+    fn execute_contract(
+        sender: String,
+        contract: String,
+        msg: Binary,
+        funds: Vec<Coin>,
+    ) -> Result<CosmosMsg, StdError> {
+        Ok(MsgExecuteContract {
+            sender,
+            contract,
+            msg: msg.to_vec(),
+            funds: funds.into_iter().map(OsmosisCoin::from).collect(),
+        }
+        .into())
+    }
+}
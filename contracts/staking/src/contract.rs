@@ -1,6 +1,10 @@
 use crate::execute::{
-    circuit_breaker, execute_submit_batch, fee_withdraw, handle_ibc_reply, receive_rewards,
-    receive_unstaked_tokens, recover, resume_contract, slash_batches, update_config,
+    circuit_breaker, complete_denom_admin_transfer, execute_submit_batch, fee_withdraw,
+    handle_hook_reply, handle_ibc_reply, handle_oracle_update_reply, import_batches, push_rates,
+    receive_rewards, receive_unstaked_tokens, record_slash, recover, remove_destination_chain,
+    resume_contract, retry_failed_transfers, revoke_denom_admin_transfer, set_denom_metadata,
+    set_destination_chain, slash_batches, subscribe_hooks, transfer_denom_admin, unsubscribe_hooks,
+    update_config, withdraw_native_unstakes,
 };
 use crate::helpers::validate_denom;
 use crate::ibc::{receive_ack, receive_timeout};
@@ -8,13 +12,17 @@ use crate::migrations;
 use crate::oracle::OracleInstantiateMsg;
 use crate::query::{
     query_admin, query_all_unstake_requests, query_batch, query_batches, query_batches_by_ids,
-    query_config, query_ibc_queue, query_pending_batch, query_reply_queue, query_state,
+    query_config, query_destination_chains, query_hook_subscriptions, query_ibc_queue,
+    query_insurance_fund, query_insurance_fund_history, query_migration_status,
+    query_oracle_update_failure, query_pending_batch, query_pending_denom_admin_transfer,
+    query_reply_queue, query_slash_history, query_state, query_unallocated_balance,
     query_unstake_requests,
 };
 use crate::state::{
-    assert_not_migrating, Config, State, ADMIN, BATCHES, CONFIG, IBC_WAITING_FOR_REPLY, MIGRATING,
-    PENDING_BATCH_ID, STATE,
+    assert_not_migrating, Config, State, ADMIN, BATCHES, CONFIG, IBC_WAITING_FOR_REPLY,
+    IMPORTING_BATCHES, MIGRATING, PENDING_BATCH_ID, STATE,
 };
+use crate::types::{MAX_HOOK_SUBSCRIPTIONS, MAX_ORACLE_TARGETS};
 use crate::{
     error::ContractError,
     execute::{
@@ -32,15 +40,34 @@ use cosmwasm_std::{
 use cosmwasm_std::{wasm_instantiate, SubMsg, Timestamp};
 use cw2::set_contract_version;
 use cw_utils::must_pay;
+use milky_way::events::MilkyWayEvent;
 use milky_way::staking::Batch;
 use milky_way::utils::{validate_address, validate_addresses};
 use semver::Version;
+#[cfg(feature = "cw20")]
+use {crate::msg::ReceiveMsg, cosmwasm_std::from_json};
 
 // Version information for migration
 pub const CONTRACT_NAME: &str = env!("CARGO_PKG_NAME");
 pub const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+/// Default timeout of the IBC transfers, used if `ProtocolChainConfig::ibc_timeout` is not set.
 pub const IBC_TIMEOUT: Timestamp = Timestamp::from_nanos(1000000000000);
 pub const INSTANTIATE_ORACLE_CONTRACT_REPLY_ID: u64 = 1;
+/// Reply id of the CW20 token instantiation, below the hook callback ids and
+/// out of the range of the IBC transfer ids.
+pub const INSTANTIATE_CW20_REPLY_ID: u64 = HOOK_REPLY_ID - MAX_HOOK_SUBSCRIPTIONS as u64;
+/// Reply id of the update of the first oracle target, the following targets use
+/// the previous ids. They're out of the range of the IBC transfer ids.
+pub const UPDATE_ORACLE_REPLY_ID: u64 = u64::MAX;
+/// Gas limit of the oracle updates, so an oracle running out of gas doesn't
+/// consume the gas of the whole transaction.
+pub const UPDATE_ORACLE_GAS_LIMIT: u64 = 500_000;
+/// Reply id of the callback of the first hook subscription, the following
+/// subscriptions use the previous ids, below the oracle update ids.
+pub const HOOK_REPLY_ID: u64 = UPDATE_ORACLE_REPLY_ID - MAX_ORACLE_TARGETS as u64;
+/// Gas limit of the hook callbacks, so a faulty subscriber doesn't consume
+/// the gas of the whole transaction.
+pub const HOOK_GAS_LIMIT: u64 = 500_000;
 
 ///////////////////
 /// INSTANTIATE ///
@@ -80,26 +107,47 @@ pub fn instantiate(
         });
     }
 
+    let subdenom = validate_denom(&msg.liquid_stake_token_denom)?;
+    if let Some(metadata) = &msg.denom_metadata {
+        metadata.validate()?;
+    }
+    let liquid_stake_token_denom = match &msg.adopted_denom {
+        Some(adopted_denom) => {
+            adopted_denom.validate()?;
+            // The contract isn't the admin of the adopted denom yet
+            if msg.denom_metadata.is_some() {
+                return Err(ContractError::InvalidDenomMetadata {
+                    reason: "the metadata of an adopted denom can't be set at instantiate"
+                        .to_string(),
+                });
+            }
+            #[cfg(feature = "cw20")]
+            deps.api.addr_validate(&adopted_denom.denom)?;
+            adopted_denom.denom.clone()
+        }
+        // The address of the CW20 token is stored once it has been instantiated
+        None if cfg!(feature = "cw20") => String::new(),
+        None => format!("factory/{0}/{1}", env.contract.address, subdenom),
+    };
+
     let config = Config {
         native_chain_config,
         protocol_chain_config,
         protocol_fee_config,
-        liquid_stake_token_denom: format!(
-            "factory/{0}/{1}",
-            env.contract.address,
-            validate_denom(&msg.liquid_stake_token_denom)?
-        ),
+        liquid_stake_token_denom,
         monitors: validate_addresses(
             &msg.monitors,
             &msg.protocol_chain_config.account_address_prefix,
         )?,
         batch_period: msg.batch_period,
         stopped: true, // we start stopped
+        resume_contract_limits: None,
+        batch_limits: None,
     };
     CONFIG.save(deps.storage, &config)?;
 
     // Init State
-    let state = State {
+    let mut state = State {
         total_native_token: Uint128::zero(),
         total_liquid_stake_token: Uint128::zero(),
         pending_owner: None,
@@ -109,6 +157,13 @@ pub fn instantiate(
         rate: 1u128.into(),
         owner_transfer_min_time: None,
     };
+    if let Some(adopted_denom) = &msg.adopted_denom {
+        state.total_native_token = adopted_denom.total_native_token;
+        state.total_liquid_stake_token = adopted_denom.total_liquid_stake_token;
+        state.total_reward_amount = adopted_denom.total_reward_amount;
+        state.total_fees = adopted_denom.total_fees;
+        IMPORTING_BATCHES.save(deps.storage, &true)?;
+    }
 
     STATE.save(deps.storage, &state)?;
 
@@ -133,25 +188,76 @@ pub fn instantiate(
     };
 
     // Create liquid stake token denom
-    let cosmos_tokenfactory_msg = tokenfactory::create_denom(
-        env.contract.address.to_string(),
-        msg.liquid_stake_token_denom,
-    )?;
+    #[cfg(not(feature = "cw20"))]
+    let create_denom_msgs = if msg.adopted_denom.is_some() {
+        vec![]
+    } else {
+        let mut msgs = vec![SubMsg::new(tokenfactory::create_denom(
+            env.contract.address.to_string(),
+            subdenom,
+        )?)];
+        if let Some(metadata) = msg.denom_metadata {
+            msgs.push(SubMsg::new(tokenfactory::set_denom_metadata(
+                env.contract.address.to_string(),
+                config.liquid_stake_token_denom.clone(),
+                metadata,
+            )?));
+        }
+        msgs
+    };
+    #[cfg(feature = "cw20")]
+    let create_denom_msgs = if msg.adopted_denom.is_some() {
+        vec![]
+    } else {
+        vec![tokenfactory::create_denom(
+            env.contract.address.to_string(),
+            subdenom,
+            msg.denom_metadata.as_ref(),
+            msg.cw20_code_id
+                .ok_or(ContractError::Cw20CodeIdRequired {})?,
+            INSTANTIATE_CW20_REPLY_ID,
+        )?]
+    };
 
+    // The pending batch of an adopted denom continues the batches of the previous deployment
+    let pending_batch_id = msg
+        .adopted_denom
+        .as_ref()
+        .map_or(1, |adopted_denom| adopted_denom.pending_batch_id);
     let pending_batch = Batch::new(
-        1,
+        pending_batch_id,
         Uint128::zero(),
         env.block.time.seconds() + config.batch_period,
     );
 
     // Set pending batch and batches
-    BATCHES.save(deps.storage, 1, &pending_batch)?;
-    PENDING_BATCH_ID.save(deps.storage, &1)?;
+    BATCHES.save(deps.storage, pending_batch_id, &pending_batch)?;
+    PENDING_BATCH_ID.save(deps.storage, &pending_batch_id)?;
 
     let mut response = Response::new()
         .add_attribute("action", "instantiate")
-        .add_attribute("owner", admin)
-        .add_message(cosmos_tokenfactory_msg);
+        .add_attribute("owner", admin.clone())
+        .add_event(
+            MilkyWayEvent::Instantiate {
+                admin: admin.to_string(),
+            }
+            .into(),
+        )
+        .add_submessages(create_denom_msgs);
+
+    if let Some(adopted_denom) = msg.adopted_denom {
+        response = response
+            .add_attribute("adopted_denom", adopted_denom.denom.clone())
+            .add_event(
+                MilkyWayEvent::AdoptDenom {
+                    denom: adopted_denom.denom,
+                    total_native_token: adopted_denom.total_native_token,
+                    total_liquid_stake_token: adopted_denom.total_liquid_stake_token,
+                    pending_batch_id,
+                }
+                .into(),
+            );
+    }
 
     // Add the oracle instantiate message if is defined
     if let Some(msg) = oracle_init_msg {
@@ -180,6 +286,7 @@ pub fn execute(
             mint_to,
             transfer_to_native_chain,
             expected_mint_amount,
+            native_sender,
         } => {
             let payment = must_pay(&info, &config.protocol_chain_config.ibc_token_denom)?;
             execute_liquid_stake(
@@ -190,14 +297,36 @@ pub fn execute(
                 mint_to,
                 transfer_to_native_chain,
                 expected_mint_amount,
+                native_sender,
             )
         }
-        ExecuteMsg::LiquidUnstake {} => {
+        ExecuteMsg::LiquidUnstake { native_sender } => {
             let payment = must_pay(&info, &config.liquid_stake_token_denom)?;
-            execute_liquid_unstake(deps, env, info, payment)
+            execute_liquid_unstake(deps, env, info, payment, native_sender)
+        }
+        #[cfg(feature = "cw20")]
+        ExecuteMsg::Receive(cw20_msg) => {
+            // Only the liquid staking token can be received
+            if info.sender != config.liquid_stake_token_denom {
+                return Err(ContractError::Unauthorized {
+                    sender: info.sender.to_string(),
+                });
+            }
+            let info = MessageInfo {
+                sender: deps.api.addr_validate(&cw20_msg.sender)?,
+                funds: vec![],
+            };
+            match from_json(&cw20_msg.msg)? {
+                ReceiveMsg::LiquidUnstake {} => {
+                    execute_liquid_unstake(deps, env, info, cw20_msg.amount, None)
+                }
+            }
         }
         ExecuteMsg::SubmitBatch {} => execute_submit_batch(deps, env, info),
         ExecuteMsg::Withdraw { batch_id } => execute_withdraw(deps, env, info, batch_id),
+        ExecuteMsg::WithdrawNativeUnstakes { batch_id, limit } => {
+            withdraw_native_unstakes(deps, env, batch_id, limit)
+        }
         ExecuteMsg::AddValidator { new_validator } => {
             execute_add_validator(deps, env, info, new_validator)
         }
@@ -217,6 +346,9 @@ pub fn execute(
             protocol_fee_config,
             monitors,
             batch_period,
+            resume_contract_limits,
+            clear_resume_contract_limits,
+            batch_limits,
         } => update_config(
             deps,
             env,
@@ -226,6 +358,9 @@ pub fn execute(
             protocol_fee_config,
             monitors,
             batch_period,
+            resume_contract_limits,
+            clear_resume_contract_limits,
+            batch_limits,
         ),
         ExecuteMsg::ReceiveRewards {} => receive_rewards(deps, env, info),
         ExecuteMsg::ReceiveUnstakedTokens { batch_id } => {
@@ -236,6 +371,7 @@ pub fn execute(
             total_native_token,
             total_liquid_stake_token,
             total_reward_amount,
+            force,
         } => resume_contract(
             deps,
             env,
@@ -243,8 +379,23 @@ pub fn execute(
             total_native_token,
             total_liquid_stake_token,
             total_reward_amount,
+            force.unwrap_or(false),
         ),
         ExecuteMsg::SlashBatches { new_amounts } => slash_batches(deps, info, new_amounts),
+        ExecuteMsg::RecordSlash {
+            validator,
+            amount,
+            infraction_height,
+            batches,
+        } => record_slash(
+            deps,
+            env,
+            info,
+            validator,
+            amount,
+            infraction_height,
+            batches,
+        ),
         ExecuteMsg::RecoverPendingIbcTransfers {
             paginated,
             selected_packets,
@@ -257,7 +408,32 @@ pub fn execute(
             receiver,
             paginated.unwrap_or(false),
         ),
+        ExecuteMsg::RetryFailedTransfers { limit } => retry_failed_transfers(deps, env, limit),
         ExecuteMsg::FeeWithdraw { amount } => fee_withdraw(deps, env, info, amount),
+        ExecuteMsg::SetDestinationChain {
+            bech32_prefix,
+            channel_id,
+            hop,
+        } => set_destination_chain(deps, info, bech32_prefix, channel_id, hop),
+        ExecuteMsg::RemoveDestinationChain { bech32_prefix } => {
+            remove_destination_chain(deps, info, bech32_prefix)
+        }
+        ExecuteMsg::SetDenomMetadata { metadata } => set_denom_metadata(deps, env, info, metadata),
+        ExecuteMsg::TransferDenomAdmin { new_admin } => {
+            transfer_denom_admin(deps, env, info, new_admin)
+        }
+        ExecuteMsg::RevokeDenomAdminTransfer {} => revoke_denom_admin_transfer(deps, info),
+        ExecuteMsg::CompleteDenomAdminTransfer {} => complete_denom_admin_transfer(deps, env, info),
+        ExecuteMsg::ImportBatches {
+            batches,
+            unstake_requests,
+            finish,
+        } => import_batches(deps, info, batches, unstake_requests, finish),
+        ExecuteMsg::PushRates {} => push_rates(deps, env),
+        ExecuteMsg::SubscribeHooks { address, events } => {
+            subscribe_hooks(deps, info, address, events)
+        }
+        ExecuteMsg::UnsubscribeHooks { address } => unsubscribe_hooks(deps, info, address),
     }
 }
 
@@ -266,7 +442,7 @@ pub fn execute(
 /////////////
 
 #[cfg_attr(not(feature = "library"), entry_point)]
-pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
+pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
     match msg {
         QueryMsg::Config {} => to_json_binary(&query_config(deps)?),
         QueryMsg::State {} => to_json_binary(&query_state(deps)?),
@@ -291,6 +467,25 @@ pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
             to_json_binary(&query_reply_queue(deps, start_after, limit)?)
         }
         QueryMsg::Admin {} => to_json_binary(&query_admin(deps)?),
+        QueryMsg::SlashHistory { start_after, limit } => {
+            to_json_binary(&query_slash_history(deps, start_after, limit)?)
+        }
+        QueryMsg::InsuranceFund {} => to_json_binary(&query_insurance_fund(deps)?),
+        QueryMsg::InsuranceFundHistory { start_after, limit } => {
+            to_json_binary(&query_insurance_fund_history(deps, start_after, limit)?)
+        }
+        QueryMsg::DestinationChains { start_after, limit } => {
+            to_json_binary(&query_destination_chains(deps, start_after, limit)?)
+        }
+        QueryMsg::PendingDenomAdminTransfer {} => {
+            to_json_binary(&query_pending_denom_admin_transfer(deps)?)
+        }
+        QueryMsg::OracleUpdateFailure {} => to_json_binary(&query_oracle_update_failure(deps)?),
+        QueryMsg::HookSubscriptions { start_after, limit } => {
+            to_json_binary(&query_hook_subscriptions(deps, start_after, limit)?)
+        }
+        QueryMsg::MigrationStatus {} => to_json_binary(&query_migration_status(deps)?),
+        QueryMsg::UnallocatedBalance {} => to_json_binary(&query_unallocated_balance(deps, env)?),
     }
 }
 
@@ -347,9 +542,19 @@ pub fn migrate(mut deps: DepsMut, env: Env, msg: MigrateMsg) -> Result<Response,
         }
         MigrateMsg::V1_1_0ToV1_2_0 {} => migrations::v1_2_0::migrate(deps.branch(), env)?,
         MigrateMsg::V1_2_0ToV1_3_0 {} => migrations::v1_3_0::migrate(deps.branch(), env)?,
+        MigrateMsg::V1_3_0ToV1_4_0 {} => migrations::v1_4_0::migrate(deps.branch(), env)?,
+        MigrateMsg::V1_4_0ToV1_5_0 { limit } => {
+            migrations::v1_5_0::migrate(deps.branch(), env, limit)?
+        }
     };
 
-    Ok(migration_response)
+    Ok(migration_response.add_event(
+        MilkyWayEvent::Migrate {
+            from_version: current_version.version,
+            to_version: CONTRACT_VERSION.to_string(),
+        }
+        .into(),
+    ))
 }
 
 /////////////
@@ -357,7 +562,7 @@ pub fn migrate(mut deps: DepsMut, env: Env, msg: MigrateMsg) -> Result<Response,
 /////////////
 
 #[cfg_attr(not(feature = "library"), entry_point)]
-pub fn sudo(deps: DepsMut, _env: Env, msg: SudoMsg) -> Result<Response, ContractError> {
+pub fn sudo(deps: DepsMut, env: Env, msg: SudoMsg) -> Result<Response, ContractError> {
     assert_not_migrating(deps.as_ref())?;
 
     match msg {
@@ -366,9 +571,9 @@ pub fn sudo(deps: DepsMut, _env: Env, msg: SudoMsg) -> Result<Response, Contract
             sequence,
             ack,
             success,
-        }) => receive_ack(deps, channel, sequence, ack, success),
+        }) => receive_ack(deps, env, channel, sequence, ack, success),
         SudoMsg::IBCLifecycleComplete(IBCLifecycleComplete::IBCTimeout { channel, sequence }) => {
-            receive_timeout(deps, channel, sequence)
+            receive_timeout(deps, env, channel, sequence)
         }
     }
 }
@@ -378,10 +583,14 @@ pub fn sudo(deps: DepsMut, _env: Env, msg: SudoMsg) -> Result<Response, Contract
 /////////////
 
 #[cfg_attr(not(feature = "library"), entry_point)]
-pub fn reply(deps: DepsMut, _env: Env, reply: Reply) -> Result<Response, ContractError> {
+pub fn reply(deps: DepsMut, env: Env, reply: Reply) -> Result<Response, ContractError> {
     assert_not_migrating(deps.as_ref())?;
 
-    if reply.id == INSTANTIATE_ORACLE_CONTRACT_REPLY_ID {
+    if reply.id > UPDATE_ORACLE_REPLY_ID - MAX_ORACLE_TARGETS as u64 {
+        handle_oracle_update_reply(deps, env, reply)
+    } else if reply.id > HOOK_REPLY_ID - MAX_HOOK_SUBSCRIPTIONS as u64 {
+        handle_hook_reply(deps, reply)
+    } else if reply.id == INSTANTIATE_ORACLE_CONTRACT_REPLY_ID {
         // Parse the contract instantiate replay
         let instantiate_reply = cw_utils::parse_reply_instantiate_data(reply)
             .map_err(|_| ContractError::InstantiateOracleFailed {})?;
@@ -399,8 +608,37 @@ pub fn reply(deps: DepsMut, _env: Env, reply: Reply) -> Result<Response, Contrac
 
         let response = Response::new()
             .add_attribute("action", "instantiate_oracle_contract")
-            .add_attribute("address", instantiate_reply.contract_address);
+            .add_attribute("address", instantiate_reply.contract_address.clone())
+            .add_event(
+                MilkyWayEvent::InstantiateOracle {
+                    address: instantiate_reply.contract_address,
+                }
+                .into(),
+            );
         Ok(response)
+    } else if cfg!(feature = "cw20") && reply.id == INSTANTIATE_CW20_REPLY_ID {
+        // The CW20 token is only instantiated by the cw20 backend
+        let instantiate_reply = cw_utils::parse_reply_instantiate_data(reply)
+            .map_err(|_| ContractError::InstantiateCw20Failed {})?;
+        let contract_addr = deps
+            .api
+            .addr_validate(&instantiate_reply.contract_address)?;
+
+        // The CW20 token address is used as liquid stake token denom
+        CONFIG.update::<_, StdError>(deps.storage, |mut config| {
+            config.liquid_stake_token_denom = contract_addr.to_string();
+            Ok(config)
+        })?;
+
+        Ok(Response::new()
+            .add_attribute("action", "instantiate_cw20_contract")
+            .add_attribute("address", contract_addr.clone())
+            .add_event(
+                MilkyWayEvent::InstantiateToken {
+                    address: contract_addr.to_string(),
+                }
+                .into(),
+            ))
     } else {
         let ibc_waiting_result = IBC_WAITING_FOR_REPLY.load(deps.storage, reply.id);
         match ibc_waiting_result {
@@ -1,4 +1,4 @@
-use cosmwasm_std::{StdError, Timestamp, Uint128};
+use cosmwasm_std::{Decimal, StdError, Timestamp, Uint128};
 use cw2::VersionError;
 use cw_controllers::AdminError;
 use cw_utils::PaymentError;
@@ -28,6 +28,18 @@ pub enum ContractError {
     #[error("Ownership transfer not ready")]
     OwnershipTransferNotReady { time_to_claim: Timestamp },
 
+    #[error("No pending denom admin transfer")]
+    NoPendingDenomAdminTransfer {},
+
+    #[error("Denom admin transfer not ready")]
+    DenomAdminTransferNotReady { time_to_complete: Timestamp },
+
+    #[error("The liquid stake token admin has been transferred to another account")]
+    DenomAdminTransferred {},
+
+    #[error("Batch {batch_id} has pending unstake requests, it must be submitted before the denom admin transfer")]
+    PendingUnstakeRequests { batch_id: u64 },
+
     #[error("Payment error: {0}")]
     Payment(#[from] PaymentError),
 
@@ -43,6 +55,9 @@ pub enum ContractError {
     #[error("Validator already exists")]
     DuplicateValidator { validator: String },
 
+    #[error("Batch {batch_id} is slashed more than once")]
+    DuplicateSlashBatch { batch_id: u64 },
+
     #[error("Validator not found")]
     ValidatorNotFound { validator: String },
 
@@ -73,15 +88,6 @@ pub enum ContractError {
     #[error("Batch {batch_id} don't have the expected native amount")]
     BatchWithoutExpectedNativeAmount { batch_id: u64 },
 
-    #[error(
-        "Received wrong batch amount, batch_id {batch_id} expected {expected}, got {received}"
-    )]
-    ReceivedWrongBatchAmount {
-        batch_id: u64,
-        expected: Uint128,
-        received: Uint128,
-    },
-
     #[error("The tokens in this batch have already been claimed")]
     TokensAlreadyClaimed { batch_id: u64 },
 
@@ -103,6 +109,15 @@ pub enum ContractError {
     #[error("No inflight packages to recover")]
     NoInflightPackets {},
 
+    #[error("Destination chain {bech32_prefix} not found")]
+    DestinationChainNotFound { bech32_prefix: String },
+
+    #[error("Hook subscription of {address} not found")]
+    HookSubscriptionNotFound { address: String },
+
+    #[error("A hook subscription must include at least one event")]
+    EmptyHookEvents {},
+
     #[error("Can't recover packet {id} because is not failed or timed out")]
     InvalidPacketStatus { id: u64 },
 
@@ -168,6 +183,12 @@ pub enum ContractError {
 
     #[error("DAO treasury fee can't be higher then 100000")]
     InvalidDaoTreasuryFee {},
+    #[error("A keeper bounty can't be paid when the fees are sent to a treasury address")]
+    KeeperBountyWithTreasury {},
+
+    #[error("The DAO treasury fee and the insurance fund fee add up to {total_fee}, which must be lower then 100000")]
+    InvalidTotalFee { total_fee: Uint128 },
+
     #[error("The field '{field_name}' has a value of {value}, which exceeds the maximum allowed value of {max}.")]
     ValueTooBig {
         field_name: String,
@@ -177,4 +198,63 @@ pub enum ContractError {
 
     #[error("Oracle contract instantiation failed")]
     InstantiateOracleFailed {},
+
+    #[error("Invalid denom metadata: {reason}")]
+    InvalidDenomMetadata { reason: String },
+
+    #[error("CW20 contract instantiation failed")]
+    InstantiateCw20Failed {},
+
+    #[error("The cw20 backend requires a cw20_code_id")]
+    Cw20CodeIdRequired {},
+
+    #[error("The liquid stake token can't be transferred over IBC")]
+    IbcTransferNotSupported {},
+
+    #[error("Can't slash {slashed} from {target} since it only has {available}")]
+    SlashExceedsAvailable {
+        target: String,
+        available: Uint128,
+        slashed: Uint128,
+    },
+
+    #[error("The provided {field} {value} deviates from {reference} more than {max_deviation}, use force to resume anyway")]
+    ResumeContractLimitExceeded {
+        field: String,
+        reference: String,
+        value: String,
+        max_deviation: Decimal,
+    },
+
+    #[error("Invalid resume contract limits: {reason}")]
+    InvalidResumeContractLimits { reason: String },
+
+    #[error("The maximum batch size must be greater than zero and at least the minimum liquid stake amount")]
+    InvalidMaxBatchSize {},
+
+    #[error("An unstake request can't be split across more than {max} batches")]
+    TooManyRolledOverBatches { max: u64 },
+
+    #[error("The IBC timeout must be greater than zero and at most 7 days")]
+    InvalidIbcTimeout {},
+
+    #[error("Invalid IBC retry policy: {reason}")]
+    InvalidIbcRetryPolicy { reason: String },
+
+    #[error("No failed oracle update to push")]
+    NoFailedOracleUpdate {},
+
+    #[error("Invalid adopted denom: {reason}")]
+    InvalidAdoptedDenom { reason: String },
+
+    #[error(
+        "Batches can only be imported before the first resume of a contract with an adopted denom"
+    )]
+    ImportClosed {},
+
+    #[error("Invalid imported batch {id}: {reason}")]
+    InvalidImportedBatch { id: u64, reason: String },
+
+    #[error("The import of the batches must be finished before resuming the contract")]
+    ImportNotFinished {},
 }
@@ -1,64 +1,113 @@
-use std::collections::BTreeMap;
-
-use crate::contract::IBC_TIMEOUT;
+use crate::ack::MsgTransferResponse;
+use crate::chain::{Backend, Chain, IbcTransferMsg};
+use crate::contract::{
+    HOOK_GAS_LIMIT, HOOK_REPLY_ID, IBC_TIMEOUT, UPDATE_ORACLE_GAS_LIMIT, UPDATE_ORACLE_REPLY_ID,
+};
 use crate::error::{ContractError, ContractResult};
 use crate::helpers::{
-    compute_mint_amount, compute_unbond_amount, dedup_vec, get_rates, paginate_map,
-    validate_ibc_denom,
+    compute_deviation, compute_mint_amount, compute_unbond_amount, dedup_vec,
+    derive_intermediate_sender, get_rates, validate_ibc_denom,
+};
+use crate::hooks::{HookExecuteMsg, HookMsg};
+use crate::oracle::{Oracle, PriceFeedMsg, UpdateRateMsg};
+use crate::state::{
+    ibc::{IBCTransfer, IBCTransferRetry, IbcHop, IbcRoute, PacketLifecycleStatus},
+    update_insurance_fund, BatchLimits, Config, DenomAdminTransfer, DestinationChain, HookEvent,
+    HookSubscription, IbcRetryPolicy, IbcWaitingForReply, InsuranceFundOperation, OracleAdapter,
+    OracleUpdateFailure, ResumeContractLimits, State, ADMIN, BATCHES, BATCH_CLAIMABLE,
+    BATCH_INSURANCE_COVERAGE, CONFIG, DENOM_ADMIN_TRANSFERRED, DESTINATION_CHAINS,
+    HOOK_SUBSCRIPTIONS, IBC_WAITING_FOR_REPLY, IMPORTED_REQUESTS_TOTALS, IMPORTING_BATCHES,
+    INFLIGHT_PACKETS, INFLIGHT_PACKET_RETRIES, INSURANCE_FUND_BALANCE, LAST_KEEPER_BOUNTY_TIME,
+    NATIVE_UNSTAKE_REQUESTS, ORACLE_TARGET_LAST_UPDATE, ORACLE_UPDATE_FAILURE, PENDING_BATCH_ID,
+    PENDING_DENOM_ADMIN_TRANSFER, RETRIES_WAITING_FOR_REPLY, SLASH_EVENTS, STATE,
+    STOPPED_REDEMPTION_RATE, TOTAL_CLAIMABLE, TOTAL_INSURANCE_COVERAGE,
+    UNBALANCED_IMPORTED_BATCHES,
 };
-use crate::oracle::Oracle;
 use crate::state::{
-    ibc::{IBCTransfer, PacketLifecycleStatus},
-    Config, IbcWaitingForReply, State, ADMIN, BATCHES, CONFIG, IBC_WAITING_FOR_REPLY,
-    INFLIGHT_PACKETS, PENDING_BATCH_ID, STATE,
+    new_unstake_request, remove_inflight_packet, remove_unstake_request, save_inflight_packet,
+    unstake_requests, update_total, SlashEvent, UnstakeRequest,
 };
-use crate::state::{new_unstake_request, remove_unstake_request, unstake_requests, UnstakeRequest};
 use crate::tokenfactory;
 use crate::types::{
-    BatchExpectedAmount, UnsafeNativeChainConfig, UnsafeProtocolChainConfig,
-    UnsafeProtocolFeeConfig,
+    BatchExpectedAmount, BatchSlashAmount, DenomMetadata, IbcTransferMemo, ImportedUnstakeRequest,
+    PacketForward, UnsafeNativeChainConfig, UnsafeProtocolChainConfig, UnsafeProtocolFeeConfig,
+    MAX_HOOK_SUBSCRIPTIONS, MAX_ROLLED_OVER_BATCHES,
 };
 use cosmwasm_std::{
-    ensure, Coin, CosmosMsg, Deps, DepsMut, Env, IbcTimeout, MessageInfo, Order, ReplyOn, Response,
-    SubMsg, SubMsgResponse, SubMsgResult, Timestamp, Uint128,
+    coins, ensure, to_json_binary, to_json_string, Addr, Coin, CosmosMsg, Decimal, Deps, DepsMut,
+    Empty, Env, Event, IbcTimeout, MessageInfo, Order, Reply, ReplyOn, Response, StdResult,
+    Storage, SubMsg, SubMsgResponse, SubMsgResult, Timestamp, Uint128,
 };
 use cw_utils::PaymentError;
+use milky_way::events::MilkyWayEvent;
 use milky_way::staking::{Batch, BatchStatus};
-use milky_way::utils::{validate_address, validate_addresses};
-use osmosis_std::types::cosmos::bank::v1beta1::MsgSend;
-use osmosis_std::types::cosmos::base::v1beta1::Coin as OsmosisCoin;
-use osmosis_std::types::cosmwasm::wasm::v1::MsgExecuteContract;
-use osmosis_std::types::ibc::applications::transfer::v1::MsgTransfer;
-use osmosis_std::types::ibc::applications::transfer::v1::MsgTransferResponse;
+use milky_way::utils::{
+    validate_address, validate_address_prefix, validate_addresses, validate_ibc_channel,
+};
 use prost::Message;
 
 const FEE_RATE_DENOMINATOR: u64 = 100_000;
 
+/// Maximum number of native chain unstake requests handled
+/// when a batch is received.
+pub const NATIVE_WITHDRAW_LIMIT: usize = 30;
+
+/// Delay before a scheduled transfer of the liquid stake token admin can be completed.
+pub const DENOM_ADMIN_TRANSFER_DELAY: u64 = 60 * 60 * 24 * 7; // 7 days
+
 pub fn ibc_transfer_msg(
     deps: &Deps,
     env: &Env,
     receiver: impl Into<String>,
     token: Coin,
-) -> Result<MsgTransfer, ContractError> {
+    route: Option<&IbcRoute>,
+) -> Result<IbcTransferMsg, ContractError> {
     let config: Config = CONFIG.load(deps.storage)?;
 
     if config.protocol_chain_config.ibc_channel_id.is_empty() {
         return Err(ContractError::IbcChannelNotFound {});
     }
 
+    // Transfers routed through an intermediate chain are sent to the hop
+    // receiver, which forwards them to the final receiver.
+    let mut receiver: String = receiver.into();
+    let mut forward = None;
+    let source_channel = match route {
+        Some(route) => {
+            if let Some(hop) = &route.hop {
+                forward = Some(PacketForward {
+                    receiver,
+                    port: "transfer".to_string(),
+                    channel: hop.channel_id.clone(),
+                    timeout: None,
+                    retries: None,
+                });
+                receiver = hop.receiver.clone();
+            }
+            route.channel_id.clone()
+        }
+        None => config.protocol_chain_config.ibc_channel_id,
+    };
+
+    let ibc_timeout = config
+        .protocol_chain_config
+        .ibc_timeout
+        .map_or(IBC_TIMEOUT, Timestamp::from_seconds);
     let timeout = IbcTimeout::with_timestamp(Timestamp::from_nanos(
-        env.block.time.nanos() + IBC_TIMEOUT.nanos(),
+        env.block.time.nanos() + ibc_timeout.nanos(),
     ));
+    let memo = IbcTransferMemo {
+        ibc_callback: env.contract.address.to_string(),
+        forward,
+    };
 
-    let ibc_msg = MsgTransfer {
-        source_channel: config.protocol_chain_config.ibc_channel_id,
-        source_port: "transfer".to_string(),
-        token: Some(OsmosisCoin::from(token)),
-        receiver: receiver.into(),
+    let ibc_msg = IbcTransferMsg {
+        source_channel,
+        token,
         sender: env.contract.address.to_string(),
-        timeout_height: None,
+        receiver,
         timeout_timestamp: timeout.timestamp().unwrap().nanos(),
-        memo: format!("{{\"ibc_callback\":\"{}\"}}", env.contract.address),
+        memo: to_json_string(&memo)?,
     };
 
     Ok(ibc_msg)
@@ -70,9 +119,17 @@ fn ibc_transfer_sub_msg(
     receiver: impl Into<String>,
     amount: Coin,
     sub_msg_id: Option<u64>,
+    route: Option<IbcRoute>,
+    depositor: Option<Addr>,
 ) -> Result<SubMsg, ContractError> {
     let receiver = receiver.into();
-    let ibc_msg = ibc_transfer_msg(&deps.as_ref(), env, &receiver, amount.clone())?;
+    let ibc_msg = ibc_transfer_msg(
+        &deps.as_ref(),
+        env,
+        &receiver,
+        amount.clone(),
+        route.as_ref(),
+    )?;
     let sub_msg_id = sub_msg_id.unwrap_or({
         match env.transaction {
             Some(ref tx) => tx.index as u64 + env.block.time.nanos(),
@@ -80,49 +137,305 @@ fn ibc_transfer_sub_msg(
         }
     });
 
-    let ibc_waiting_for_reply = IbcWaitingForReply { amount, receiver };
+    let ibc_waiting_for_reply = IbcWaitingForReply {
+        amount,
+        receiver,
+        route,
+        depositor,
+    };
 
     save_ibc_waiting_for_reply(deps, sub_msg_id, ibc_waiting_for_reply)?;
 
     Ok(SubMsg {
         id: sub_msg_id,
-        msg: ibc_msg.into(),
+        msg: Backend::ibc_transfer(ibc_msg)?,
         gas_limit: None,
         reply_on: ReplyOn::Always,
     })
 }
 
+/// Pushes the rates to the oracle targets, skipping the ones updated within their
+/// `min_update_interval` unless `force` is set. The updates are sent with a gas limit
+/// and their errors are recorded in the reply, so an oracle outage doesn't block the caller.
 fn update_oracle_msgs(
+    storage: &mut dyn Storage,
     env: &Env,
     config: &Config,
     state: &State,
-) -> Result<Vec<CosmosMsg>, ContractError> {
-    let mut messages: Vec<CosmosMsg> = Vec::new();
-
-    if let Some(oracle_address) = &config.protocol_chain_config.oracle_address {
-        let (redemption_rate, purchase_rate) = get_rates(state);
-        // Post rates to Milkyway Oracle contract
-        let post_rates_msg = Oracle::PostRates {
-            purchase_rate: purchase_rate.to_string(),
-            redemption_rate: redemption_rate.to_string(),
-            denom: config.liquid_stake_token_denom.clone(),
+    force: bool,
+) -> Result<Vec<SubMsg>, ContractError> {
+    let mut messages: Vec<SubMsg> = Vec::new();
+    let (redemption_rate, purchase_rate) = get_rates(state);
+
+    for (index, target) in config
+        .protocol_chain_config
+        .oracle_targets()
+        .into_iter()
+        .enumerate()
+    {
+        if let (false, Some(interval)) = (force, target.min_update_interval) {
+            let last_update = ORACLE_TARGET_LAST_UPDATE.may_load(storage, &target.address)?;
+            if matches!(last_update, Some(t) if env.block.time < t.plus_seconds(interval)) {
+                continue;
+            }
+        }
+        ORACLE_TARGET_LAST_UPDATE.save(storage, &target.address, &env.block.time)?;
+
+        let denom = config.liquid_stake_token_denom.clone();
+        let msg = match target.adapter {
+            // Post rates to Milkyway Oracle contract
+            OracleAdapter::MilkyWay => to_json_binary(&Oracle::PostRates {
+                purchase_rate: purchase_rate.to_string(),
+                redemption_rate: redemption_rate.to_string(),
+                denom,
+            })?,
+            OracleAdapter::UpdateRate => to_json_binary(&UpdateRateMsg::UpdateRate {
+                denom,
+                rate: redemption_rate,
+            })?,
+            OracleAdapter::PriceFeed => to_json_binary(&PriceFeedMsg::SetPrice {
+                denom,
+                base_denom: config.protocol_chain_config.ibc_token_denom.clone(),
+                price: redemption_rate,
+            })?,
         };
 
-        let post_rate_msg_json = serde_json::to_string(&post_rates_msg).unwrap();
         messages.push(
-            MsgExecuteContract {
-                sender: env.contract.address.to_string(),
-                contract: oracle_address.to_string(),
-                msg: post_rate_msg_json.as_bytes().to_vec(),
-                funds: vec![],
+            SubMsg::reply_on_error(
+                Backend::execute_contract(
+                    env.contract.address.to_string(),
+                    target.address.to_string(),
+                    msg,
+                    vec![],
+                )?,
+                UPDATE_ORACLE_REPLY_ID - index as u64,
+            )
+            .with_gas_limit(UPDATE_ORACLE_GAS_LIMIT),
+        );
+    }
+
+    Ok(messages)
+}
+
+/// Records a failed oracle update, the rates are posted again with
+/// [crate::msg::ExecuteMsg::PushRates].
+pub fn handle_oracle_update_reply(
+    deps: DepsMut,
+    env: Env,
+    reply: Reply,
+) -> ContractResult<Response> {
+    let SubMsgResult::Err(error) = reply.result else {
+        return Ok(Response::new());
+    };
+    let config = CONFIG.load(deps.storage)?;
+    let target = config
+        .protocol_chain_config
+        .oracle_targets()
+        .into_iter()
+        .nth((UPDATE_ORACLE_REPLY_ID - reply.id) as usize)
+        .ok_or(ContractError::InvalidReplyID { id: reply.id })?
+        .address;
+
+    let failures = ORACLE_UPDATE_FAILURE
+        .may_load(deps.storage)?
+        .map_or(0, |failure| failure.failures);
+    ORACLE_UPDATE_FAILURE.save(
+        deps.storage,
+        &OracleUpdateFailure {
+            target: target.clone(),
+            error: error.clone(),
+            time: env.block.time,
+            failures: failures + 1,
+        },
+    )?;
+
+    Ok(Response::new()
+        .add_attribute("action", "oracle_update_failed")
+        .add_attribute("target", target.clone())
+        .add_attribute("error", error.clone())
+        .add_event(
+            MilkyWayEvent::OracleUpdateFailed {
+                target: target.to_string(),
+                error,
+            }
+            .into(),
+        ))
+}
+
+/// Posts the current rates to all the oracle targets after a failed update; callable by anyone.
+pub fn push_rates(deps: DepsMut, env: Env) -> ContractResult<Response> {
+    let config = CONFIG.load(deps.storage)?;
+    check_stopped(&config)?;
+
+    let failure = ORACLE_UPDATE_FAILURE
+        .may_load(deps.storage)?
+        .ok_or(ContractError::NoFailedOracleUpdate {})?;
+    ORACLE_UPDATE_FAILURE.remove(deps.storage);
+
+    let state = STATE.load(deps.storage)?;
+    let (redemption_rate, purchase_rate) = get_rates(&state);
+    let update_oracle_msgs = update_oracle_msgs(deps.storage, &env, &config, &state, true)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "push_rates")
+        .add_attribute("failures", failure.failures.to_string())
+        .add_event(
+            MilkyWayEvent::PushRates {
+                redemption_rate,
+                purchase_rate,
             }
             .into(),
+        )
+        .add_submessages(update_oracle_msgs))
+}
+
+/// Creates the callbacks of the contracts subscribed to the event of `msg`,
+/// sent as reply on error submessages so a faulty subscriber can't block the protocol.
+fn hook_msgs(
+    storage: &dyn Storage,
+    env: &Env,
+    state: &State,
+    msg: HookMsg,
+) -> ContractResult<Vec<SubMsg>> {
+    let event = msg.event();
+    let (redemption_rate, purchase_rate) = get_rates(state);
+    let hook_msg = to_json_binary(&HookExecuteMsg::MilkywayHook {
+        msg,
+        redemption_rate,
+        purchase_rate,
+    })?;
+
+    let mut messages: Vec<SubMsg> = Vec::new();
+    for (index, subscription) in HOOK_SUBSCRIPTIONS
+        .range(storage, None, None, Order::Ascending)
+        .enumerate()
+    {
+        let (_, subscription) = subscription?;
+        if !subscription.events.contains(&event) {
+            continue;
+        }
+        messages.push(
+            SubMsg::reply_on_error(
+                Backend::execute_contract(
+                    env.contract.address.to_string(),
+                    subscription.address.to_string(),
+                    hook_msg.clone(),
+                    vec![],
+                )?,
+                HOOK_REPLY_ID - index as u64,
+            )
+            .with_gas_limit(HOOK_GAS_LIMIT),
         );
     }
 
     Ok(messages)
 }
 
+/// Reports a failed hook callback, the failure doesn't revert the protocol operation.
+pub fn handle_hook_reply(deps: DepsMut, reply: Reply) -> ContractResult<Response> {
+    let SubMsgResult::Err(error) = reply.result else {
+        return Ok(Response::new());
+    };
+    let subscriber = HOOK_SUBSCRIPTIONS
+        .keys(deps.storage, None, None, Order::Ascending)
+        .nth((HOOK_REPLY_ID - reply.id) as usize)
+        .transpose()?
+        .ok_or(ContractError::InvalidReplyID { id: reply.id })?;
+
+    Ok(Response::new()
+        .add_attribute("action", "hook_failed")
+        .add_attribute("subscriber", subscriber.clone())
+        .add_attribute("error", error.clone())
+        .add_event(
+            MilkyWayEvent::HookFailed {
+                subscriber: subscriber.to_string(),
+                error,
+            }
+            .into(),
+        ))
+}
+
+pub fn subscribe_hooks(
+    deps: DepsMut,
+    info: MessageInfo,
+    address: String,
+    events: Vec<HookEvent>,
+) -> ContractResult<Response> {
+    ADMIN.assert_admin(deps.as_ref(), &info.sender)?;
+
+    let address = deps.api.addr_validate(&address)?;
+    let mut unique_events: Vec<HookEvent> = Vec::new();
+    for event in events {
+        if !unique_events.contains(&event) {
+            unique_events.push(event);
+        }
+    }
+    if unique_events.is_empty() {
+        return Err(ContractError::EmptyHookEvents {});
+    }
+
+    if !HOOK_SUBSCRIPTIONS.has(deps.storage, &address) {
+        let subscriptions = HOOK_SUBSCRIPTIONS
+            .keys(deps.storage, None, None, Order::Ascending)
+            .count();
+        if subscriptions >= MAX_HOOK_SUBSCRIPTIONS {
+            return Err(ContractError::ValueTooBig {
+                field_name: "hook_subscriptions".to_string(),
+                value: Uint128::from(subscriptions as u128 + 1),
+                max: Uint128::from(MAX_HOOK_SUBSCRIPTIONS as u128),
+            });
+        }
+    }
+    HOOK_SUBSCRIPTIONS.save(
+        deps.storage,
+        &address,
+        &HookSubscription {
+            address: address.clone(),
+            events: unique_events.clone(),
+        },
+    )?;
+
+    Ok(Response::new()
+        .add_attribute("action", "subscribe_hooks")
+        .add_attribute("address", address.clone())
+        .add_event(
+            MilkyWayEvent::SubscribeHooks {
+                address: address.to_string(),
+                events: unique_events
+                    .iter()
+                    .map(|event| event.as_str().to_string())
+                    .collect(),
+            }
+            .into(),
+        ))
+}
+
+pub fn unsubscribe_hooks(
+    deps: DepsMut,
+    info: MessageInfo,
+    address: String,
+) -> ContractResult<Response> {
+    ADMIN.assert_admin(deps.as_ref(), &info.sender)?;
+
+    let address = deps.api.addr_validate(&address)?;
+    if !HOOK_SUBSCRIPTIONS.has(deps.storage, &address) {
+        return Err(ContractError::HookSubscriptionNotFound {
+            address: address.to_string(),
+        });
+    }
+    HOOK_SUBSCRIPTIONS.remove(deps.storage, &address);
+
+    Ok(Response::new()
+        .add_attribute("action", "unsubscribe_hooks")
+        .add_attribute("address", address.clone())
+        .add_event(
+            MilkyWayEvent::UnsubscribeHooks {
+                address: address.to_string(),
+            }
+            .into(),
+        ))
+}
+
 pub fn check_stopped(config: &Config) -> Result<(), ContractError> {
     if config.stopped {
         return Err(ContractError::Stopped {});
@@ -130,9 +443,74 @@ pub fn check_stopped(config: &Config) -> Result<(), ContractError> {
     Ok(())
 }
 
+/// Fails once the liquid stake token admin has been handed over, since the
+/// contract can't mint or burn the token anymore.
+fn check_denom_admin_not_transferred(storage: &dyn Storage) -> Result<(), ContractError> {
+    if DENOM_ADMIN_TRANSFERRED
+        .may_load(storage)?
+        .unwrap_or_default()
+    {
+        return Err(ContractError::DenomAdminTransferred {});
+    }
+    Ok(())
+}
+
+/// Validates the native chain account that sent tokens through ibc-hooks,
+/// the sender must be its intermediate sender on the configured channel.
+fn ibc_hooks_native_sender(
+    config: &Config,
+    sender: &Addr,
+    native_sender: &str,
+) -> ContractResult<Addr> {
+    let native_sender = validate_address(
+        native_sender,
+        &config.native_chain_config.account_address_prefix,
+    )?;
+    let intermediate_sender = derive_intermediate_sender(
+        &config.protocol_chain_config.ibc_channel_id,
+        native_sender.as_str(),
+        &config.protocol_chain_config.account_address_prefix,
+    )?;
+    if *sender != intermediate_sender {
+        return Err(ContractError::Unauthorized {
+            sender: sender.to_string(),
+        });
+    }
+    Ok(native_sender)
+}
+
+/// Ensures that the sender is the ibc-hooks intermediate sender of `original_sender`
+/// on the configured channel, if the check is enabled in the config.
+fn check_ibc_hooks_sender(
+    config: &Config,
+    sender: &Addr,
+    original_sender: &str,
+) -> Result<(), ContractError> {
+    if !config
+        .protocol_chain_config
+        .authenticate_ibc_hooks_sender
+        .unwrap_or(false)
+    {
+        return Ok(());
+    }
+
+    let intermediate_sender = derive_intermediate_sender(
+        &config.protocol_chain_config.ibc_channel_id,
+        original_sender,
+        &config.protocol_chain_config.account_address_prefix,
+    )?;
+    if *sender != intermediate_sender {
+        return Err(ContractError::Unauthorized {
+            sender: sender.to_string(),
+        });
+    }
+    Ok(())
+}
+
 // PENDING
 // Payment validation handled by caller (not sure what this means)
 // Denom validation handled by caller (done in contract.rs)
+#[allow(clippy::too_many_arguments)]
 pub fn execute_liquid_stake(
     mut deps: DepsMut,
     env: Env,
@@ -141,12 +519,22 @@ pub fn execute_liquid_stake(
     mint_to: Option<String>,
     transfer_to_native_chain: Option<bool>,
     expected_mint_amount: Option<Uint128>,
+    native_sender: Option<String>,
 ) -> ContractResult<Response> {
     let config = CONFIG.load(deps.storage)?;
 
     check_stopped(&config)?;
-
-    let mint_to_address = mint_to.unwrap_or_else(|| info.sender.to_string());
+    check_denom_admin_not_transferred(deps.storage)?;
+
+    let native_sender = native_sender
+        .map(|s| ibc_hooks_native_sender(&config, &info.sender, &s))
+        .transpose()?;
+    // Stakes received through ibc-hooks are delivered by default
+    // to the native chain sender.
+    let transfer_to_native_chain =
+        transfer_to_native_chain.or(native_sender.as_ref().map(|_| true));
+    let mint_to_address =
+        mint_to.unwrap_or_else(|| native_sender.as_ref().unwrap_or(&info.sender).to_string());
     let mint_to_is_native = validate_address(
         &mint_to_address,
         &config.native_chain_config.account_address_prefix,
@@ -158,10 +546,16 @@ pub fn execute_liquid_stake(
     )
     .is_ok();
 
-    // Ensure the mint to is either a protocol chain account or
-    // native chain account.
+    // Ensure the mint to is either a protocol chain account, a native
+    // chain account or an account on a registered destination chain.
+    let mut destination_route = None;
     if !mint_to_is_protocol && !mint_to_is_native {
-        return Err(ContractError::InvalidAddress {});
+        let (prefix, _) =
+            bech32::decode(&mint_to_address).map_err(|_| ContractError::InvalidAddress {})?;
+        let destination_chain = DESTINATION_CHAINS
+            .may_load(deps.storage, prefix.as_str())?
+            .ok_or(ContractError::InvalidAddress {})?;
+        destination_route = Some(destination_chain.route());
     }
 
     // There may be cases where the address prefixes of the native chain and the protocol chain are the same.
@@ -169,6 +563,9 @@ pub fn execute_liquid_stake(
     if mint_to_is_native && mint_to_is_protocol && transfer_to_native_chain.unwrap_or(false) {
         mint_to_is_protocol = false;
     }
+    if !mint_to_is_protocol && !tokenfactory::IBC_TRANSFER_SUPPORTED {
+        return Err(ContractError::IbcTransferNotSupported {});
+    }
 
     let mut state: State = STATE.load(deps.storage)?;
     ensure!(
@@ -224,6 +621,8 @@ pub fn execute_liquid_stake(
         &config.native_chain_config.staker_address,
         Coin::new(amount.u128(), &config.protocol_chain_config.ibc_token_denom),
         None,
+        None,
+        None,
     )?;
     state.total_native_token += amount;
     state.total_liquid_stake_token += mint_amount;
@@ -232,36 +631,65 @@ pub fn execute_liquid_stake(
     // Get the stake sub message id so if we need to ibc transfer the minted
     // liquid staked tokens we use this id plus one.
     let stake_sub_message_id = stake_sub_message.id;
-    let update_oracle_msgs = update_oracle_msgs(&env, &config, &state)?;
+    let update_oracle_msgs = update_oracle_msgs(deps.storage, &env, &config, &state, false)?;
+    let hook_msgs = hook_msgs(
+        deps.storage,
+        &env,
+        &state,
+        HookMsg::LiquidStake {
+            user: native_sender.as_ref().unwrap_or(&info.sender).to_string(),
+            amount,
+            mint_amount,
+        },
+    )?;
 
     let response = Response::new()
         .add_message(mint_msg)
-        .add_messages(update_oracle_msgs)
+        .add_submessages(update_oracle_msgs)
         .add_submessage(stake_sub_message)
+        .add_submessages(hook_msgs)
         .add_attribute("action", "liquid_stake")
         .add_attribute("sender", info.sender.to_string())
         .add_attribute("in_amount", amount)
-        .add_attribute("mint_amount", mint_amount);
+        .add_attribute("mint_amount", mint_amount)
+        .add_event(
+            MilkyWayEvent::LiquidStake {
+                sender: info.sender.to_string(),
+                native_sender: native_sender.as_ref().map(Addr::to_string),
+                mint_to: mint_to_address.clone(),
+                denom: config.protocol_chain_config.ibc_token_denom.clone(),
+                amount,
+                mint_denom: config.liquid_stake_token_denom.clone(),
+                mint_amount,
+                redemption_rate: get_rates(&state).0,
+            }
+            .into(),
+        );
+    let response = match &native_sender {
+        Some(native_sender) => response.add_attribute("native_sender", native_sender),
+        None => response,
+    };
 
     let response = if mint_to_is_protocol {
-        // Send the minted tokens to the user on the protocol network trough a MsgSend
-        response.add_message(osmosis_std::types::cosmos::bank::v1beta1::MsgSend {
-            from_address: env.contract.address.to_string(),
-            to_address: mint_to_address,
-            amount: vec![OsmosisCoin {
-                denom: config.liquid_stake_token_denom.clone(),
-                amount: mint_amount.to_string(),
-            }],
-        })
+        // Send the minted tokens to the user on the protocol network
+        response.add_message(tokenfactory::send(
+            env.contract.address.to_string(),
+            Coin::new(mint_amount.u128(), &config.liquid_stake_token_denom),
+            mint_to_address,
+        )?)
     } else {
         // IBC transfer the minted liquid staked representation
-        // back to the native chain account
+        // back to the native chain account or to the destination chain
         response.add_submessage(ibc_transfer_sub_msg(
             &mut deps,
             &env,
             mint_to_address,
             Coin::new(mint_amount.u128(), &config.liquid_stake_token_denom),
             Some(stake_sub_message_id + 1),
+            destination_route,
+            // The intermediate sender of ibc-hooks is not controlled by anyone,
+            // failed deliveries to native chain senders are re-sent instead.
+            native_sender.is_none().then_some(info.sender),
         )?)
     };
 
@@ -270,71 +698,142 @@ pub fn execute_liquid_stake(
 
 pub fn execute_liquid_unstake(
     mut deps: DepsMut,
-    _env: Env,
+    env: Env,
     info: MessageInfo,
     amount: Uint128,
+    native_sender: Option<String>,
 ) -> ContractResult<Response> {
     let config = CONFIG.load(deps.storage)?;
 
     check_stopped(&config)?;
+    check_denom_admin_not_transferred(deps.storage)?;
+
+    let native_sender = native_sender
+        .map(|s| ibc_hooks_native_sender(&config, &info.sender, &s))
+        .transpose()?;
+    // Requests received through ibc-hooks are recorded under the native chain sender
+    let user = native_sender.as_ref().unwrap_or(&info.sender).to_string();
 
-    STATE.load(deps.storage)?;
+    let state = STATE.load(deps.storage)?;
 
     // Load current pending batch
     let pending_batch_id = PENDING_BATCH_ID.load(deps.storage)?;
+    let pending_batch = BATCHES.load(deps.storage, pending_batch_id)?;
+
+    let max_batch_liquid_stake = config
+        .batch_limits
+        .as_ref()
+        .and_then(|l| l.max_batch_liquid_stake);
+
+    let mut response = Response::new()
+        .add_attribute("action", "liquid_unstake")
+        .add_attribute("sender", info.sender.to_string())
+        .add_attribute("batch", pending_batch_id.to_string())
+        .add_attribute("amount", amount);
+    if let Some(native_sender) = &native_sender {
+        response = response.add_attribute("native_sender", native_sender);
+    }
+
+    // Add unstake request to pending batch, the amount exceeding the
+    // maximum batch size is rolled over into the next batches.
+    let mut remaining = amount;
+    let mut batch_id = pending_batch_id;
+    while !remaining.is_zero() {
+        if batch_id - pending_batch_id >= MAX_ROLLED_OVER_BATCHES {
+            return Err(ContractError::TooManyRolledOverBatches {
+                max: MAX_ROLLED_OVER_BATCHES,
+            });
+        }
+        let mut batch = match BATCHES.may_load(deps.storage, batch_id)? {
+            Some(batch) => batch,
+            None => Batch::new(
+                batch_id,
+                Uint128::zero(),
+                pending_batch.next_batch_action_time.unwrap_or_default()
+                    + config.batch_period * (batch_id - pending_batch_id),
+            ),
+        };
 
-    // Add unstake request to pending batch
-    let pending_unstake_request =
-        unstake_requests().may_load(deps.storage, (pending_batch_id, info.sender.to_string()))?;
-    let is_new_request = pending_unstake_request.is_none();
-    match pending_unstake_request {
-        Some(_) => {
-            unstake_requests().update(
+        let batch_amount = match max_batch_liquid_stake {
+            Some(max) => remaining.min(max.saturating_sub(batch.batch_total_liquid_stake)),
+            None => remaining,
+        };
+        if !batch_amount.is_zero() {
+            if add_unstake_request(&mut deps, user.clone(), batch_id, batch_amount)? {
+                batch.unstake_requests_count = Some(batch.unstake_requests_count.unwrap_or(0) + 1);
+            }
+            if native_sender.is_some() {
+                NATIVE_UNSTAKE_REQUESTS.save(deps.storage, (batch_id, &user), &Empty {})?;
+            }
+            response = response.add_event(
+                MilkyWayEvent::LiquidUnstake {
+                    sender: info.sender.to_string(),
+                    user: user.clone(),
+                    batch_id,
+                    amount: batch_amount,
+                }
+                .into(),
+            );
+            // Add amount to batch total (stTIA)
+            batch.batch_total_liquid_stake += batch_amount;
+            BATCHES.save(deps.storage, batch_id, &batch)?;
+            response = response.add_submessages(hook_msgs(
                 deps.storage,
-                (pending_batch_id, info.sender.to_string()),
-                |or| -> Result<UnstakeRequest, ContractError> {
-                    match or {
-                        Some(r) => Ok(UnstakeRequest {
-                            batch_id: r.batch_id,
-                            user: r.user.clone(),
-                            amount: r.amount + amount,
-                        }),
-                        None => Err(ContractError::NoRequestInBatch {}),
-                    }
+                &env,
+                &state,
+                HookMsg::LiquidUnstake {
+                    user: user.clone(),
+                    batch_id,
+                    amount: batch_amount,
+                },
+            )?);
+
+            if batch_id != pending_batch_id {
+                response = response
+                    .add_attribute("rolled_over_batch", batch_id.to_string())
+                    .add_attribute("rolled_over_amount", batch_amount);
+            }
+        }
+
+        remaining -= batch_amount;
+        batch_id += 1;
+    }
+
+    Ok(response)
+}
+
+/// Adds the amount to the user unstake request in the batch,
+/// returns true if a new request has been created.
+fn add_unstake_request(
+    deps: &mut DepsMut,
+    user: String,
+    batch_id: u64,
+    amount: Uint128,
+) -> ContractResult<bool> {
+    match unstake_requests().may_load(deps.storage, (batch_id, user.clone()))? {
+        Some(request) => {
+            unstake_requests().save(
+                deps.storage,
+                (batch_id, user),
+                &UnstakeRequest {
+                    amount: request.amount + amount,
+                    ..request
                 },
             )?;
+            Ok(false)
         }
         None => {
-            new_unstake_request(&mut deps, info.sender.to_string(), pending_batch_id, amount)?;
+            new_unstake_request(deps, user, batch_id, amount)?;
+            Ok(true)
         }
     }
-
-    // Add amount to batch total (stTIA)
-    BATCHES.update(
-        deps.storage,
-        pending_batch_id,
-        |_batch| -> Result<Batch, ContractError> {
-            let mut batch = _batch.unwrap();
-            batch.batch_total_liquid_stake += amount;
-            if is_new_request {
-                batch.unstake_requests_count = Some(batch.unstake_requests_count.unwrap_or(0) + 1);
-            }
-            Ok(batch)
-        },
-    )?;
-
-    Ok(Response::new()
-        .add_attribute("action", "liquid_unstake")
-        .add_attribute("sender", info.sender.to_string())
-        .add_attribute("batch", pending_batch_id.to_string())
-        .add_attribute("amount", amount))
 }
 
 /// Submit batch and transition pending batch to submitted.
 pub fn execute_submit_batch(
     deps: DepsMut,
     env: Env,
-    _info: MessageInfo,
+    info: MessageInfo,
 ) -> ContractResult<Response> {
     let config = CONFIG.load(deps.storage)?;
 
@@ -343,9 +842,12 @@ pub fn execute_submit_batch(
     let pending_batch_id = PENDING_BATCH_ID.load(deps.storage)?;
     let mut batch = BATCHES.load(deps.storage, pending_batch_id)?;
 
+    // A batch that reached the configured size can be submitted early
+    let early_submit = matches!(&config.batch_limits, Some(l) if l.allows_early_submit(&batch));
+
     if let Some(est_next_batch_time) = batch.next_batch_action_time {
         // Check if the batch has been submitted
-        if env.block.time.seconds() < est_next_batch_time {
+        if env.block.time.seconds() < est_next_batch_time && !early_submit {
             return Err(ContractError::BatchNotReady {
                 actual: env.block.time.seconds(),
                 expected: est_next_batch_time,
@@ -373,12 +875,15 @@ pub fn execute_submit_batch(
         }
     );
 
-    // Create new pending batch
-    let new_pending_batch = Batch::new(
-        batch.id + 1,
-        Uint128::zero(),
-        env.block.time.seconds() + config.batch_period,
-    );
+    // Create new pending batch, keeping the requests rolled over into it
+    let next_batch_action_time = env.block.time.seconds() + config.batch_period;
+    let new_pending_batch = match BATCHES.may_load(deps.storage, batch.id + 1)? {
+        Some(mut next_batch) => {
+            next_batch.next_batch_action_time = Some(next_batch_action_time);
+            next_batch
+        }
+        None => Batch::new(batch.id + 1, Uint128::zero(), next_batch_action_time),
+    };
 
     // Save new pending batch
     BATCHES.save(deps.storage, new_pending_batch.id, &new_pending_batch)?;
@@ -413,6 +918,23 @@ pub fn execute_submit_batch(
         .checked_sub(batch.batch_total_liquid_stake)
         .unwrap_or_else(|_| Uint128::zero());
 
+    // Pay the keeper bounty from the collected fees
+    let mut keeper_bounty = Uint128::zero();
+    if let Some(bounty) = &config.protocol_fee_config.keeper_bounty {
+        let last_bounty_time = LAST_KEEPER_BOUNTY_TIME.may_load(deps.storage)?;
+        let cooldown_elapsed = match last_bounty_time {
+            Some(time) => env.block.time >= time.plus_seconds(bounty.cooldown),
+            None => true,
+        };
+        if cooldown_elapsed {
+            keeper_bounty = bounty.max_amount.min(state.total_fees);
+        }
+        if !keeper_bounty.is_zero() {
+            state.total_fees -= keeper_bounty;
+            LAST_KEEPER_BOUNTY_TIME.save(deps.storage, &env.block.time)?;
+        }
+    }
+
     STATE.save(deps.storage, &state)?;
 
     // Update batch status
@@ -424,15 +946,52 @@ pub fn execute_submit_batch(
 
     BATCHES.save(deps.storage, batch.id, &batch)?;
 
-    let update_oracle_msgs = update_oracle_msgs(&env, &config, &state)?;
+    let update_oracle_msgs = update_oracle_msgs(deps.storage, &env, &config, &state, false)?;
+    let hook_msgs = hook_msgs(
+        deps.storage,
+        &env,
+        &state,
+        HookMsg::SubmitBatch {
+            batch_id: batch.id,
+            batch_total_liquid_stake: batch.batch_total_liquid_stake,
+            expected_native_unstaked: unbond_amount,
+        },
+    )?;
 
-    Ok(Response::new()
+    let mut response = Response::new()
         .add_message(tokenfactory_burn_msg)
-        .add_messages(update_oracle_msgs)
+        .add_submessages(update_oracle_msgs)
+        .add_submessages(hook_msgs)
         .add_attribute("action", "submit_batch")
         .add_attribute("batch_id", batch.id.to_string())
         .add_attribute("batch_total", batch.batch_total_liquid_stake)
-        .add_attribute("expected_native_unstaked", unbond_amount))
+        .add_attribute("expected_native_unstaked", unbond_amount)
+        .add_event(
+            MilkyWayEvent::SubmitBatch {
+                batch_id: batch.id,
+                batch_total_liquid_stake: batch.batch_total_liquid_stake,
+                expected_native_unstaked: unbond_amount,
+                keeper: info.sender.to_string(),
+                keeper_bounty,
+            }
+            .into(),
+        );
+
+    if !keeper_bounty.is_zero() {
+        response = response
+            .add_attribute("keeper", info.sender.to_string())
+            .add_attribute("keeper_bounty", keeper_bounty)
+            .add_message(Backend::bank_send(
+                env.contract.address.to_string(),
+                info.sender.to_string(),
+                coins(
+                    keeper_bounty.u128(),
+                    &config.protocol_chain_config.ibc_token_denom,
+                ),
+            )?);
+    }
+
+    Ok(response)
 }
 
 // doing a "push over pool" pattern for now
@@ -469,56 +1028,208 @@ pub fn execute_withdraw(
     );
 
     remove_unstake_request(&mut deps, info.sender.to_string(), batch.id)?;
+    let dust_event = claim_from_batch(deps.storage, batch.id, amount)?;
 
     let mut messages: Vec<CosmosMsg> = vec![];
-    let send_msg = MsgSend {
-        from_address: env.contract.address.to_string(),
-        to_address: info.sender.to_string(),
-        amount: vec![OsmosisCoin {
-            denom: config.protocol_chain_config.ibc_token_denom.clone(),
-            amount: amount.to_string(),
-        }],
-    };
-    messages.push(send_msg.into());
+    let send_msg = Backend::bank_send(
+        env.contract.address.to_string(),
+        info.sender.to_string(),
+        coins(amount.u128(), &config.protocol_chain_config.ibc_token_denom),
+    )?;
+    messages.push(send_msg);
 
     let state = STATE.load(deps.storage)?;
-    let update_oracle_msgs = update_oracle_msgs(&env, &config, &state)?;
+    let update_oracle_msgs = update_oracle_msgs(deps.storage, &env, &config, &state, false)?;
 
     Ok(Response::new()
         .add_attribute("action", "execute_withdraw")
         .add_attribute("batch", batch.id.to_string())
-        .add_attribute("amount", amount.to_string())
-        .add_messages([messages, update_oracle_msgs].concat()))
+        .add_attribute("amount", amount)
+        .add_event(
+            MilkyWayEvent::Withdraw {
+                user: info.sender.to_string(),
+                batch_id: batch.id,
+                denom: config.protocol_chain_config.ibc_token_denom.clone(),
+                amount,
+            }
+            .into(),
+        )
+        .add_events(dust_event)
+        .add_messages(messages)
+        .add_submessages(update_oracle_msgs))
 }
 
-// Add a validator to the list of validators; callable by the owner
-pub fn execute_add_validator(
-    deps: DepsMut,
-    _env: Env,
-    info: MessageInfo,
-    new_validator: String,
-) -> ContractResult<Response> {
-    ADMIN.assert_admin(deps.as_ref(), &info.sender)?;
-
-    let mut config = CONFIG.load(deps.storage)?;
-    let new_validator_addr = validate_address(
-        &new_validator,
-        &config.native_chain_config.validator_address_prefix,
-    )?;
+/// Deducts a withdrawal from the claimable tokens of the batch. Once the last
+/// request is withdrawn, the rounding dust left is credited to the fees.
+fn claim_from_batch(
+    storage: &mut dyn Storage,
+    batch_id: u64,
+    amount: Uint128,
+) -> ContractResult<Option<Event>> {
+    // Batches received before the claimable tokens were tracked aren't swept
+    let Some(claimable) = BATCH_CLAIMABLE.may_load(storage, batch_id)? else {
+        return Ok(None);
+    };
+    let claimed = claimable.min(amount);
+    let claimable = claimable - claimed;
 
-    // Check if the new_validator is already in the list.
-    if config
-        .native_chain_config
-        .validators
-        .iter()
-        .any(|validator| *validator == new_validator_addr)
-    {
-        return Err(ContractError::DuplicateValidator {
-            validator: new_validator.clone(),
-        });
+    let has_requests = unstake_requests()
+        .prefix(batch_id)
+        .keys(storage, None, None, Order::Ascending)
+        .next()
+        .is_some();
+    if has_requests {
+        BATCH_CLAIMABLE.save(storage, batch_id, &claimable)?;
+        update_total(storage, &TOTAL_CLAIMABLE, Uint128::zero(), claimed)?;
+        return Ok(None);
     }
 
-    // Add the new validator to the list.
+    BATCH_CLAIMABLE.remove(storage, batch_id);
+    update_total(
+        storage,
+        &TOTAL_CLAIMABLE,
+        Uint128::zero(),
+        claimed + claimable,
+    )?;
+    if claimable.is_zero() {
+        return Ok(None);
+    }
+    let mut state = STATE.load(storage)?;
+    state.total_fees += claimable;
+    STATE.save(storage, &state)?;
+
+    Ok(Some(
+        MilkyWayEvent::SweepBatchDust {
+            batch_id,
+            amount: claimable,
+        }
+        .into(),
+    ))
+}
+
+/// Sends the unstaked tokens of a received batch to the native chain accounts
+/// that requested the unstake through ibc-hooks.
+pub fn withdraw_native_unstakes(
+    mut deps: DepsMut,
+    env: Env,
+    batch_id: u64,
+    limit: Option<u32>,
+) -> ContractResult<Response> {
+    let config: Config = CONFIG.load(deps.storage)?;
+
+    check_stopped(&config)?;
+
+    let batch = BATCHES
+        .may_load(deps.storage, batch_id)?
+        .ok_or(ContractError::BatchEmpty {})?;
+    if batch.status != BatchStatus::Received {
+        return Err(ContractError::BatchNotClaimable {
+            batch_id: batch.id,
+            status: batch.status,
+        });
+    }
+
+    let limit = limit.map_or(NATIVE_WITHDRAW_LIMIT, |l| l as usize);
+    let (sub_msgs, events) = native_withdraw_sub_msgs(&mut deps, &env, &config, &batch, limit)?;
+    if sub_msgs.is_empty() {
+        return Err(ContractError::NoRequestInBatch {});
+    }
+
+    Ok(Response::new()
+        .add_attribute("action", "withdraw_native_unstakes")
+        .add_attribute("batch", batch_id.to_string())
+        .add_attribute("native_withdrawals", sub_msgs.len().to_string())
+        .add_submessages(sub_msgs)
+        .add_events(events))
+}
+
+// IBC transfers the unstaked tokens of up to `limit` native chain
+// requests of a received batch and removes the requests.
+fn native_withdraw_sub_msgs(
+    deps: &mut DepsMut,
+    env: &Env,
+    config: &Config,
+    batch: &Batch,
+    limit: usize,
+) -> ContractResult<(Vec<SubMsg>, Vec<Event>)> {
+    let received_native_unstaked = batch.received_native_unstaked.unwrap_or_default();
+    let users = NATIVE_UNSTAKE_REQUESTS
+        .prefix(batch.id)
+        .keys(deps.storage, None, None, Order::Ascending)
+        .take(limit)
+        .collect::<Result<Vec<_>, _>>()?;
+
+    // Sub message ids after the one used by the transfers without an
+    // explicit id in the same transaction.
+    let base_sub_msg_id = match env.transaction {
+        Some(ref tx) => tx.index as u64 + env.block.time.nanos(),
+        None => env.block.time.nanos(),
+    };
+
+    let mut sub_msgs = vec![];
+    let mut events = vec![];
+    for (index, user) in users.into_iter().enumerate() {
+        NATIVE_UNSTAKE_REQUESTS.remove(deps.storage, (batch.id, &user));
+        let Some(request) = unstake_requests().may_load(deps.storage, (batch.id, user.clone()))?
+        else {
+            continue;
+        };
+        let amount =
+            received_native_unstaked.multiply_ratio(request.amount, batch.batch_total_liquid_stake);
+        remove_unstake_request(deps, user.clone(), batch.id)?;
+        let dust_event = claim_from_batch(deps.storage, batch.id, amount)?;
+
+        let sub_msg = ibc_transfer_sub_msg(
+            deps,
+            env,
+            user.clone(),
+            Coin::new(amount.u128(), &config.protocol_chain_config.ibc_token_denom),
+            Some(base_sub_msg_id + index as u64 + 1),
+            None,
+            None,
+        )?;
+        let event = MilkyWayEvent::Withdraw {
+            user,
+            batch_id: batch.id,
+            denom: config.protocol_chain_config.ibc_token_denom.clone(),
+            amount,
+        };
+        sub_msgs.push(sub_msg);
+        events.push(event.into());
+        events.extend(dust_event);
+    }
+
+    Ok((sub_msgs, events))
+}
+
+// Add a validator to the list of validators; callable by the owner
+pub fn execute_add_validator(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    new_validator: String,
+) -> ContractResult<Response> {
+    ADMIN.assert_admin(deps.as_ref(), &info.sender)?;
+
+    let mut config = CONFIG.load(deps.storage)?;
+    let new_validator_addr = validate_address(
+        &new_validator,
+        &config.native_chain_config.validator_address_prefix,
+    )?;
+
+    // Check if the new_validator is already in the list.
+    if config
+        .native_chain_config
+        .validators
+        .iter()
+        .any(|validator| *validator == new_validator_addr)
+    {
+        return Err(ContractError::DuplicateValidator {
+            validator: new_validator.clone(),
+        });
+    }
+
+    // Add the new validator to the list.
     config
         .native_chain_config
         .validators
@@ -529,8 +1240,14 @@ pub fn execute_add_validator(
 
     Ok(Response::new()
         .add_attribute("action", "add_validator")
-        .add_attribute("new_validator", new_validator_addr)
-        .add_attribute("sender", info.sender))
+        .add_attribute("new_validator", new_validator_addr.clone())
+        .add_attribute("sender", info.sender)
+        .add_event(
+            MilkyWayEvent::AddValidator {
+                validator: new_validator_addr.to_string(),
+            }
+            .into(),
+        ))
 }
 
 pub fn execute_remove_validator(
@@ -568,8 +1285,14 @@ pub fn execute_remove_validator(
 
     Ok(Response::new()
         .add_attribute("action", "remove_validator")
-        .add_attribute("removed_validator", validator_addr_to_remove)
-        .add_attribute("sender", info.sender))
+        .add_attribute("removed_validator", validator_addr_to_remove.clone())
+        .add_attribute("sender", info.sender)
+        .add_event(
+            MilkyWayEvent::RemoveValidator {
+                validator: validator_addr_to_remove.to_string(),
+            }
+            .into(),
+        ))
 }
 
 // Transfer ownership to another account; callable by the owner
@@ -594,8 +1317,15 @@ pub fn execute_transfer_ownership(
 
     Ok(Response::new()
         .add_attribute("action", "transfer_ownership")
-        .add_attribute("new_owner", new_owner)
-        .add_attribute("previous_owner", info.sender))
+        .add_attribute("new_owner", new_owner.clone())
+        .add_attribute("previous_owner", info.sender.clone())
+        .add_event(
+            MilkyWayEvent::TransferOwnership {
+                previous_owner: info.sender.to_string(),
+                new_owner,
+            }
+            .into(),
+        ))
 }
 
 // Revoke transfer ownership, callable by the owner
@@ -612,7 +1342,14 @@ pub fn execute_revoke_ownership_transfer(
 
     STATE.save(deps.storage, &state)?;
 
-    Ok(Response::new().add_attribute("action", "revoke_ownership_transfer"))
+    Ok(Response::new()
+        .add_attribute("action", "revoke_ownership_transfer")
+        .add_event(
+            MilkyWayEvent::RevokeOwnershipTransfer {
+                owner: info.sender.to_string(),
+            }
+            .into(),
+        ))
 }
 
 pub fn execute_accept_ownership(
@@ -647,88 +1384,114 @@ pub fn execute_accept_ownership(
             ADMIN.set(deps, Some(pending_owner))?;
             Ok(Response::new()
                 .add_attribute("action", "accept_ownership")
-                .add_attribute("new_owner", info.sender))
+                .add_attribute("new_owner", info.sender.clone())
+                .add_event(
+                    MilkyWayEvent::AcceptOwnership {
+                        new_owner: info.sender.to_string(),
+                    }
+                    .into(),
+                ))
         }
         None => Err(ContractError::NoPendingOwner {}),
     }
 }
 
+// The receiver of a recovery is either a native chain account or an
+// account on a registered destination chain.
+fn validate_recovery_receiver(deps: Deps, config: &Config, receiver: &str) -> ContractResult<Addr> {
+    if let Ok(addr) = validate_address(receiver, &config.native_chain_config.account_address_prefix)
+    {
+        return Ok(addr);
+    }
+
+    let (prefix, _) = bech32::decode(receiver).map_err(|_| ContractError::InvalidAddress {})?;
+    if !DESTINATION_CHAINS.has(deps.storage, prefix.as_str()) {
+        return Err(ContractError::InvalidAddress {});
+    }
+
+    Ok(Addr::unchecked(receiver))
+}
+
 pub fn recover(
     mut deps: DepsMut,
     env: Env,
     _info: MessageInfo,
-    selected_packets: Option<Vec<u64>>,
+    selected_packets: Option<Vec<(String, u64)>>,
     receiver: Option<String>,
     page: bool,
 ) -> Result<Response, ContractError> {
     let page_size = 10;
     let config = CONFIG.load(deps.storage)?;
+    // Without a receiver the packets are re-sent to their original receiver
     let receiver = receiver
-        // Validate the address
-        .map(|s| validate_address(&s, &config.native_chain_config.account_address_prefix))
-        .transpose()?
-        // Fallback to staker address in case the receiver was None
-        .unwrap_or(config.native_chain_config.staker_address);
+        .map(|s| validate_recovery_receiver(deps.as_ref(), &config, &s))
+        .transpose()?;
 
     // timed out and failed packets
-    let packets: Vec<IBCTransfer> = if selected_packets.is_some() {
-        let selected_packets = dedup_vec(selected_packets.unwrap());
-        let mut packets: Vec<IBCTransfer> = vec![];
-        for packet_id in selected_packets {
-            let packet = INFLIGHT_PACKETS.load(deps.storage, packet_id)?;
+    let packets: Vec<((String, u64), IBCTransfer)> = if let Some(selected_packets) =
+        selected_packets
+    {
+        let selected_packets = dedup_vec(selected_packets);
+        let mut packets: Vec<((String, u64), IBCTransfer)> = vec![];
+        for (channel_id, sequence) in selected_packets {
+            let packet = INFLIGHT_PACKETS.load(deps.storage, (channel_id.as_str(), sequence))?;
             // Ensure the selected packet are all for the same user
-            if packet.receiver != receiver.as_str() {
+            if receiver
+                .as_ref()
+                .map_or(false, |receiver| packet.receiver != receiver.as_str())
+            {
                 return Err(ContractError::InvalidReceiver {});
             }
             if packet.status != PacketLifecycleStatus::AckFailure
                 && packet.status != PacketLifecycleStatus::TimedOut
+                && packet.status != PacketLifecycleStatus::DeadLetter
             {
-                return Err(ContractError::InvalidPacketStatus { id: packet_id });
+                return Err(ContractError::InvalidPacketStatus { id: sequence });
             }
-            packets.push(packet);
+            packets.push(((channel_id, sequence), packet));
         }
         packets
     } else {
-        // Clone the receiver so that can be captured by the filter function.
-        let receiver = receiver.clone();
-        let packets: Vec<IBCTransfer> = paginate_map(
-            deps.as_ref(),
-            &INFLIGHT_PACKETS,
-            None,
-            if page { Some(page_size) } else { None },
-            Order::Ascending,
-            Some(Box::new(move |r: &IBCTransfer| {
-                r.receiver == receiver
-                    && (r.status == PacketLifecycleStatus::AckFailure
-                        || r.status == PacketLifecycleStatus::TimedOut)
-            })),
-        )?;
-        packets
+        INFLIGHT_PACKETS
+            .range(deps.storage, None, None, Order::Ascending)
+            .filter(|r| {
+                r.as_ref().map_or(true, |(_, packet)| {
+                    receiver
+                        .as_ref()
+                        .map_or(true, |receiver| packet.receiver == receiver.as_str())
+                        && (packet.status == PacketLifecycleStatus::AckFailure
+                            || packet.status == PacketLifecycleStatus::TimedOut
+                            || packet.status == PacketLifecycleStatus::DeadLetter)
+                })
+            })
+            .take(if page { page_size } else { usize::MAX })
+            .collect::<StdResult<Vec<_>>>()?
     };
 
     if packets.is_empty() {
         return Err(ContractError::NoInflightPackets {});
     }
 
-    let max_submessage_id = INFLIGHT_PACKETS
-        .range(deps.storage, None, None, Order::Descending)
-        .take(1)
-        .next()
-        .unwrap()
-        .unwrap()
-        .0;
+    let max_submessage_id = max_inflight_sequence(deps.storage)?;
 
-    // Compute the total amount and remove the packets from the
-    // INFLIGHT_PACKETS state.
-    let mut total_amounts = BTreeMap::<String, Uint128>::new();
+    // Compute the total amount for each receiver, denom and route and remove
+    // the packets from the INFLIGHT_PACKETS state.
+    let mut total_amounts: Vec<(String, Coin, Option<IbcRoute>)> = vec![];
     let mut handled_packets_count = 0usize;
-    for packet in packets.iter() {
-        let coin_amount = total_amounts.get(&packet.amount.denom);
+    for ((channel_id, sequence), packet) in packets.iter() {
+        let packet_receiver = receiver
+            .as_ref()
+            .map_or(packet.receiver.clone(), Addr::to_string);
+        let total_amount = total_amounts.iter_mut().find(|(receiver, coin, route)| {
+            *receiver == packet_receiver
+                && coin.denom == packet.amount.denom
+                && *route == packet.route
+        });
 
         // Compute the new amount
-        let new_amount = if let Some(amount) = coin_amount {
-            // We found the value in the map, update it.
-            (*amount).checked_add(packet.amount.amount)
+        let new_amount = if let Some((_, coin, _)) = &total_amount {
+            // We found the value, update it.
+            coin.amount.checked_add(packet.amount.amount)
         } else {
             Ok(packet.amount.amount)
         };
@@ -736,27 +1499,34 @@ pub fn recover(
         if let Ok(amount) = new_amount {
             // If we have correctly computed the new amount
             // remove the packet from the inflight packets
-            INFLIGHT_PACKETS.remove(deps.storage, packet.sequence);
-            // Update the amount for the denom
-            total_amounts.insert(packet.amount.denom.clone(), amount);
+            remove_inflight_packet(deps.storage, (channel_id, *sequence))?;
+            INFLIGHT_PACKET_RETRIES.remove(deps.storage, (channel_id, *sequence));
+            // Update the amount for the receiver, denom and route
+            match total_amount {
+                Some((_, coin, _)) => coin.amount = amount,
+                None => total_amounts.push((
+                    packet_receiver,
+                    packet.amount.clone(),
+                    packet.route.clone(),
+                )),
+            }
             // Update the number of handled packets
             handled_packets_count += 1;
         }
     }
 
     let sub_msgs = total_amounts
-        .iter()
+        .into_iter()
         .enumerate()
-        .map(|(index, (key, value))| {
+        .map(|(index, (receiver, amount, route))| {
             ibc_transfer_sub_msg(
                 &mut deps,
                 &env,
-                receiver.as_str(),
-                Coin {
-                    denom: key.to_string(),
-                    amount: *value,
-                },
+                receiver,
+                amount,
                 Some(max_submessage_id + (index as u64) + 1),
+                route,
+                None,
             )
         })
         .collect::<Result<Vec<_>, _>>()?;
@@ -764,6 +1534,204 @@ pub fn recover(
     Ok(Response::new()
         .add_attribute("action", "recover")
         .add_attribute("packets", handled_packets_count.to_string())
+        .add_event(
+            MilkyWayEvent::RecoverIbcTransfers {
+                receiver: receiver.map(|receiver| receiver.to_string()),
+                packets: handled_packets_count as u64,
+            }
+            .into(),
+        )
+        .add_submessages(sub_msgs))
+}
+
+/// Returns the highest sequence of the in-flight packets, the transfers re-sent
+/// by a recovery or a retry use the following ids as submessage ids.
+fn max_inflight_sequence(storage: &dyn Storage) -> StdResult<u64> {
+    INFLIGHT_PACKETS
+        .keys(storage, None, None, Order::Ascending)
+        .try_fold(0, |max, key| key.map(|(_, sequence)| max.max(sequence)))
+}
+
+/// Returns the number of previous retries of a failed packet if it can be
+/// re-sent or moved to the dead letters, the packets waiting for their backoff
+/// are skipped.
+fn retryable_packet(
+    storage: &dyn Storage,
+    env: &Env,
+    policy: &IbcRetryPolicy,
+    key: (&str, u64),
+    packet: &IBCTransfer,
+) -> StdResult<Option<u32>> {
+    if packet.status != PacketLifecycleStatus::AckFailure
+        && packet.status != PacketLifecycleStatus::TimedOut
+    {
+        return Ok(None);
+    }
+
+    let Some(retry) = INFLIGHT_PACKET_RETRIES.may_load(storage, key)? else {
+        return Ok(Some(0));
+    };
+    if retry.attempts < policy.max_attempts {
+        // Wait for the backoff before retrying again
+        let backoff = policy
+            .backoff
+            .saturating_mul(2u64.saturating_pow(retry.attempts - 1));
+        if env.block.time.seconds() < retry.last_attempt.seconds().saturating_add(backoff) {
+            return Ok(None);
+        }
+    }
+    Ok(Some(retry.attempts))
+}
+
+/// Re-sends a failed transfer to its original receiver as the attempt
+/// following the `attempts` already made.
+fn resend_transfer_sub_msg(
+    deps: &mut DepsMut,
+    env: &Env,
+    packet: &IBCTransfer,
+    attempts: u32,
+    sub_msg_id: u64,
+) -> ContractResult<SubMsg> {
+    RETRIES_WAITING_FOR_REPLY.save(
+        deps.storage,
+        sub_msg_id,
+        &IBCTransferRetry {
+            attempts: attempts + 1,
+            last_attempt: env.block.time,
+        },
+    )?;
+    ibc_transfer_sub_msg(
+        deps,
+        env,
+        packet.receiver.as_str(),
+        packet.amount.clone(),
+        Some(sub_msg_id),
+        packet.route.clone(),
+        packet.depositor.clone(),
+    )
+}
+
+/// Re-sends right away a failed liquid stake token delivery without a depositor
+/// to the same receiver, since the native chain senders staking through ibc-hooks
+/// have no protocol chain account to refund. Returns `None` once the delivery
+/// has been attempted the maximum number of times of the retry policy.
+pub(crate) fn redeliver_liquid_stake_tokens(
+    deps: &mut DepsMut,
+    env: &Env,
+    key: (&str, u64),
+    packet: &IBCTransfer,
+) -> ContractResult<Option<SubMsg>> {
+    let config = CONFIG.load(deps.storage)?;
+    if packet.depositor.is_some() || packet.amount.denom != config.liquid_stake_token_denom {
+        return Ok(None);
+    }
+
+    let policy = config
+        .protocol_chain_config
+        .ibc_retry_policy
+        .unwrap_or_default();
+    let attempts = INFLIGHT_PACKET_RETRIES
+        .may_load(deps.storage, key)?
+        .map_or(0, |retry| retry.attempts);
+    if attempts >= policy.max_attempts {
+        return Ok(None);
+    }
+
+    remove_inflight_packet(deps.storage, key)?;
+    INFLIGHT_PACKET_RETRIES.remove(deps.storage, key);
+    let sub_msg_id = max_inflight_sequence(deps.storage)?.max(packet.sequence) + 1;
+    resend_transfer_sub_msg(deps, env, packet, attempts, sub_msg_id).map(Some)
+}
+
+/// Re-sends the failed IBC transfers to their original receiver, the transfers
+/// that already failed after a retry are re-sent only once the backoff has passed.
+pub fn retry_failed_transfers(
+    mut deps: DepsMut,
+    env: Env,
+    limit: Option<u32>,
+) -> ContractResult<Response> {
+    let config = CONFIG.load(deps.storage)?;
+
+    check_stopped(&config)?;
+
+    let policy = config
+        .protocol_chain_config
+        .ibc_retry_policy
+        .unwrap_or_default();
+    let limit = limit.unwrap_or(10) as usize;
+
+    // The packets waiting for their backoff are skipped before applying
+    // the limit, so they can't starve the retryable ones.
+    let failed_packets = INFLIGHT_PACKETS
+        .range(deps.storage, None, None, Order::Ascending)
+        .map(|r| {
+            r.and_then(|((channel_id, sequence), packet)| {
+                let attempts = retryable_packet(
+                    deps.storage,
+                    &env,
+                    &policy,
+                    (&channel_id, sequence),
+                    &packet,
+                )?;
+                Ok(attempts.map(|attempts| ((channel_id, sequence), packet, attempts)))
+            })
+        })
+        .filter_map(StdResult::transpose)
+        .take(limit)
+        .collect::<StdResult<Vec<_>>>()?;
+
+    // Packets to re-send with their number of previous attempts
+    let mut retried_packets: Vec<(IBCTransfer, u32)> = vec![];
+    let mut retried_ids: Vec<String> = vec![];
+    let mut dead_letters: Vec<String> = vec![];
+    for ((channel_id, sequence), mut packet, attempts) in failed_packets {
+        let key = (channel_id.as_str(), sequence);
+        if attempts >= policy.max_attempts {
+            packet.status = PacketLifecycleStatus::DeadLetter;
+            save_inflight_packet(deps.storage, key, &packet)?;
+            dead_letters.push(format!("{channel_id}/{sequence}"));
+            continue;
+        }
+
+        remove_inflight_packet(deps.storage, key)?;
+        INFLIGHT_PACKET_RETRIES.remove(deps.storage, key);
+        retried_ids.push(format!("{channel_id}/{sequence}"));
+        retried_packets.push((packet, attempts));
+    }
+
+    if retried_packets.is_empty() && dead_letters.is_empty() {
+        return Err(ContractError::NoInflightPackets {});
+    }
+
+    let max_submessage_id = max_inflight_sequence(deps.storage)?.max(
+        retried_packets
+            .iter()
+            .map(|(p, _)| p.sequence)
+            .max()
+            .unwrap_or(0),
+    );
+
+    let mut sub_msgs: Vec<SubMsg> = vec![];
+    for (index, (packet, attempts)) in retried_packets.iter().enumerate() {
+        sub_msgs.push(resend_transfer_sub_msg(
+            &mut deps,
+            &env,
+            packet,
+            *attempts,
+            max_submessage_id + (index as u64) + 1,
+        )?);
+    }
+
+    let event: Event = MilkyWayEvent::RetryIbcTransfers {
+        retried_packets: retried_ids,
+        dead_letter_packets: dead_letters,
+    }
+    .into();
+
+    Ok(Response::new()
+        .add_attribute("action", "retry_failed_transfers")
+        .add_attributes(event.attributes.clone())
+        .add_event(event)
         .add_submessages(sub_msgs))
 }
 
@@ -778,6 +1746,9 @@ pub fn update_config(
     protocol_fee_config: Option<UnsafeProtocolFeeConfig>,
     monitors: Option<Vec<String>>,
     batch_period: Option<u64>,
+    resume_contract_limits: Option<ResumeContractLimits>,
+    clear_resume_contract_limits: Option<bool>,
+    batch_limits: Option<BatchLimits>,
 ) -> ContractResult<Response> {
     ADMIN.assert_admin(deps.as_ref(), &info.sender)?;
 
@@ -827,9 +1798,46 @@ pub fn update_config(
         config.batch_period = batch_period;
     }
 
+    if clear_resume_contract_limits.unwrap_or(false) {
+        if resume_contract_limits.is_some() {
+            return Err(ContractError::InvalidResumeContractLimits {
+                reason: "the limits can't be both updated and cleared".to_string(),
+            });
+        }
+        config.resume_contract_limits = None;
+    }
+    if let Some(resume_contract_limits) = resume_contract_limits {
+        resume_contract_limits.validate()?;
+        config.resume_contract_limits = Some(resume_contract_limits);
+    }
+
+    if let Some(batch_limits) = batch_limits {
+        config.batch_limits = Some(batch_limits);
+    }
+    // Checked after both configs are updated, since the minimum liquid stake
+    // amount is part of the protocol chain config
+    if let Some(max_batch_liquid_stake) = config
+        .batch_limits
+        .as_ref()
+        .and_then(|l| l.max_batch_liquid_stake)
+    {
+        if max_batch_liquid_stake.is_zero()
+            || max_batch_liquid_stake < config.protocol_chain_config.minimum_liquid_stake_amount
+        {
+            return Err(ContractError::InvalidMaxBatchSize {});
+        }
+    }
+
     CONFIG.save(deps.storage, &config)?;
 
-    Ok(Response::new().add_attribute("action", "update_config"))
+    Ok(Response::new()
+        .add_attribute("action", "update_config")
+        .add_event(
+            MilkyWayEvent::UpdateConfig {
+                sender: info.sender.to_string(),
+            }
+            .into(),
+        ))
 }
 
 pub fn receive_rewards(mut deps: DepsMut, env: Env, info: MessageInfo) -> ContractResult<Response> {
@@ -837,6 +1845,11 @@ pub fn receive_rewards(mut deps: DepsMut, env: Env, info: MessageInfo) -> Contra
     let mut state: State = STATE.load(deps.storage)?;
 
     check_stopped(&config)?;
+    check_ibc_hooks_sender(
+        &config,
+        &info.sender,
+        config.native_chain_config.reward_collector_address.as_str(),
+    )?;
 
     if state.total_liquid_stake_token.is_zero() {
         return Err(ContractError::NoLiquidStake {});
@@ -869,6 +1882,22 @@ pub fn receive_rewards(mut deps: DepsMut, env: Env, info: MessageInfo) -> Contra
     }
     let amount_after_fees = amount_after_fees.unwrap();
 
+    // route a share of the fees to the insurance fund
+    let insurance_fund_amount = config
+        .protocol_fee_config
+        .insurance_fund_fee
+        .map(|share| share.multiply_ratio(fee, FEE_RATE_DENOMINATOR))
+        .unwrap_or_default();
+    let fee = fee - insurance_fund_amount;
+    if !insurance_fund_amount.is_zero() {
+        update_insurance_fund(
+            deps.storage,
+            env.block.time,
+            InsuranceFundOperation::Deposit {},
+            insurance_fund_amount,
+        )?;
+    }
+
     // update the accounting of tokens
     state.total_native_token += amount_after_fees;
     state.total_reward_amount += amount;
@@ -888,39 +1917,73 @@ pub fn receive_rewards(mut deps: DepsMut, env: Env, info: MessageInfo) -> Contra
             &config.protocol_chain_config.ibc_token_denom,
         ),
         None,
+        None,
+        None,
+    )?;
+    let update_oracle_msgs = update_oracle_msgs(deps.storage, &env, &config, &state, false)?;
+    let hook_msgs = hook_msgs(
+        deps.storage,
+        &env,
+        &state,
+        HookMsg::ReceiveRewards { amount, fee },
     )?;
-    let update_oracle_msgs = update_oracle_msgs(&env, &config, &state)?;
 
     let mut response = Response::new()
         .add_attribute("action", "receive_rewards")
-        .add_attribute("action", "transfer_stake")
         .add_attribute("amount", amount)
         .add_attribute("amount_after_fees", amount_after_fees)
-        .add_messages(update_oracle_msgs)
-        .add_submessage(ibc_transfer_msg);
+        .add_attribute("insurance_fund_amount", insurance_fund_amount)
+        .add_event(
+            MilkyWayEvent::ReceiveRewards {
+                denom: config.protocol_chain_config.ibc_token_denom.clone(),
+                amount,
+                fee,
+                insurance_fund_amount,
+                amount_after_fees,
+                redemption_rate: get_rates(&state).0,
+            }
+            .into(),
+        )
+        .add_submessages(update_oracle_msgs)
+        .add_submessage(ibc_transfer_msg)
+        .add_submessages(hook_msgs);
 
-    if let Some(treasury_address) = config.protocol_fee_config.treasury_address {
-        response = response.add_message(cosmwasm_std::BankMsg::Send {
-            to_address: treasury_address.to_string(),
-            amount: vec![cosmwasm_std::Coin::new(
+    if let Some(treasury_address) = config
+        .protocol_fee_config
+        .treasury_address
+        .filter(|_| !fee.is_zero())
+    {
+        response = response.add_message(Backend::bank_send(
+            env.contract.address.to_string(),
+            treasury_address.to_string(),
+            vec![Coin::new(
                 fee.u128(),
                 config.protocol_chain_config.ibc_token_denom,
             )],
-        });
+        )?);
     }
 
     Ok(response)
 }
 
+/// Receives the unstaked tokens of a batch, the tokens can be received in
+/// multiple tranches. Once the received amount reaches the expected amount,
+/// minus the configured tolerance, the batch is marked as received.
+/// Any surplus is credited as rewards and sent back to be staked.
 pub fn receive_unstaked_tokens(
-    deps: DepsMut,
-    _env: Env,
+    mut deps: DepsMut,
+    env: Env,
     info: MessageInfo,
     batch_id: u64,
 ) -> ContractResult<Response> {
     let config: Config = CONFIG.load(deps.storage)?;
 
     check_stopped(&config)?;
+    check_ibc_hooks_sender(
+        &config,
+        &info.sender,
+        config.native_chain_config.staker_address.as_str(),
+    )?;
 
     let coin = info
         .funds
@@ -934,7 +1997,7 @@ pub fn receive_unstaked_tokens(
 
     let mut batch: Batch = BATCHES.load(deps.storage, batch_id)?;
 
-    if batch.status != BatchStatus::Submitted {
+    if batch.status != BatchStatus::Submitted && batch.status != BatchStatus::PartiallyReceived {
         return Err(ContractError::BatchNotClaimable {
             batch_id: batch.id,
             status: batch.status,
@@ -944,23 +2007,150 @@ pub fn receive_unstaked_tokens(
     let expected_native_amount = batch
         .expected_native_unstaked
         .ok_or(ContractError::BatchWithoutExpectedNativeAmount { batch_id })?;
-    if expected_native_amount != amount {
-        return Err(ContractError::ReceivedWrongBatchAmount {
-            batch_id,
-            expected: expected_native_amount,
-            received: amount,
-        });
+    // the part covered by the insurance fund is already held by the contract
+    let insurance_coverage = BATCH_INSURANCE_COVERAGE
+        .may_load(deps.storage, batch_id)?
+        .unwrap_or_default();
+    let expected_amount = expected_native_amount.saturating_sub(insurance_coverage);
+
+    // While partially received, received_native_unstaked tracks the tranches
+    // received so far.
+    let previously_received = match batch.status {
+        BatchStatus::PartiallyReceived => batch.received_native_unstaked.unwrap_or_default(),
+        _ => Uint128::zero(),
+    };
+    let total_received = previously_received + amount;
+
+    let tolerance = config
+        .native_chain_config
+        .unbonding_receipt_tolerance
+        .unwrap_or_default();
+    if total_received + tolerance < expected_amount {
+        batch.received_native_unstaked = Some(total_received);
+        batch.update_status(BatchStatus::PartiallyReceived, None);
+        BATCHES.save(deps.storage, batch.id, &batch)?;
+        update_total(deps.storage, &TOTAL_CLAIMABLE, amount, Uint128::zero())?;
+
+        return Ok(Response::new()
+            .add_attribute("action", "receive_unstaked_tokens")
+            .add_attribute("batch", batch_id.to_string())
+            .add_attribute("amount", amount)
+            .add_attribute("total_received", total_received)
+            .add_attribute("status", batch.status.as_str())
+            .add_event(
+                MilkyWayEvent::ReceiveUnstakedTokens {
+                    batch_id,
+                    amount,
+                    total_received,
+                    status: batch.status.as_str().to_string(),
+                }
+                .into(),
+            ));
     }
 
-    batch.received_native_unstaked = Some(amount);
+    let surplus = total_received.saturating_sub(expected_amount);
+    let shortfall = expected_amount.saturating_sub(total_received);
+
+    batch.received_native_unstaked = Some(total_received - surplus + insurance_coverage);
     batch.update_status(BatchStatus::Received, None);
 
     BATCHES.save(deps.storage, batch.id, &batch)?;
+    BATCH_INSURANCE_COVERAGE.remove(deps.storage, batch_id);
+    update_total(
+        deps.storage,
+        &TOTAL_INSURANCE_COVERAGE,
+        Uint128::zero(),
+        insurance_coverage,
+    )?;
+    // The tranches received so far are already counted in the total
+    let claimable = batch.received_native_unstaked.unwrap_or_default();
+    BATCH_CLAIMABLE.save(deps.storage, batch.id, &claimable)?;
+    update_total(
+        deps.storage,
+        &TOTAL_CLAIMABLE,
+        claimable,
+        previously_received,
+    )?;
 
-    Ok(Response::new()
+    let mut response = Response::new()
         .add_attribute("action", "receive_unstaked_tokens")
         .add_attribute("batch", batch_id.to_string())
-        .add_attribute("amount", amount))
+        .add_attribute("amount", amount)
+        .add_attribute("total_received", total_received)
+        .add_attribute("status", batch.status.as_str())
+        .add_attribute("insurance_coverage", insurance_coverage)
+        .add_event(
+            MilkyWayEvent::ReceiveUnstakedTokens {
+                batch_id,
+                amount,
+                total_received,
+                status: batch.status.as_str().to_string(),
+            }
+            .into(),
+        );
+
+    if !shortfall.is_zero() {
+        response = response.add_event(
+            MilkyWayEvent::UnstakedTokensShortfall {
+                batch_id,
+                expected: expected_amount,
+                received: total_received,
+                shortfall,
+            }
+            .into(),
+        );
+    }
+
+    // Send the unstaked tokens to the native chain accounts, the remaining
+    // requests are handled by WithdrawNativeUnstakes.
+    let (native_withdraw_msgs, native_withdraw_events) =
+        native_withdraw_sub_msgs(&mut deps, &env, &config, &batch, NATIVE_WITHDRAW_LIMIT)?;
+    if !native_withdraw_msgs.is_empty() {
+        response = response
+            .add_attribute("native_withdrawals", native_withdraw_msgs.len().to_string())
+            .add_submessages(native_withdraw_msgs)
+            .add_events(native_withdraw_events);
+    }
+
+    if !surplus.is_zero() {
+        // The surplus is credited as rewards and staked again. Unbonding tokens
+        // don't earn staking rewards, so the surplus is an excess of principal
+        // (e.g. the rounding of the unbonded amounts) that belongs to the stakers
+        // and no protocol fee is taken on it.
+        let mut state = STATE.load(deps.storage)?;
+        state.total_native_token += surplus;
+        state.total_reward_amount += surplus;
+        STATE.save(deps.storage, &state)?;
+
+        let ibc_transfer_msg = ibc_transfer_sub_msg(
+            &mut deps,
+            &env,
+            &config.native_chain_config.staker_address,
+            Coin::new(
+                surplus.u128(),
+                &config.protocol_chain_config.ibc_token_denom,
+            ),
+            None,
+            None,
+            None,
+        )?;
+        let update_oracle_msgs = update_oracle_msgs(deps.storage, &env, &config, &state, false)?;
+
+        response = response
+            .add_attribute("surplus", surplus)
+            .add_submessages(update_oracle_msgs)
+            .add_submessage(ibc_transfer_msg);
+    }
+
+    let state = STATE.load(deps.storage)?;
+    response = response.add_submessages(hook_msgs(
+        deps.storage,
+        &env,
+        &state,
+        HookMsg::ReceiveUnstakedTokens { batch_id, amount },
+    )?);
+
+    Ok(response)
 }
 
 pub fn circuit_breaker(deps: DepsMut, _env: Env, info: MessageInfo) -> ContractResult<Response> {
@@ -974,48 +2164,205 @@ pub fn circuit_breaker(deps: DepsMut, _env: Env, info: MessageInfo) -> ContractR
         return Err(ContractError::Unauthorized { sender });
     }
 
+    // Keep track of the redemption rate before the stop so that it can
+    // be used as reference when the contract is resumed.
+    if !config.stopped {
+        let state = STATE.load(deps.storage)?;
+        let (redemption_rate, _) = get_rates(&state);
+        STOPPED_REDEMPTION_RATE.save(deps.storage, &redemption_rate)?;
+    }
+
     config.stopped = true;
     CONFIG.save(deps.storage, &config)?;
 
-    Ok(Response::new().add_attribute("action", "circuit_breaker"))
+    Ok(Response::new()
+        .add_attribute("action", "circuit_breaker")
+        .add_event(MilkyWayEvent::CircuitBreaker { sender }.into()))
 }
 
-pub fn resume_contract(
-    deps: DepsMut,
-    env: Env,
-    info: MessageInfo,
-    total_native_token: Uint128,
-    total_liquid_stake_token: Uint128,
-    total_reward_amount: Uint128,
-) -> ContractResult<Response> {
-    ADMIN.assert_admin(deps.as_ref(), &info.sender)?;
+/// A value provided to [resume_contract] that exceeds the configured
+/// [ResumeContractLimits].
+struct ExceededResumeLimit {
+    field: &'static str,
+    reference: String,
+    value: String,
+    deviation: Option<Decimal>,
+    max_deviation: Decimal,
+}
 
-    let mut config: Config = CONFIG.load(deps.storage)?;
-    if !config.stopped {
-        return Err(ContractError::NotStopped {});
+impl ExceededResumeLimit {
+    fn into_event(self) -> Event {
+        MilkyWayEvent::ResumeContractLimitExceeded {
+            field: self.field.to_string(),
+            reference: self.reference,
+            value: self.value,
+            deviation: self.deviation,
+            max_deviation: self.max_deviation,
+        }
+        .into()
+    }
+}
+
+/// Checks the values provided to [resume_contract] against the configured
+/// limits, returning the ones that exceeds them.
+fn check_resume_contract_limits(
+    deps: Deps,
+    limits: &ResumeContractLimits,
+    current_state: &State,
+    new_state: &State,
+) -> ContractResult<Vec<ExceededResumeLimit>> {
+    let mut exceeded = vec![];
+
+    let totals = [
+        (
+            "total_native_token",
+            current_state.total_native_token,
+            new_state.total_native_token,
+            limits.max_total_native_token_deviation,
+        ),
+        (
+            "total_liquid_stake_token",
+            current_state.total_liquid_stake_token,
+            new_state.total_liquid_stake_token,
+            limits.max_total_liquid_stake_token_deviation,
+        ),
+        (
+            "total_reward_amount",
+            current_state.total_reward_amount,
+            new_state.total_reward_amount,
+            limits.max_total_reward_amount_deviation,
+        ),
+    ];
+    for (field, reference, value, max_deviation) in totals {
+        let deviation = compute_deviation(reference, value);
+        if deviation.map_or(true, |d| d > max_deviation) {
+            exceeded.push(ExceededResumeLimit {
+                field,
+                reference: reference.to_string(),
+                value: value.to_string(),
+                deviation,
+                max_deviation,
+            });
+        }
+    }
+
+    let (new_redemption_rate, _) = get_rates(new_state);
+
+    // Redemption rate before the contract was stopped
+    let stopped_redemption_rate = STOPPED_REDEMPTION_RATE.may_load(deps.storage)?;
+
+    // Redemption rate of the last received batch
+    let last_batch_redemption_rate = BATCHES
+        .range(deps.storage, None, None, Order::Descending)
+        .find(|r| {
+            r.as_ref()
+                .map_or(true, |(_, batch)| batch.status == BatchStatus::Received)
+        })
+        .transpose()?
+        .and_then(|(_, batch)| {
+            batch
+                .expected_native_unstaked
+                .filter(|_| !batch.batch_total_liquid_stake.is_zero())
+                .map(|expected| Decimal::from_ratio(expected, batch.batch_total_liquid_stake))
+        });
+
+    let rates = [
+        ("redemption_rate", stopped_redemption_rate),
+        ("last_batch_redemption_rate", last_batch_redemption_rate),
+    ];
+    for (field, reference) in rates {
+        let Some(reference) = reference else {
+            continue;
+        };
+
+        let deviation = compute_deviation(reference.atomics(), new_redemption_rate.atomics());
+        if deviation.map_or(true, |d| d > limits.max_redemption_rate_deviation) {
+            exceeded.push(ExceededResumeLimit {
+                field,
+                reference: reference.to_string(),
+                value: new_redemption_rate.to_string(),
+                deviation,
+                max_deviation: limits.max_redemption_rate_deviation,
+            });
+        }
+    }
+
+    Ok(exceeded)
+}
+
+pub fn resume_contract(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    total_native_token: Uint128,
+    total_liquid_stake_token: Uint128,
+    total_reward_amount: Uint128,
+    force: bool,
+) -> ContractResult<Response> {
+    ADMIN.assert_admin(deps.as_ref(), &info.sender)?;
+
+    let mut config: Config = CONFIG.load(deps.storage)?;
+    if !config.stopped {
+        return Err(ContractError::NotStopped {});
+    }
+    if IMPORTING_BATCHES.may_load(deps.storage)?.unwrap_or(false) {
+        return Err(ContractError::ImportNotFinished {});
+    }
+    check_denom_admin_not_transferred(deps.storage)?;
+
+    let current_state = STATE.load(deps.storage)?;
+    let state = State {
+        total_native_token,
+        total_liquid_stake_token,
+        total_reward_amount,
+        ..current_state.clone()
+    };
+
+    let exceeded_limits = match &config.resume_contract_limits {
+        Some(limits) => {
+            check_resume_contract_limits(deps.as_ref(), limits, &current_state, &state)?
+        }
+        None => vec![],
+    };
+    if !force {
+        if let Some(exceeded) = exceeded_limits.first() {
+            return Err(ContractError::ResumeContractLimitExceeded {
+                field: exceeded.field.to_string(),
+                reference: exceeded.reference.clone(),
+                value: exceeded.value.clone(),
+                max_deviation: exceeded.max_deviation,
+            });
+        }
     }
 
     config.stopped = false;
     CONFIG.save(deps.storage, &config)?;
+    STATE.save(deps.storage, &state)?;
+    STOPPED_REDEMPTION_RATE.remove(deps.storage);
 
-    let state = STATE.update(
-        deps.storage,
-        |mut state| -> Result<State, cosmwasm_std::StdError> {
-            state.total_native_token = total_native_token;
-            state.total_liquid_stake_token = total_liquid_stake_token;
-            state.total_reward_amount = total_reward_amount;
-            Ok(state)
-        },
-    )?;
-
-    let update_oracle_msgs = update_oracle_msgs(&env, &config, &state)?;
+    let update_oracle_msgs = update_oracle_msgs(deps.storage, &env, &config, &state, false)?;
 
     Ok(Response::new()
         .add_attribute("action", "resume_contract")
         .add_attribute("total_native_token", total_native_token)
         .add_attribute("total_liquid_stake_token", total_liquid_stake_token)
         .add_attribute("total_reward_amount", total_reward_amount)
-        .add_messages(update_oracle_msgs))
+        .add_attribute("forced", (!exceeded_limits.is_empty()).to_string())
+        .add_event(
+            MilkyWayEvent::ResumeContract {
+                total_native_token,
+                total_liquid_stake_token,
+                total_reward_amount,
+                forced: !exceeded_limits.is_empty(),
+            }
+            .into(),
+        )
+        .add_events(
+            exceeded_limits
+                .into_iter()
+                .map(ExceededResumeLimit::into_event),
+        )
+        .add_submessages(update_oracle_msgs))
 }
 
 pub fn slash_batches(
@@ -1051,7 +2398,200 @@ pub fn slash_batches(
 
     Ok(Response::new()
         .add_attribute("action", "slash_batches")
-        .add_attribute("updated_batches", serde_json::to_string(&expected_amounts)?))
+        .add_attribute(
+            "updated_batches",
+            expected_amounts
+                .iter()
+                .map(|b| b.batch_id.to_string())
+                .collect::<Vec<_>>()
+                .join(","),
+        )
+        .add_events(expected_amounts.into_iter().map(|b| {
+            MilkyWayEvent::SlashBatch {
+                batch_id: b.batch_id,
+                expected_native_unstaked: b.amount,
+            }
+            .into()
+        })))
+}
+
+pub fn record_slash(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    validator: String,
+    amount: Uint128,
+    infraction_height: u64,
+    batches: Vec<BatchSlashAmount>,
+) -> ContractResult<Response> {
+    ADMIN.assert_admin(deps.as_ref(), &info.sender)?;
+
+    // Like slash_batches, the contract must be stopped to record a slash
+    let config = CONFIG.load(deps.storage)?;
+    if !config.stopped {
+        return Err(ContractError::NotStopped {});
+    }
+    let validator = validate_address(
+        &validator,
+        &config.native_chain_config.validator_address_prefix,
+    )?;
+
+    // Each batch can only be slashed once per slash
+    let mut batch_ids = batches.iter().map(|b| b.batch_id).collect::<Vec<_>>();
+    batch_ids.sort_unstable();
+    if let Some(ids) = batch_ids.windows(2).find(|ids| ids[0] == ids[1]) {
+        return Err(ContractError::DuplicateSlashBatch { batch_id: ids[0] });
+    }
+
+    // The insurance fund covers the losses first
+    let mut insurance_fund_available = INSURANCE_FUND_BALANCE
+        .may_load(deps.storage)?
+        .unwrap_or_default();
+    let mut insurance_covered_amount = Uint128::zero();
+
+    // Remove the slashed amounts from the batches
+    let mut batches_amount = Uint128::zero();
+    for batch_slash_amount in batches.iter() {
+        let mut batch = BATCHES.load(deps.storage, batch_slash_amount.batch_id)?;
+        if batch.status != BatchStatus::Submitted {
+            return Err(ContractError::UnexpecedBatchStatus {
+                actual: batch.status,
+            });
+        }
+
+        let expected_native_unstaked = batch
+            .expected_native_unstaked
+            .ok_or(ContractError::BatchWithoutExpectedNativeAmount { batch_id: batch.id })?;
+        let batch_coverage = BATCH_INSURANCE_COVERAGE
+            .may_load(deps.storage, batch.id)?
+            .unwrap_or_default();
+        let available = expected_native_unstaked
+            .checked_sub(batch_coverage)
+            .map_err(|_| ContractError::SlashExceedsAvailable {
+                target: format!("batch {}", batch.id),
+                available: expected_native_unstaked,
+                slashed: batch_coverage,
+            })?;
+        if batch_slash_amount.amount > available {
+            return Err(ContractError::SlashExceedsAvailable {
+                target: format!("batch {}", batch.id),
+                available,
+                slashed: batch_slash_amount.amount,
+            });
+        }
+
+        // Only the part not covered by the insurance fund is lost
+        let covered = batch_slash_amount.amount.min(insurance_fund_available);
+        insurance_fund_available -= covered;
+        insurance_covered_amount += covered;
+        batch.expected_native_unstaked =
+            Some(expected_native_unstaked - (batch_slash_amount.amount - covered));
+        BATCHES.save(deps.storage, batch.id, &batch)?;
+        if !covered.is_zero() {
+            BATCH_INSURANCE_COVERAGE.save(deps.storage, batch.id, &(batch_coverage + covered))?;
+            update_total(
+                deps.storage,
+                &TOTAL_INSURANCE_COVERAGE,
+                covered,
+                Uint128::zero(),
+            )?;
+        }
+
+        batches_amount += batch_slash_amount.amount;
+    }
+
+    // The remaining amount has been slashed from the delegated tokens
+    let delegated_amount =
+        amount
+            .checked_sub(batches_amount)
+            .map_err(|_| ContractError::SlashExceedsAvailable {
+                target: "slash amount".to_string(),
+                available: amount,
+                slashed: batches_amount,
+            })?;
+    let delegated_covered = delegated_amount.min(insurance_fund_available);
+    insurance_covered_amount += delegated_covered;
+    let mut state = STATE.load(deps.storage)?;
+    state.total_native_token = state
+        .total_native_token
+        .checked_sub(delegated_amount - delegated_covered)
+        .map_err(|_| ContractError::SlashExceedsAvailable {
+            target: "total_native_token".to_string(),
+            available: state.total_native_token,
+            slashed: delegated_amount - delegated_covered,
+        })?;
+    STATE.save(deps.storage, &state)?;
+
+    let id = SLASH_EVENTS
+        .keys(deps.storage, None, None, Order::Descending)
+        .next()
+        .transpose()?
+        .map_or(1, |id| id + 1);
+    SLASH_EVENTS.save(
+        deps.storage,
+        id,
+        &SlashEvent {
+            id,
+            validator: validator.clone(),
+            amount,
+            infraction_height,
+            batches,
+            delegated_amount,
+            insurance_covered_amount,
+            recorded_at: env.block.time,
+        },
+    )?;
+
+    if !insurance_covered_amount.is_zero() {
+        update_insurance_fund(
+            deps.storage,
+            env.block.time,
+            InsuranceFundOperation::CoverSlash { slash_id: id },
+            insurance_covered_amount,
+        )?;
+    }
+
+    let update_oracle_msgs = update_oracle_msgs(deps.storage, &env, &config, &state, false)?;
+
+    let mut response = Response::new()
+        .add_attribute("action", "record_slash")
+        .add_attribute("slash_id", id.to_string())
+        .add_attribute("validator", validator.clone())
+        .add_attribute("amount", amount)
+        .add_attribute("infraction_height", infraction_height.to_string())
+        .add_attribute("batches_amount", batches_amount)
+        .add_attribute("delegated_amount", delegated_amount)
+        .add_attribute("insurance_covered_amount", insurance_covered_amount)
+        .add_event(
+            MilkyWayEvent::RecordSlash {
+                slash_id: id,
+                validator: validator.to_string(),
+                amount,
+                delegated_amount,
+                insurance_covered_amount,
+            }
+            .into(),
+        )
+        .add_submessages(update_oracle_msgs);
+
+    // Send the tokens covering the delegated losses to Celestia to be staked
+    if !delegated_covered.is_zero() {
+        let ibc_transfer_msg = ibc_transfer_sub_msg(
+            &mut deps,
+            &env,
+            &config.native_chain_config.staker_address,
+            Coin::new(
+                delegated_covered.u128(),
+                &config.protocol_chain_config.ibc_token_denom,
+            ),
+            None,
+            None,
+            None,
+        )?;
+        response = response.add_submessage(ibc_transfer_msg);
+    }
+
+    Ok(response)
 }
 
 pub fn handle_ibc_reply(deps: DepsMut, msg: cosmwasm_std::Reply) -> ContractResult<Response> {
@@ -1070,8 +2610,12 @@ pub fn handle_ibc_reply(deps: DepsMut, msg: cosmwasm_std::Reply) -> ContractResu
             msg: format!("could not decode response: {b}"),
         })?;
 
-    let IbcWaitingForReply { amount, receiver } =
-        IBC_WAITING_FOR_REPLY.load(deps.storage, msg.id)?;
+    let IbcWaitingForReply {
+        amount,
+        receiver,
+        route,
+        depositor,
+    } = IBC_WAITING_FOR_REPLY.load(deps.storage, msg.id)?;
     IBC_WAITING_FOR_REPLY.remove(deps.storage, msg.id);
 
     let recovery = IBCTransfer {
@@ -1079,10 +2623,26 @@ pub fn handle_ibc_reply(deps: DepsMut, msg: cosmwasm_std::Reply) -> ContractResu
         amount,
         receiver,
         status: PacketLifecycleStatus::Sent,
+        route,
+        depositor,
     };
 
+    // The sequences are only unique within a channel, so the in-flight
+    // packets are tracked by the channel they have been sent through.
+    let config = CONFIG.load(deps.storage)?;
+    let channel_id = recovery
+        .channel_id(&config.protocol_chain_config.ibc_channel_id)
+        .to_string();
+    let key = (channel_id.as_str(), transfer_response.sequence);
+
     // Save as in-flight to be able to manipulate when the ack/timeout is received
-    INFLIGHT_PACKETS.save(deps.storage, transfer_response.sequence, &recovery)?;
+    save_inflight_packet(deps.storage, key, &recovery)?;
+
+    // Keep track of the attempts if the transfer is a retry
+    if let Some(retry) = RETRIES_WAITING_FOR_REPLY.may_load(deps.storage, msg.id)? {
+        RETRIES_WAITING_FOR_REPLY.remove(deps.storage, msg.id);
+        INFLIGHT_PACKET_RETRIES.save(deps.storage, key, &retry)?;
+    }
 
     let response = Response::new()
         .add_attribute("action", "handle_ibc_reply")
@@ -1090,6 +2650,13 @@ pub fn handle_ibc_reply(deps: DepsMut, msg: cosmwasm_std::Reply) -> ContractResu
         .add_attribute(
             "packet_sequence",
             format!("{:?}", transfer_response.sequence),
+        )
+        .add_event(
+            MilkyWayEvent::IbcTransferSent {
+                channel: channel_id.clone(),
+                sequence: transfer_response.sequence,
+            }
+            .into(),
         );
 
     Ok(response)
@@ -1143,18 +2710,402 @@ pub fn fee_withdraw(
     state.total_fees = state.total_fees.checked_sub(amount).unwrap();
     STATE.save(deps.storage, &state)?;
 
-    let send_msg = MsgSend {
-        from_address: env.contract.address.to_string(),
-        to_address: treasury_address.clone(),
-        amount: vec![OsmosisCoin {
-            denom: config.protocol_chain_config.ibc_token_denom,
-            amount: amount.to_string(),
-        }],
-    };
+    let send_msg = Backend::bank_send(
+        env.contract.address.to_string(),
+        treasury_address.clone(),
+        coins(amount.u128(), &config.protocol_chain_config.ibc_token_denom),
+    )?;
 
     Ok(Response::new()
         .add_attribute("action", "fee_withdraw")
-        .add_attribute("receiver", treasury_address)
+        .add_attribute("receiver", treasury_address.clone())
         .add_attribute("amount", amount)
+        .add_event(
+            MilkyWayEvent::FeeWithdraw {
+                receiver: treasury_address,
+                denom: config.protocol_chain_config.ibc_token_denom,
+                amount,
+            }
+            .into(),
+        )
         .add_message(send_msg))
 }
+
+pub fn set_destination_chain(
+    deps: DepsMut,
+    info: MessageInfo,
+    bech32_prefix: String,
+    channel_id: String,
+    hop: Option<IbcHop>,
+) -> ContractResult<Response> {
+    ADMIN.assert_admin(deps.as_ref(), &info.sender)?;
+
+    let config = CONFIG.load(deps.storage)?;
+    let bech32_prefix = validate_address_prefix(&bech32_prefix)?;
+    // Addresses of the native and protocol chains are already handled
+    if bech32_prefix == config.native_chain_config.account_address_prefix
+        || bech32_prefix == config.protocol_chain_config.account_address_prefix
+    {
+        return Err(ContractError::InvalidAddress {});
+    }
+
+    let hop = hop
+        .map(|hop| -> ContractResult<IbcHop> {
+            Ok(IbcHop {
+                receiver: hop.receiver,
+                channel_id: validate_ibc_channel(&hop.channel_id)?,
+            })
+        })
+        .transpose()?;
+    let destination_chain = DestinationChain {
+        bech32_prefix: bech32_prefix.clone(),
+        channel_id: validate_ibc_channel(&channel_id)?,
+        hop,
+    };
+    DESTINATION_CHAINS.save(deps.storage, &bech32_prefix, &destination_chain)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "set_destination_chain")
+        .add_attribute("bech32_prefix", bech32_prefix.clone())
+        .add_attribute("channel_id", destination_chain.channel_id.clone())
+        .add_event(
+            MilkyWayEvent::SetDestinationChain {
+                bech32_prefix,
+                channel_id: destination_chain.channel_id,
+            }
+            .into(),
+        ))
+}
+
+pub fn remove_destination_chain(
+    deps: DepsMut,
+    info: MessageInfo,
+    bech32_prefix: String,
+) -> ContractResult<Response> {
+    ADMIN.assert_admin(deps.as_ref(), &info.sender)?;
+
+    if !DESTINATION_CHAINS.has(deps.storage, &bech32_prefix) {
+        return Err(ContractError::DestinationChainNotFound { bech32_prefix });
+    }
+    DESTINATION_CHAINS.remove(deps.storage, &bech32_prefix);
+
+    Ok(Response::new()
+        .add_attribute("action", "remove_destination_chain")
+        .add_attribute("bech32_prefix", bech32_prefix.clone())
+        .add_event(MilkyWayEvent::RemoveDestinationChain { bech32_prefix }.into()))
+}
+
+pub fn set_denom_metadata(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    metadata: DenomMetadata,
+) -> ContractResult<Response> {
+    ADMIN.assert_admin(deps.as_ref(), &info.sender)?;
+    metadata.validate()?;
+
+    let config = CONFIG.load(deps.storage)?;
+    let event = MilkyWayEvent::SetDenomMetadata {
+        denom: config.liquid_stake_token_denom.clone(),
+        display: metadata.display.clone(),
+        symbol: metadata.symbol.clone(),
+        exponent: metadata.exponent,
+    };
+    let set_metadata_msg = tokenfactory::set_denom_metadata(
+        env.contract.address.to_string(),
+        config.liquid_stake_token_denom.clone(),
+        metadata,
+    )?;
+
+    Ok(Response::new()
+        .add_attribute("action", "set_denom_metadata")
+        .add_attribute("denom", config.liquid_stake_token_denom)
+        .add_event(event.into())
+        .add_message(set_metadata_msg))
+}
+
+pub fn transfer_denom_admin(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    new_admin: String,
+) -> ContractResult<Response> {
+    ADMIN.assert_admin(deps.as_ref(), &info.sender)?;
+
+    let transfer = DenomAdminTransfer {
+        new_admin: deps.api.addr_validate(&new_admin)?,
+        min_time: env.block.time.plus_seconds(DENOM_ADMIN_TRANSFER_DELAY),
+    };
+    PENDING_DENOM_ADMIN_TRANSFER.save(deps.storage, &transfer)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "transfer_denom_admin")
+        .add_attribute("new_admin", new_admin.clone())
+        .add_attribute("min_time", transfer.min_time.seconds().to_string())
+        .add_event(
+            MilkyWayEvent::TransferDenomAdmin {
+                new_admin,
+                min_time: transfer.min_time.seconds(),
+            }
+            .into(),
+        ))
+}
+
+pub fn revoke_denom_admin_transfer(deps: DepsMut, info: MessageInfo) -> ContractResult<Response> {
+    ADMIN.assert_admin(deps.as_ref(), &info.sender)?;
+
+    let transfer = PENDING_DENOM_ADMIN_TRANSFER
+        .may_load(deps.storage)?
+        .ok_or(ContractError::NoPendingDenomAdminTransfer {})?;
+    PENDING_DENOM_ADMIN_TRANSFER.remove(deps.storage);
+
+    Ok(Response::new()
+        .add_attribute("action", "revoke_denom_admin_transfer")
+        .add_attribute("new_admin", transfer.new_admin.clone())
+        .add_event(
+            MilkyWayEvent::RevokeDenomAdminTransfer {
+                new_admin: transfer.new_admin.to_string(),
+            }
+            .into(),
+        ))
+}
+
+/// Transfers the admin of the liquid stake token. The contract can't mint or
+/// burn the token afterwards, so it's stopped.
+pub fn complete_denom_admin_transfer(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+) -> ContractResult<Response> {
+    ADMIN.assert_admin(deps.as_ref(), &info.sender)?;
+
+    let transfer = PENDING_DENOM_ADMIN_TRANSFER
+        .may_load(deps.storage)?
+        .ok_or(ContractError::NoPendingDenomAdminTransfer {})?;
+    if transfer.min_time > env.block.time {
+        return Err(ContractError::DenomAdminTransferNotReady {
+            time_to_complete: transfer.min_time,
+        });
+    }
+
+    // The liquid stake tokens of the pending and rolled over batches are held
+    // by the contract, they can't be burnt once it stops for good
+    let pending_batch_id = PENDING_BATCH_ID.load(deps.storage)?;
+    for batch_id in pending_batch_id..pending_batch_id + MAX_ROLLED_OVER_BATCHES {
+        let has_requests = BATCHES
+            .may_load(deps.storage, batch_id)?
+            .map_or(false, |batch| !batch.batch_total_liquid_stake.is_zero());
+        if has_requests {
+            return Err(ContractError::PendingUnstakeRequests { batch_id });
+        }
+    }
+
+    PENDING_DENOM_ADMIN_TRANSFER.remove(deps.storage);
+    DENOM_ADMIN_TRANSFERRED.save(deps.storage, &true)?;
+
+    let mut config = CONFIG.load(deps.storage)?;
+    config.stopped = true;
+    CONFIG.save(deps.storage, &config)?;
+
+    let change_admin_msg = tokenfactory::change_admin(
+        env.contract.address.to_string(),
+        config.liquid_stake_token_denom.clone(),
+        transfer.new_admin.to_string(),
+    )?;
+
+    Ok(Response::new()
+        .add_attribute("action", "complete_denom_admin_transfer")
+        .add_attribute("denom", config.liquid_stake_token_denom.clone())
+        .add_attribute("new_admin", transfer.new_admin.clone())
+        .add_event(
+            MilkyWayEvent::CompleteDenomAdminTransfer {
+                denom: config.liquid_stake_token_denom,
+                new_admin: transfer.new_admin.to_string(),
+            }
+            .into(),
+        )
+        .add_message(change_admin_msg))
+}
+
+/// Unstaked tokens held by the contract for a batch, counted in [TOTAL_CLAIMABLE].
+fn held_unstaked_tokens(storage: &dyn Storage, batch: &Batch) -> StdResult<Uint128> {
+    Ok(match batch.status {
+        BatchStatus::Received => BATCH_CLAIMABLE
+            .may_load(storage, batch.id)?
+            .unwrap_or_default(),
+        BatchStatus::PartiallyReceived => batch.received_native_unstaked.unwrap_or_default(),
+        _ => Uint128::zero(),
+    })
+}
+
+/// Checks the unstake requests imported so far against their batch and
+/// tracks the tokens claimable from it if it has been received. `previous`
+/// is the batch before the import, if any.
+fn update_imported_batch(
+    storage: &mut dyn Storage,
+    previous: Option<&Batch>,
+    batch: &Batch,
+) -> StdResult<()> {
+    let held_before = match previous {
+        Some(previous) => held_unstaked_tokens(storage, previous)?,
+        None => Uint128::zero(),
+    };
+
+    let (amount, count) = IMPORTED_REQUESTS_TOTALS
+        .may_load(storage, batch.id)?
+        .unwrap_or_default();
+    let expected_count = batch.unstake_requests_count.unwrap_or(0);
+
+    // Requests already withdrawn from the received batches aren't imported
+    let valid = if batch.status == BatchStatus::Received {
+        amount <= batch.batch_total_liquid_stake && count <= expected_count
+    } else {
+        amount == batch.batch_total_liquid_stake && count == expected_count
+    };
+    if valid {
+        UNBALANCED_IMPORTED_BATCHES.remove(storage, batch.id);
+    } else {
+        UNBALANCED_IMPORTED_BATCHES.save(storage, batch.id, &Empty {})?;
+    }
+
+    if batch.status == BatchStatus::Received && !batch.batch_total_liquid_stake.is_zero() {
+        let claimable = batch
+            .received_native_unstaked
+            .unwrap_or_default()
+            .multiply_ratio(amount, batch.batch_total_liquid_stake);
+        BATCH_CLAIMABLE.save(storage, batch.id, &claimable)?;
+    } else {
+        BATCH_CLAIMABLE.remove(storage, batch.id);
+    }
+
+    let held_after = held_unstaked_tokens(storage, batch)?;
+    update_total(storage, &TOTAL_CLAIMABLE, held_after, held_before)
+}
+
+/// Closes the import if the unstake requests of every batch are balanced,
+/// the batches are checked while they're imported.
+fn finish_batches_import(storage: &mut dyn Storage) -> ContractResult<()> {
+    let unbalanced_batch_id = UNBALANCED_IMPORTED_BATCHES
+        .keys(storage, None, None, Order::Ascending)
+        .next()
+        .transpose()?;
+    if let Some(id) = unbalanced_batch_id {
+        let batch = BATCHES.load(storage, id)?;
+        let (amount, count) = IMPORTED_REQUESTS_TOTALS
+            .may_load(storage, id)?
+            .unwrap_or_default();
+        return Err(ContractError::InvalidImportedBatch {
+            id,
+            reason: format!(
+                "{count} unstake requests of {amount} imported for {} requests of {}",
+                batch.unstake_requests_count.unwrap_or(0),
+                batch.batch_total_liquid_stake
+            ),
+        });
+    }
+
+    IMPORTING_BATCHES.remove(storage);
+    Ok(())
+}
+
+pub fn import_batches(
+    mut deps: DepsMut,
+    info: MessageInfo,
+    batches: Vec<Batch>,
+    imported_requests: Vec<ImportedUnstakeRequest>,
+    finish: bool,
+) -> ContractResult<Response> {
+    ADMIN.assert_admin(deps.as_ref(), &info.sender)?;
+
+    if !IMPORTING_BATCHES.may_load(deps.storage)?.unwrap_or(false) {
+        return Err(ContractError::ImportClosed {});
+    }
+    let config = CONFIG.load(deps.storage)?;
+    if !config.stopped {
+        return Err(ContractError::NotStopped {});
+    }
+
+    let pending_batch_id = PENDING_BATCH_ID.load(deps.storage)?;
+    let batches_count = batches.len() as u64;
+    for mut batch in batches {
+        // The requests exceeding the maximum batch size may have been rolled
+        // over into the batches following the pending one
+        if batch.id >= pending_batch_id + MAX_ROLLED_OVER_BATCHES {
+            return Err(ContractError::InvalidImportedBatch {
+                id: batch.id,
+                reason: format!(
+                    "batch id is greater than the last batch an unstake request can be rolled over into ({})",
+                    pending_batch_id + MAX_ROLLED_OVER_BATCHES - 1
+                ),
+            });
+        }
+        if (batch.status == BatchStatus::Pending) != (batch.id >= pending_batch_id) {
+            return Err(ContractError::InvalidImportedBatch {
+                id: batch.id,
+                reason: "only the pending batch and the following ones can have the pending status"
+                    .to_string(),
+            });
+        }
+        if batch.status == BatchStatus::Pending && batch.next_batch_action_time.is_none() {
+            // Carry over the schedule of the pending batch
+            let pending_batch = BATCHES.load(deps.storage, pending_batch_id)?;
+            batch.next_batch_action_time = pending_batch
+                .next_batch_action_time
+                .map(|time| time + config.batch_period * (batch.id - pending_batch_id));
+        }
+        let previous = BATCHES.may_load(deps.storage, batch.id)?;
+        BATCHES.save(deps.storage, batch.id, &batch)?;
+        update_imported_batch(deps.storage, previous.as_ref(), &batch)?;
+    }
+
+    let unstake_requests_count = imported_requests.len() as u64;
+    for request in imported_requests {
+        if !BATCHES.has(deps.storage, request.batch_id) {
+            return Err(ContractError::InvalidImportedBatch {
+                id: request.batch_id,
+                reason: "unstake request of a batch that hasn't been imported".to_string(),
+            });
+        }
+        let prefix = if request.native {
+            &config.native_chain_config.account_address_prefix
+        } else {
+            &config.protocol_chain_config.account_address_prefix
+        };
+        let user = validate_address(&request.user, prefix)?.to_string();
+        if unstake_requests().has(deps.storage, (request.batch_id, user.clone())) {
+            return Err(ContractError::InvalidImportedBatch {
+                id: request.batch_id,
+                reason: format!("unstake request of {user} imported twice"),
+            });
+        }
+
+        new_unstake_request(&mut deps, user.clone(), request.batch_id, request.amount)?;
+        if request.native {
+            NATIVE_UNSTAKE_REQUESTS.save(deps.storage, (request.batch_id, &user), &Empty {})?;
+        }
+        IMPORTED_REQUESTS_TOTALS.update(deps.storage, request.batch_id, |totals| {
+            let (amount, count) = totals.unwrap_or_default();
+            StdResult::Ok((amount + request.amount, count + 1))
+        })?;
+        let batch = BATCHES.load(deps.storage, request.batch_id)?;
+        update_imported_batch(deps.storage, Some(&batch), &batch)?;
+    }
+
+    if finish {
+        finish_batches_import(deps.storage)?;
+    }
+
+    Ok(Response::new()
+        .add_attribute("action", "import_batches")
+        .add_attribute("batches", batches_count.to_string())
+        .add_attribute("unstake_requests", unstake_requests_count.to_string())
+        .add_attribute("finished", finish.to_string())
+        .add_event(
+            MilkyWayEvent::ImportBatches {
+                batches: batches_count,
+                unstake_requests: unstake_requests_count,
+                finished: finish,
+            }
+            .into(),
+        ))
+}
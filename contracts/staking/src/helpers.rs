@@ -132,6 +132,17 @@ pub fn get_rates(state: &State) -> (Decimal, Decimal) {
     }
 }
 
+/// Computes the deviation of `value` relative to `reference`.
+/// Returns `None` if the deviation can't be computed, which happens
+/// when `reference` is zero and `value` is not.
+pub fn compute_deviation(reference: Uint128, value: Uint128) -> Option<Decimal> {
+    if reference == value {
+        return Some(Decimal::zero());
+    }
+
+    Decimal::checked_from_ratio(reference.abs_diff(value), reference).ok()
+}
+
 /// Checks if the provided denom is valid or not.
 pub fn validate_denom(denom: impl Into<String>) -> StdResult<String> {
     let denom: String = denom.into();
@@ -0,0 +1,55 @@
+// Types of the callbacks sent to the contracts subscribed to the protocol events
+use crate::state::HookEvent;
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{Decimal, Uint128};
+
+/// Message executed on the subscribed contracts.
+#[cw_serde]
+pub enum HookExecuteMsg {
+    MilkywayHook {
+        /// The protocol event.
+        msg: HookMsg,
+        /// Redemption rate after the event.
+        redemption_rate: Decimal,
+        /// Purchase rate after the event.
+        purchase_rate: Decimal,
+    },
+}
+
+#[cw_serde]
+pub enum HookMsg {
+    /// Native tokens have been liquid staked.
+    LiquidStake {
+        user: String,
+        amount: Uint128,
+        mint_amount: Uint128,
+    },
+    /// Liquid stake tokens have been added to the unstake requests of a batch.
+    LiquidUnstake {
+        user: String,
+        batch_id: u64,
+        amount: Uint128,
+    },
+    /// A batch has been submitted.
+    SubmitBatch {
+        batch_id: u64,
+        batch_total_liquid_stake: Uint128,
+        expected_native_unstaked: Uint128,
+    },
+    /// Unstaked tokens of a batch have been received.
+    ReceiveUnstakedTokens { batch_id: u64, amount: Uint128 },
+    /// Staking rewards have been received.
+    ReceiveRewards { amount: Uint128, fee: Uint128 },
+}
+
+impl HookMsg {
+    pub fn event(&self) -> HookEvent {
+        match self {
+            HookMsg::LiquidStake { .. } => HookEvent::LiquidStake,
+            HookMsg::LiquidUnstake { .. } => HookEvent::LiquidUnstake,
+            HookMsg::SubmitBatch { .. } => HookEvent::SubmitBatch,
+            HookMsg::ReceiveUnstakedTokens { .. } => HookEvent::ReceiveUnstakedTokens,
+            HookMsg::ReceiveRewards { .. } => HookEvent::ReceiveRewards,
+        }
+    }
+}
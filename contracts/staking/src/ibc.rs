@@ -1,7 +1,15 @@
-use cosmwasm_std::{DepsMut, Response};
+use cosmwasm_std::{Addr, DepsMut, Env, Event, Response, Storage};
+use milky_way::events::MilkyWayEvent;
 
-use crate::state::{self, Config, CONFIG};
-use crate::{error::ContractError, state::INFLIGHT_PACKETS};
+use crate::execute::redeliver_liquid_stake_tokens;
+use crate::state;
+use crate::tokenfactory;
+use crate::{
+    error::ContractError,
+    state::{
+        remove_inflight_packet, save_inflight_packet, INFLIGHT_PACKETS, INFLIGHT_PACKET_RETRIES,
+    },
+};
 
 /// Called by the chain when the ack for a packet that has configured this contract as its
 /// callback has been received.
@@ -19,10 +27,13 @@ use crate::{error::ContractError, state::INFLIGHT_PACKETS};
 /// no longer be tracked.
 ///
 /// If it's a failure, the sent funds will have been returned to this contract.
-/// We then store the amount and original sender on RECOVERY_STATES so that the
-/// sender can recover the funds by calling execute::Recover{}.
+/// If the packet delivered liquid stake tokens, they are refunded to the depositor
+/// on the protocol chain, or re-sent to the same receiver for the native chain
+/// senders without a depositor. Otherwise we mark the packet as failed so that the
+/// funds can be recovered by calling execute::Recover{}.
 pub fn receive_ack(
-    deps: DepsMut,
+    mut deps: DepsMut,
+    env: Env,
     source_channel: String,
     sequence: u64,
     _ack: String,
@@ -32,64 +43,120 @@ pub fn receive_ack(
     //     "received ack for packet {source_channel:?} {sequence:?}: {ack:?}, {success:?}"
     // ));
 
-    let config: Config = CONFIG.load(deps.storage)?;
-    if source_channel != config.protocol_chain_config.ibc_channel_id {
-        // If the ack is not for this contract, return a success
-        return Ok(Response::new()
-            .add_attribute("action", "receive_ack")
-            .add_attribute("channel", source_channel)
-            .add_attribute("error", "received ack for different channel"));
-    }
-
+    let key = (source_channel.as_str(), sequence);
     let response = Response::new().add_attribute("action", "receive_ack");
 
-    // Check if there is an inflight packet for the received (sequence)
-    let sent_packet = INFLIGHT_PACKETS.may_load(deps.storage, sequence)?;
-    let Some(mut inflight_packet) = sent_packet else {
+    // Check if there is an inflight packet for the received (source_channel, sequence)
+    let Some(mut inflight_packet) = INFLIGHT_PACKETS.may_load(deps.storage, key)? else {
         // If there isn't, continue
         return Ok(response.add_attribute("msg", "received unexpected ack"));
     };
 
     if success {
         // Remove the in-flight packet
-        INFLIGHT_PACKETS.remove(deps.storage, sequence);
+        remove_inflight_packet(deps.storage, key)?;
+        INFLIGHT_PACKET_RETRIES.remove(deps.storage, key);
 
         // If the acc is successful, there is nothing else to do and the crosschain swap has been completed
-        return Ok(response.add_attribute("msg", "success"));
+        return Ok(response
+            .add_attribute("msg", "success")
+            .add_event(completed_event(&source_channel, sequence, "success")));
+    }
+
+    let response = response.add_attribute("error", "ibc acknowledgement failed");
+    if let Some(depositor) = inflight_packet.depositor.clone() {
+        return refund_depositor(
+            deps.storage,
+            &env,
+            key,
+            &inflight_packet,
+            depositor,
+            response.add_event(completed_event(&source_channel, sequence, "refunded")),
+        );
+    }
+    if let Some(sub_msg) = redeliver_liquid_stake_tokens(&mut deps, &env, key, &inflight_packet)? {
+        return Ok(response.add_submessage(sub_msg).add_event(completed_event(
+            &source_channel,
+            sequence,
+            "retried",
+        )));
     }
 
     inflight_packet.status = state::ibc::PacketLifecycleStatus::AckFailure;
-    INFLIGHT_PACKETS.save(deps.storage, sequence, &inflight_packet)?;
+    save_inflight_packet(deps.storage, key, &inflight_packet)?;
 
-    Ok(response.add_attribute("error", "ibc acknowledgement failed"))
+    Ok(response.add_event(completed_event(&source_channel, sequence, "ack_failure")))
 }
 
 // This is very similar to the handling of acks, but it always creates a
 // recovery since there is no concept of a "successful timeout"
 pub fn receive_timeout(
-    deps: DepsMut,
+    mut deps: DepsMut,
+    env: Env,
     source_channel: String,
     sequence: u64,
 ) -> Result<Response, ContractError> {
-    let config: Config = CONFIG.load(deps.storage)?;
-    if source_channel != config.protocol_chain_config.ibc_channel_id {
-        // If the ack is not for this contract, return a success
-        return Ok(Response::new()
-            .add_attribute("action", "receive_timeout")
-            .add_attribute("error", "received timeout for different channel"));
-    }
-
+    let key = (source_channel.as_str(), sequence);
     let response = Response::new().add_attribute("action", "receive_timeout");
 
-    // Check if there is an inflight packet for the received (sequence)
-    let sent_packet = INFLIGHT_PACKETS.may_load(deps.storage, sequence)?;
-    let Some(mut inflight_packet) = sent_packet else {
+    // Check if there is an inflight packet for the received (source_channel, sequence)
+    let Some(mut inflight_packet) = INFLIGHT_PACKETS.may_load(deps.storage, key)? else {
         // If there isn't, continue
         return Ok(response.add_attribute("error", "received unexpected timeout"));
     };
 
+    let response = response.add_attribute("error", "ibc packet timed out");
+    if let Some(depositor) = inflight_packet.depositor.clone() {
+        return refund_depositor(
+            deps.storage,
+            &env,
+            key,
+            &inflight_packet,
+            depositor,
+            response.add_event(completed_event(&source_channel, sequence, "refunded")),
+        );
+    }
+    if let Some(sub_msg) = redeliver_liquid_stake_tokens(&mut deps, &env, key, &inflight_packet)? {
+        return Ok(response.add_submessage(sub_msg).add_event(completed_event(
+            &source_channel,
+            sequence,
+            "retried",
+        )));
+    }
+
     inflight_packet.status = state::ibc::PacketLifecycleStatus::TimedOut;
-    INFLIGHT_PACKETS.save(deps.storage, sequence, &inflight_packet)?;
+    save_inflight_packet(deps.storage, key, &inflight_packet)?;
+
+    Ok(response.add_event(completed_event(&source_channel, sequence, "timed_out")))
+}
+
+fn completed_event(channel: &str, sequence: u64, status: &str) -> Event {
+    MilkyWayEvent::IbcTransferCompleted {
+        channel: channel.to_string(),
+        sequence,
+        status: status.to_string(),
+    }
+    .into()
+}
+
+// Failed liquid stake token deliveries are sent back to the depositor on the
+// protocol chain instead of waiting for a recovery.
+fn refund_depositor(
+    storage: &mut dyn Storage,
+    env: &Env,
+    key: (&str, u64),
+    packet: &state::ibc::IBCTransfer,
+    depositor: Addr,
+    response: Response,
+) -> Result<Response, ContractError> {
+    remove_inflight_packet(storage, key)?;
+    INFLIGHT_PACKET_RETRIES.remove(storage, key);
 
-    Ok(response.add_attribute("error", "ibc packet timed out"))
+    Ok(response
+        .add_message(tokenfactory::send(
+            env.contract.address.to_string(),
+            packet.amount.clone(),
+            depositor.to_string(),
+        )?)
+        .add_attribute("refunded_to", depositor))
 }
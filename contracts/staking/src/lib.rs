@@ -1,8 +1,10 @@
 pub mod ack;
+pub mod chain;
 pub mod contract;
 pub mod error;
 pub mod execute;
 pub mod helpers;
+pub mod hooks;
 pub mod ibc;
 pub mod migrations;
 pub mod msg;
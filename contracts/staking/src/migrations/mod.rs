@@ -4,3 +4,5 @@ pub mod v1_0_0;
 pub mod v1_1_0;
 pub mod v1_2_0;
 pub mod v1_3_0;
+pub mod v1_4_0;
+pub mod v1_5_0;
@@ -1,3 +1,4 @@
 pub mod v0_4_18;
 pub mod v0_4_20;
 pub mod v1_0_0;
+pub mod v1_4_0;
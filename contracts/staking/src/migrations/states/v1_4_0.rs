@@ -0,0 +1,8 @@
+use cw_storage_plus::Map;
+
+use crate::state::ibc::{IBCTransfer, IBCTransferRetry};
+
+/// In-Flight packets by sequence
+pub const INFLIGHT_PACKETS: Map<u64, IBCTransfer> = Map::new("inflight");
+/// Retries of the in-flight packets by sequence.
+pub const INFLIGHT_PACKET_RETRIES: Map<u64, IBCTransferRetry> = Map::new("inflight_packet_retries");
@@ -77,6 +77,7 @@ pub fn migrate(
             reward_collector_address: old_config.multisig_address_config.reward_collector_address,
             staker_address: old_config.multisig_address_config.staker_address,
             unbonding_period: old_config.unbonding_period,
+            unbonding_receipt_tolerance: None,
         },
         protocol_chain_config: ProtocolChainConfig {
             account_address_prefix: protocol_account_address_prefix,
@@ -84,6 +85,10 @@ pub fn migrate(
             ibc_token_denom: old_config.native_token_denom,
             minimum_liquid_stake_amount: old_config.minimum_liquid_stake_amount,
             oracle_address: old_config.oracle_address,
+            authenticate_ibc_hooks_sender: None,
+            oracle_targets: None,
+            ibc_retry_policy: None,
+            ibc_timeout: None,
         },
         protocol_fee_config: ProtocolFeeConfig {
             dao_treasury_fee: old_config.protocol_fee_config.dao_treasury_fee,
@@ -92,11 +97,15 @@ pub fn migrate(
             } else {
                 None
             },
+            insurance_fund_fee: None,
+            keeper_bounty: None,
         },
         liquid_stake_token_denom: old_config.liquid_stake_token_denom,
         batch_period: old_config.batch_period,
         monitors: old_config.monitors.unwrap_or_default(),
         stopped: old_config.stopped,
+        resume_contract_limits: None,
+        batch_limits: None,
     };
     // Save the new config.
     CONFIG.save(deps.storage, &new_config)?;
@@ -1,11 +1,8 @@
 use crate::{
     contract::CONTRACT_NAME,
     error::ContractResult,
-    migrations::states::v1_0_0,
-    state::{
-        ibc::IBCTransfer, IbcWaitingForReply, CONFIG, IBC_WAITING_FOR_REPLY, INFLIGHT_PACKETS,
-        MIGRATING,
-    },
+    migrations::states::{v1_0_0, v1_4_0::INFLIGHT_PACKETS},
+    state::{ibc::IBCTransfer, IbcWaitingForReply, CONFIG, IBC_WAITING_FOR_REPLY, MIGRATING},
 };
 use cosmwasm_schema::cw_serde;
 use cosmwasm_std::{Coin, DepsMut, Env, Response};
@@ -82,6 +79,8 @@ pub fn migrate(deps: DepsMut, _env: Env, limit: Option<usize>) -> ContractResult
                     amount: Coin::new(packet.amount, &config.protocol_chain_config.ibc_token_denom),
                     receiver: config.native_chain_config.staker_address.to_string(),
                     status: packet.status,
+                    route: None,
+                    depositor: None,
                 },
             )?;
         }
@@ -125,6 +124,8 @@ pub fn migrate(deps: DepsMut, _env: Env, limit: Option<usize>) -> ContractResult
                         &config.protocol_chain_config.ibc_token_denom,
                     ),
                     receiver: config.native_chain_config.staker_address.to_string(),
+                    route: None,
+                    depositor: None,
                 },
             )?;
         }
@@ -0,0 +1,192 @@
+use cosmwasm_std::{Deps, DepsMut, Env, Order, Response, StdError, StdResult, Uint128};
+use cw_storage_plus::Bound;
+use milky_way::migration::{Migration, MigrationStep, StepProgress};
+use milky_way::staking::BatchStatus;
+
+use crate::{
+    contract::CONTRACT_NAME,
+    error::{ContractError, ContractResult},
+    migrations::states::v1_4_0,
+    state::{
+        save_inflight_packet, unstake_requests, update_total, BATCHES, BATCH_CLAIMABLE, CONFIG,
+        INFLIGHT_PACKET_RETRIES, TOTAL_CLAIMABLE,
+    },
+};
+
+const FROM_VERSION: &str = "1.4.0";
+const TO_VERSION: &str = "1.5.0";
+
+/// Moves the retries of the in-flight packets to the map keyed by
+/// (channel, sequence). It runs before the packets are moved since the
+/// channel is taken from the packet of the retry.
+struct MoveInflightPacketRetries;
+
+impl MigrationStep<ContractError> for MoveInflightPacketRetries {
+    type Cursor = u64;
+
+    fn name(&self) -> &'static str {
+        "inflight_packet_retries"
+    }
+
+    fn migrate(
+        &self,
+        deps: DepsMut,
+        _env: &Env,
+        cursor: Option<u64>,
+        limit: usize,
+    ) -> ContractResult<StepProgress<u64>> {
+        let config = CONFIG.load(deps.storage)?;
+        let mut retries = v1_4_0::INFLIGHT_PACKET_RETRIES
+            .range(
+                deps.storage,
+                cursor.map(Bound::exclusive),
+                None,
+                Order::Ascending,
+            )
+            .take(limit.saturating_add(1))
+            .collect::<StdResult<Vec<_>>>()?;
+        let completed = retries.len() <= limit;
+        retries.truncate(limit);
+
+        for (sequence, retry) in &retries {
+            v1_4_0::INFLIGHT_PACKET_RETRIES.remove(deps.storage, *sequence);
+            // The retries of packets that are no longer tracked are dropped
+            let Some(packet) = v1_4_0::INFLIGHT_PACKETS.may_load(deps.storage, *sequence)? else {
+                continue;
+            };
+            let channel_id = packet.channel_id(&config.protocol_chain_config.ibc_channel_id);
+            INFLIGHT_PACKET_RETRIES.save(deps.storage, (channel_id, *sequence), retry)?;
+        }
+
+        Ok(StepProgress {
+            cursor: retries.last().map(|(sequence, _)| *sequence),
+            migrated: retries.len(),
+            completed,
+        })
+    }
+}
+
+/// Moves the in-flight packets to the map keyed by (channel, sequence), the
+/// packets without a route have been sent through the configured channel.
+struct MoveInflightPackets;
+
+impl MigrationStep<ContractError> for MoveInflightPackets {
+    type Cursor = u64;
+
+    fn name(&self) -> &'static str {
+        "inflight_packets"
+    }
+
+    fn migrate(
+        &self,
+        deps: DepsMut,
+        _env: &Env,
+        cursor: Option<u64>,
+        limit: usize,
+    ) -> ContractResult<StepProgress<u64>> {
+        let config = CONFIG.load(deps.storage)?;
+        let mut packets = v1_4_0::INFLIGHT_PACKETS
+            .range(
+                deps.storage,
+                cursor.map(Bound::exclusive),
+                None,
+                Order::Ascending,
+            )
+            .take(limit.saturating_add(1))
+            .collect::<StdResult<Vec<_>>>()?;
+        let completed = packets.len() <= limit;
+        packets.truncate(limit);
+
+        for (sequence, packet) in &packets {
+            v1_4_0::INFLIGHT_PACKETS.remove(deps.storage, *sequence);
+            let channel_id = packet.channel_id(&config.protocol_chain_config.ibc_channel_id);
+            save_inflight_packet(deps.storage, (channel_id, *sequence), packet)?;
+        }
+
+        Ok(StepProgress {
+            cursor: packets.last().map(|(sequence, _)| *sequence),
+            migrated: packets.len(),
+            completed,
+        })
+    }
+}
+
+/// Tracks the tokens claimable from the received batches, computed from
+/// their remaining unstake requests.
+struct TrackClaimable;
+
+impl MigrationStep<ContractError> for TrackClaimable {
+    type Cursor = (u64, String);
+
+    fn name(&self) -> &'static str {
+        "claimable"
+    }
+
+    fn migrate(
+        &self,
+        deps: DepsMut,
+        _env: &Env,
+        cursor: Option<(u64, String)>,
+        limit: usize,
+    ) -> ContractResult<StepProgress<(u64, String)>> {
+        let mut requests = unstake_requests()
+            .range(
+                deps.storage,
+                cursor.map(Bound::exclusive),
+                None,
+                Order::Ascending,
+            )
+            .take(limit.saturating_add(1))
+            .collect::<StdResult<Vec<_>>>()?;
+        let completed = requests.len() <= limit;
+        requests.truncate(limit);
+
+        for (_, request) in &requests {
+            let batch = BATCHES.load(deps.storage, request.batch_id)?;
+            if batch.status != BatchStatus::Received {
+                continue;
+            }
+            let amount = batch
+                .received_native_unstaked
+                .unwrap_or_default()
+                .multiply_ratio(request.amount, batch.batch_total_liquid_stake);
+            let claimable = BATCH_CLAIMABLE
+                .may_load(deps.storage, batch.id)?
+                .unwrap_or_default();
+            BATCH_CLAIMABLE.save(deps.storage, batch.id, &(claimable + amount))?;
+            update_total(deps.storage, &TOTAL_CLAIMABLE, amount, Uint128::zero())?;
+        }
+
+        Ok(StepProgress {
+            cursor: requests.last().map(|(key, _)| key.clone()),
+            migrated: requests.len(),
+            completed,
+        })
+    }
+}
+
+fn legacy_packets_moved(deps: Deps) -> ContractResult<()> {
+    if !v1_4_0::INFLIGHT_PACKETS.is_empty(deps.storage)
+        || !v1_4_0::INFLIGHT_PACKET_RETRIES.is_empty(deps.storage)
+    {
+        return Err(StdError::generic_err("In-flight packets not migrated").into());
+    }
+    Ok(())
+}
+
+pub fn migrate(deps: DepsMut, env: Env, limit: Option<usize>) -> ContractResult<Response> {
+    let status = Migration::new(CONTRACT_NAME, FROM_VERSION, TO_VERSION)
+        .step(MoveInflightPacketRetries)
+        .step(MoveInflightPackets)
+        .step(TrackClaimable)
+        .invariant(legacy_packets_moved)
+        .run(deps, &env, limit)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "migrate")
+        .add_attribute("from_version", FROM_VERSION)
+        .add_attribute("to_version", TO_VERSION)
+        .add_attribute("step", status.step)
+        .add_attribute("migrated", status.migrated.to_string())
+        .add_attribute("completed", status.completed.to_string()))
+}
@@ -1,16 +1,21 @@
 use crate::{
     state::{
-        ibc::IBCTransfer, IbcWaitingForReply, NativeChainConfig, ProtocolChainConfig,
-        ProtocolFeeConfig,
+        ibc::{IBCTransfer, IbcHop},
+        BatchLimits, DenomAdminTransfer, DestinationChain, HookEvent, HookSubscription,
+        IbcWaitingForReply, InsuranceFundEntry, NativeChainConfig, OracleUpdateFailure,
+        ProtocolChainConfig, ProtocolFeeConfig, ResumeContractLimits, SlashEvent,
     },
     types::{
-        BatchExpectedAmount, UnsafeNativeChainConfig, UnsafeProtocolChainConfig,
-        UnsafeProtocolFeeConfig,
+        AdoptedDenom, BatchExpectedAmount, BatchSlashAmount, DenomMetadata, ImportedUnstakeRequest,
+        UnsafeNativeChainConfig, UnsafeProtocolChainConfig, UnsafeProtocolFeeConfig,
     },
 };
 use cosmwasm_schema::{cw_serde, QueryResponses};
+#[cfg(feature = "cw20")]
+use cosmwasm_std::Binary;
 use cosmwasm_std::{Addr, Decimal, Timestamp, Uint128};
-use milky_way::staking::BatchStatus;
+use milky_way::migration::MigrationStatus;
+use milky_way::staking::{Batch, BatchStatus};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
@@ -41,6 +46,36 @@ pub struct InstantiateMsg {
     /// Optional oracle contract code ID that will be instantiated if
     /// it is `Some` and the `protocol_chain_config.oracle_address` is `None`.
     pub oracle_code_id: Option<u64>,
+
+    /// Code ID of the cw20-base contract instantiated as liquid staking token.
+    /// Required by the `cw20` backend and ignored by the tokenfactory ones.
+    pub cw20_code_id: Option<u64>,
+
+    /// Optional bank metadata of the liquid stake token.
+    /// With the `cw20` backend the symbol and exponent are used as the
+    /// token symbol and decimals.
+    pub denom_metadata: Option<DenomMetadata>,
+
+    /// Liquid stake token of a previous deployment to adopt instead of creating
+    /// a new one. Its batches and unstake requests are imported with
+    /// [ExecuteMsg::ImportBatches] before the contract is resumed.
+    pub adopted_denom: Option<AdoptedDenom>,
+}
+
+/// Message sent by the CW20 liquid staking token when tokens are sent to this contract.
+#[cfg(feature = "cw20")]
+#[cw_serde]
+pub struct Cw20ReceiveMsg {
+    pub sender: String,
+    pub amount: Uint128,
+    pub msg: Binary,
+}
+
+#[cfg(feature = "cw20")]
+#[cw_serde]
+pub enum ReceiveMsg {
+    /// Initiates the unbonding process for the sender of the tokens.
+    LiquidUnstake {},
 }
 
 #[cw_serde]
@@ -61,10 +96,28 @@ pub enum ExecuteMsg {
         /// Minimum expected amount of LST tokens to be received
         /// for the operation to be considered valid.
         expected_mint_amount: Option<Uint128>,
+
+        /// Native chain account that sent the tokens through ibc-hooks.
+        /// The message sender must be the ibc-hooks intermediate sender
+        /// of this account. If provided, `mint_to` defaults to this account.
+        native_sender: Option<String>,
     },
 
     /// Initiates the unbonding process for a user.
-    LiquidUnstake {},
+    LiquidUnstake {
+        /// Native chain account that sent the tokens through ibc-hooks.
+        /// The message sender must be the ibc-hooks intermediate sender
+        /// of this account. If provided, the request is recorded under this
+        /// account and the unstaked tokens are sent to it when the batch is
+        /// received.
+        native_sender: Option<String>,
+    },
+
+    /// Initiates the unbonding process for a user sending CW20 liquid
+    /// staking tokens, the embedded message must be a [`ReceiveMsg`].
+    /// Only available with the `cw20` backend.
+    #[cfg(feature = "cw20")]
+    Receive(Cw20ReceiveMsg),
 
     /// Withdraws unstaked tokens.
     Withdraw {
@@ -72,7 +125,19 @@ pub enum ExecuteMsg {
         batch_id: u64,
     },
 
+    /// Sends the unstaked tokens of a received batch to the native chain
+    /// accounts that requested the unstake through ibc-hooks.
+    /// The requests not handled when the batch was received are sent here.
+    WithdrawNativeUnstakes {
+        /// ID of the received batch.
+        batch_id: u64,
+
+        /// Maximum number of requests to handle.
+        limit: Option<u32>,
+    },
+
     /// Processes the pending batch.
+    /// If configured, a keeper bounty is paid to the caller from the collected fees.
     SubmitBatch {},
 
     /// Adds a validator to the validator set; callable by the owner.
@@ -116,12 +181,22 @@ pub enum ExecuteMsg {
 
         /// Updated unbonding batch execution frequency (in seconds).
         batch_period: Option<u64>,
+
+        /// Updated limits applied when resuming the contract.
+        resume_contract_limits: Option<ResumeContractLimits>,
+
+        /// If true, removes the limits applied when resuming the contract.
+        clear_resume_contract_limits: Option<bool>,
+
+        /// Updated size limits of the unstaking batches.
+        batch_limits: Option<BatchLimits>,
     },
 
     /// Receives rewards from the native chain.
     ReceiveRewards {},
 
     /// Receives unstaked tokens from the native chain.
+    /// The tokens of a batch can be received in multiple tranches.
     ReceiveUnstakedTokens {
         /// ID of the batch that originated the unstake request.
         batch_id: u64,
@@ -140,31 +215,128 @@ pub enum ExecuteMsg {
 
         /// Updated total protocol rewards.
         total_reward_amount: Uint128,
+
+        /// If true, resumes the contract even if the provided values
+        /// exceed the configured resume contract limits.
+        force: Option<bool>,
     },
     SlashBatches {
         new_amounts: Vec<BatchExpectedAmount>,
     },
 
+    /// Records a validator slash and applies it to the contract accounting;
+    /// callable by the admin while the contract is stopped.
+    /// The amounts slashed from the batches are removed from their expected
+    /// native amount while the remaining amount is removed from the
+    /// total native tokens.
+    RecordSlash {
+        /// Address of the slashed validator.
+        validator: String,
+
+        /// Total amount of native tokens lost due to the slash.
+        amount: Uint128,
+
+        /// Height of the native chain at which the infraction happened.
+        infraction_height: u64,
+
+        /// Amounts slashed from the submitted batches, each batch at most once.
+        batches: Vec<BatchSlashAmount>,
+    },
+
     /// Recovers IBC transfers that timed out or failed.
     RecoverPendingIbcTransfers {
         /// If true and neither `selected_packets` nor `receiver` are specified,
         /// recovers only the 10 oldest failed IBC transfers.
         paginated: Option<bool>,
 
-        /// Specific packet IDs to recover.
+        /// Specific packets to recover, by (channel ID, sequence).
         /// Overrides other parameters if provided.
-        selected_packets: Option<Vec<u64>>,
+        selected_packets: Option<Vec<(String, u64)>>,
 
-        /// Recovers packets addressed to this account.
-        /// Considered only if `selected_packets` is not provided.
+        /// Recovers only the packets addressed to this account and sends
+        /// them to it. If `None`, each packet is re-sent to its original receiver.
         receiver: Option<String>,
     },
 
+    /// Re-sends the failed IBC transfers to their original receiver.
+    /// Transfers failing after the maximum number of attempts are
+    /// moved to the dead-letter state.
+    RetryFailedTransfers {
+        /// Maximum number of transfers to handle, defaults to 10.
+        limit: Option<u32>,
+    },
+
     /// Sends the protocol fee to the treasury.
     FeeWithdraw {
         /// Amount to send to the treasury.
         amount: Uint128,
     },
+
+    /// Registers or updates a chain to which the minted liquid stake tokens
+    /// can be delivered; callable by the admin.
+    SetDestinationChain {
+        /// Bech32 prefix of the chain accounts.
+        bech32_prefix: String,
+
+        /// IBC channel id from the protocol chain used to reach the chain,
+        /// or the intermediate chain if `hop` is provided.
+        channel_id: String,
+
+        /// Packet forward middleware hop through an intermediate chain.
+        hop: Option<IbcHop>,
+    },
+
+    /// Schedules the transfer of the liquid stake token admin to another
+    /// account, e.g. a successor contract; callable by the admin.
+    /// The transfer can be completed after 7 days.
+    TransferDenomAdmin { new_admin: String },
+
+    /// Cancels the scheduled transfer of the liquid stake token admin;
+    /// callable by the admin.
+    RevokeDenomAdminTransfer {},
+
+    /// Transfers the liquid stake token admin once the timelock has expired
+    /// and stops the contract for good, it can't be resumed afterwards;
+    /// callable by the admin. The pending batch and the batches rolled over
+    /// from it must not have any unstake request.
+    CompleteDenomAdminTransfer {},
+
+    /// Imports a page of the batches and unstake requests of the deployment whose
+    /// liquid stake token has been adopted; callable by the admin while the
+    /// contract is stopped. Imported batches overwrite the existing ones, while an
+    /// unstake request can only be imported once.
+    ImportBatches {
+        batches: Vec<Batch>,
+        unstake_requests: Vec<ImportedUnstakeRequest>,
+        /// If true, the unstake requests are checked against their batches and
+        /// the import is closed and can't be executed anymore.
+        finish: bool,
+    },
+
+    /// Posts the current rates to the oracle after an update failed; callable by anyone.
+    PushRates {},
+
+    /// Subscribes a contract to the callbacks of the given protocol events,
+    /// replacing its previous subscription; callable by the admin.
+    SubscribeHooks {
+        /// Address of the contract receiving the callbacks.
+        address: String,
+
+        /// Events for which the callback is sent.
+        events: Vec<HookEvent>,
+    },
+
+    /// Removes the hook subscription of a contract; callable by the admin.
+    UnsubscribeHooks { address: String },
+
+    /// Sets the bank metadata of the liquid stake token; callable by the admin.
+    SetDenomMetadata { metadata: DenomMetadata },
+
+    /// Removes a registered destination chain; callable by the admin.
+    RemoveDestinationChain {
+        /// Bech32 prefix of the chain to remove.
+        bech32_prefix: String,
+    },
 }
 
 #[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
@@ -176,6 +348,8 @@ pub struct ConfigResponse {
     pub liquid_stake_token_denom: String,
     pub batch_period: u64,
     pub stopped: bool,
+    pub resume_contract_limits: Option<ResumeContractLimits>,
+    pub batch_limits: Option<BatchLimits>,
 }
 
 #[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug, Default)]
@@ -221,6 +395,48 @@ pub struct IBCReplyQueueResponse {
     pub ibc_queue: Vec<IbcWaitingForReply>,
 }
 
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug, Default)]
+pub struct SlashHistoryResponse {
+    pub slashes: Vec<SlashEvent>,
+}
+
+#[cw_serde]
+pub struct InsuranceFundResponse {
+    pub balance: Uint128,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug, Default)]
+pub struct InsuranceFundHistoryResponse {
+    pub entries: Vec<InsuranceFundEntry>,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug, Default)]
+pub struct DestinationChainsResponse {
+    pub chains: Vec<DestinationChain>,
+}
+
+#[cw_serde]
+pub struct UnallocatedBalanceResponse {
+    /// Balance of `ibc_token_denom` held by the contract.
+    pub balance: Uint128,
+    /// Protocol fees not withdrawn yet.
+    pub total_fees: Uint128,
+    /// Insurance fund balance, including the coverage reserved for the submitted batches.
+    pub insurance_fund: Uint128,
+    /// Unstaked tokens of the received batches not withdrawn yet, including the
+    /// tranches already received for the partially received batches.
+    pub claimable: Uint128,
+    /// Failed transfers refunded to the contract, waiting to be retried or recovered.
+    pub failed_transfers: Uint128,
+    /// Part of the balance not allocated to any of the above.
+    pub unallocated: Uint128,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug, Default)]
+pub struct HookSubscriptionsResponse {
+    pub subscriptions: Vec<HookSubscription>,
+}
+
 #[cw_serde]
 #[derive(QueryResponses)]
 pub enum QueryMsg {
@@ -285,8 +501,8 @@ pub enum QueryMsg {
 
     #[returns(IBCQueueResponse)]
     IbcQueue {
-        /// If provided, starts listing IBC packets after this packet ID.
-        start_after: Option<u64>,
+        /// If provided, starts listing IBC packets after this (channel ID, sequence).
+        start_after: Option<(String, u64)>,
 
         /// Maximum number of IBC packets to return.
         limit: Option<u32>,
@@ -305,6 +521,67 @@ pub enum QueryMsg {
     /// Queries the current admin.
     #[returns(cw_controllers::AdminResponse)]
     Admin {},
+
+    /// Queries the recorded slash events.
+    #[returns(SlashHistoryResponse)]
+    SlashHistory {
+        /// If provided, starts listing slash events after this ID.
+        start_after: Option<u64>,
+
+        /// Maximum number of slash events to return.
+        limit: Option<u32>,
+    },
+
+    /// Queries the insurance fund balance.
+    #[returns(InsuranceFundResponse)]
+    InsuranceFund {},
+
+    /// Queries the insurance fund deposits and coverages.
+    #[returns(InsuranceFundHistoryResponse)]
+    InsuranceFundHistory {
+        /// If provided, starts listing entries after this ID.
+        start_after: Option<u64>,
+
+        /// Maximum number of entries to return.
+        limit: Option<u32>,
+    },
+
+    /// Queries the chains to which the liquid stake tokens can be delivered.
+    #[returns(DestinationChainsResponse)]
+    DestinationChains {
+        /// If provided, starts listing chains after this bech32 prefix.
+        start_after: Option<String>,
+
+        /// Maximum number of chains to return.
+        limit: Option<u32>,
+    },
+
+    /// Queries the scheduled transfer of the liquid stake token admin.
+    #[returns(Option<DenomAdminTransfer>)]
+    PendingDenomAdminTransfer {},
+
+    /// Queries the last failed oracle update, if the rates haven't been pushed since.
+    #[returns(Option<OracleUpdateFailure>)]
+    OracleUpdateFailure {},
+
+    /// Queries the contracts subscribed to the protocol events.
+    #[returns(HookSubscriptionsResponse)]
+    HookSubscriptions {
+        /// If provided, starts listing subscriptions after this address.
+        start_after: Option<String>,
+
+        /// Maximum number of subscriptions to return.
+        limit: Option<u32>,
+    },
+
+    /// Queries the progress of the last storage migration.
+    #[returns(Option<MigrationStatus>)]
+    MigrationStatus {},
+
+    /// Queries the part of the contract balance that isn't owed to the users,
+    /// the fees or the insurance fund.
+    #[returns(UnallocatedBalanceResponse)]
+    UnallocatedBalance {},
 }
 
 #[cw_serde]
@@ -323,6 +600,10 @@ pub enum MigrateMsg {
     },
     V1_1_0ToV1_2_0 {},
     V1_2_0ToV1_3_0 {},
+    V1_3_0ToV1_4_0 {},
+    V1_4_0ToV1_5_0 {
+        limit: Option<usize>,
+    },
 }
 
 #[cw_serde]
@@ -1,5 +1,6 @@
-// Types for the Oracle contract
+// Types for the Oracle contract, implemented by the `oracle` contract of this workspace
 use cosmwasm_schema::cw_serde;
+use cosmwasm_std::Decimal;
 
 #[cw_serde]
 pub enum Oracle {
@@ -14,3 +15,22 @@ pub enum Oracle {
 pub struct OracleInstantiateMsg {
     pub admin_address: String,
 }
+
+/// Message of the contracts with the [crate::state::OracleAdapter::UpdateRate] adapter.
+#[cw_serde]
+pub enum UpdateRateMsg {
+    UpdateRate { denom: String, rate: Decimal },
+}
+
+/// Message of the price feeds with the [crate::state::OracleAdapter::PriceFeed] adapter.
+#[cw_serde]
+pub enum PriceFeedMsg {
+    SetPrice {
+        /// Denom of the liquid stake token.
+        denom: String,
+        /// Denom in which the price is expressed.
+        base_denom: String,
+        /// Amount of `base_denom` tokens for one liquid stake token.
+        price: Decimal,
+    },
+}
@@ -1,16 +1,22 @@
 use crate::helpers::{get_rates, paginate_map};
 use crate::msg::{
-    BatchResponse, BatchesResponse, ConfigResponse, IBCQueueResponse, IBCReplyQueueResponse,
-    StateResponse,
+    BatchResponse, BatchesResponse, ConfigResponse, DestinationChainsResponse,
+    HookSubscriptionsResponse, IBCQueueResponse, IBCReplyQueueResponse,
+    InsuranceFundHistoryResponse, InsuranceFundResponse, SlashHistoryResponse, StateResponse,
+    UnallocatedBalanceResponse,
 };
 use crate::state::ibc::IBCTransfer;
 use crate::state::{
-    unstake_requests, UnstakeRequest, ADMIN, BATCHES, CONFIG, IBC_WAITING_FOR_REPLY,
-    INFLIGHT_PACKETS, PENDING_BATCH_ID, STATE,
+    unstake_requests, DenomAdminTransfer, OracleUpdateFailure, UnstakeRequest, ADMIN, BATCHES,
+    CONFIG, DESTINATION_CHAINS, FAILED_TRANSFERS_AMOUNT, HOOK_SUBSCRIPTIONS, IBC_WAITING_FOR_REPLY,
+    INFLIGHT_PACKETS, INSURANCE_FUND_BALANCE, INSURANCE_FUND_HISTORY, ORACLE_UPDATE_FAILURE,
+    PENDING_BATCH_ID, PENDING_DENOM_ADMIN_TRANSFER, SLASH_EVENTS, STATE, TOTAL_CLAIMABLE,
+    TOTAL_INSURANCE_COVERAGE,
 };
-use cosmwasm_std::{Deps, StdResult, Timestamp, Uint128};
+use cosmwasm_std::{Deps, Env, StdResult, Timestamp, Uint128};
 use cw_controllers::AdminResponse;
 use cw_storage_plus::Bound;
+use milky_way::migration::{MigrationStatus, MIGRATION_STATUS};
 use milky_way::staking::{Batch, BatchStatus};
 
 pub fn query_config(deps: Deps) -> StdResult<ConfigResponse> {
@@ -24,6 +30,8 @@ pub fn query_config(deps: Deps) -> StdResult<ConfigResponse> {
         monitors: config.monitors,
         batch_period: config.batch_period,
         stopped: config.stopped,
+        resume_contract_limits: config.resume_contract_limits,
+        batch_limits: config.batch_limits,
     };
     Ok(res)
 }
@@ -116,13 +124,15 @@ pub fn query_pending_batch(deps: Deps) -> StdResult<BatchResponse> {
 
 pub fn query_ibc_queue(
     deps: Deps,
-    start_after: Option<u64>,
+    start_after: Option<(String, u64)>,
     limit: Option<u32>,
 ) -> StdResult<IBCQueueResponse> {
     let inflight_packets: Vec<IBCTransfer> = paginate_map(
         deps,
         &INFLIGHT_PACKETS,
-        start_after,
+        start_after
+            .as_ref()
+            .map(|(channel_id, sequence)| (channel_id.as_str(), *sequence)),
         limit,
         cosmwasm_std::Order::Ascending,
         None,
@@ -201,3 +211,131 @@ pub fn query_all_unstake_requests(
 pub fn query_admin(deps: Deps) -> StdResult<AdminResponse> {
     ADMIN.query_admin(deps)
 }
+
+pub fn query_slash_history(
+    deps: Deps,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<SlashHistoryResponse> {
+    let slashes = paginate_map(
+        deps,
+        &SLASH_EVENTS,
+        start_after,
+        limit,
+        cosmwasm_std::Order::Ascending,
+        None,
+    )?;
+
+    Ok(SlashHistoryResponse { slashes })
+}
+
+pub fn query_insurance_fund(deps: Deps) -> StdResult<InsuranceFundResponse> {
+    let balance = INSURANCE_FUND_BALANCE
+        .may_load(deps.storage)?
+        .unwrap_or_default();
+
+    Ok(InsuranceFundResponse { balance })
+}
+
+pub fn query_insurance_fund_history(
+    deps: Deps,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<InsuranceFundHistoryResponse> {
+    let entries = paginate_map(
+        deps,
+        &INSURANCE_FUND_HISTORY,
+        start_after,
+        limit,
+        cosmwasm_std::Order::Ascending,
+        None,
+    )?;
+
+    Ok(InsuranceFundHistoryResponse { entries })
+}
+
+pub fn query_destination_chains(
+    deps: Deps,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<DestinationChainsResponse> {
+    let chains = paginate_map(
+        deps,
+        &DESTINATION_CHAINS,
+        start_after.as_deref(),
+        limit,
+        cosmwasm_std::Order::Ascending,
+        None,
+    )?;
+
+    Ok(DestinationChainsResponse { chains })
+}
+
+pub fn query_pending_denom_admin_transfer(deps: Deps) -> StdResult<Option<DenomAdminTransfer>> {
+    PENDING_DENOM_ADMIN_TRANSFER.may_load(deps.storage)
+}
+
+pub fn query_oracle_update_failure(deps: Deps) -> StdResult<Option<OracleUpdateFailure>> {
+    ORACLE_UPDATE_FAILURE.may_load(deps.storage)
+}
+
+pub fn query_hook_subscriptions(
+    deps: Deps,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<HookSubscriptionsResponse> {
+    let start_after = start_after
+        .map(|address| deps.api.addr_validate(&address))
+        .transpose()?;
+    let subscriptions = paginate_map(
+        deps,
+        &HOOK_SUBSCRIPTIONS,
+        start_after.as_ref(),
+        limit,
+        cosmwasm_std::Order::Ascending,
+        None,
+    )?;
+
+    Ok(HookSubscriptionsResponse { subscriptions })
+}
+
+pub fn query_migration_status(deps: Deps) -> StdResult<Option<MigrationStatus>> {
+    MIGRATION_STATUS.may_load(deps.storage)
+}
+
+pub fn query_unallocated_balance(deps: Deps, env: Env) -> StdResult<UnallocatedBalanceResponse> {
+    let config = CONFIG.load(deps.storage)?;
+    let state = STATE.load(deps.storage)?;
+    let denom = config.protocol_chain_config.ibc_token_denom;
+
+    let balance = deps
+        .querier
+        .query_balance(&env.contract.address, &denom)?
+        .amount;
+
+    let insurance_fund = INSURANCE_FUND_BALANCE
+        .may_load(deps.storage)?
+        .unwrap_or_default()
+        + TOTAL_INSURANCE_COVERAGE
+            .may_load(deps.storage)?
+            .unwrap_or_default();
+    let claimable = TOTAL_CLAIMABLE.may_load(deps.storage)?.unwrap_or_default();
+    let failed_transfers = FAILED_TRANSFERS_AMOUNT
+        .may_load(deps.storage, &denom)?
+        .unwrap_or_default();
+
+    let unallocated = balance
+        .saturating_sub(state.total_fees)
+        .saturating_sub(insurance_fund)
+        .saturating_sub(claimable)
+        .saturating_sub(failed_transfers);
+
+    Ok(UnallocatedBalanceResponse {
+        balance,
+        total_fees: state.total_fees,
+        insurance_fund,
+        claimable,
+        failed_transfers,
+        unallocated,
+    })
+}
@@ -1,10 +1,15 @@
 use cosmwasm_schema::cw_serde;
-use cosmwasm_std::{Addr, Coin, Deps, DepsMut, StdError, Timestamp, Uint128};
+use cosmwasm_std::{
+    Addr, Coin, Decimal, Deps, DepsMut, Empty, Order, StdError, StdResult, Storage, Timestamp,
+    Uint128,
+};
 use cw_controllers::Admin;
 use cw_storage_plus::{Index, IndexList, IndexedMap, Item, Map, UniqueIndex};
+use milky_way::migration::migration_in_progress;
 use milky_way::staking::Batch;
 
 use crate::error::{ContractError, ContractResult};
+use crate::types::{BatchSlashAmount, MAX_IBC_RETRY_ATTEMPTS, MAX_IBC_RETRY_BACKOFF};
 
 #[cw_serde]
 pub struct Config {
@@ -32,6 +37,14 @@ pub struct Config {
 
     /// If true, the contract is stopped and no actions are allowed.
     pub stopped: bool,
+
+    /// Bounds applied to the totals provided when resuming the contract.
+    /// If `None`, the provided totals are not checked.
+    pub resume_contract_limits: Option<ResumeContractLimits>,
+
+    /// Size limits of the unstaking batches.
+    /// If `None`, batches are only submitted after `batch_period` and have no size limit.
+    pub batch_limits: Option<BatchLimits>,
 }
 
 /// Config related to the chain for which we are creating
@@ -60,6 +73,11 @@ pub struct NativeChainConfig {
 
     /// Address where the staking rewards are withdrawn.
     pub reward_collector_address: Addr,
+
+    /// Maximum shortfall accepted when receiving the unstaked tokens of a batch,
+    /// a batch receiving at least `expected_native_unstaked` minus this amount
+    /// is considered fully received. If `None`, the full amount is required.
+    pub unbonding_receipt_tolerance: Option<Uint128>,
 }
 
 /// Config related to the chain where the smart contract is deployed.
@@ -80,6 +98,102 @@ pub struct ProtocolChainConfig {
 
     /// The redemption / purchase rate oracle address
     pub oracle_address: Option<Addr>,
+
+    /// Policy applied when retrying the failed IBC transfers.
+    /// If `None`, the default policy is used.
+    pub ibc_retry_policy: Option<IbcRetryPolicy>,
+
+    /// Timeout in seconds of the IBC transfers sent by the contract,
+    /// at most 7 days. If `None`, the default timeout is used.
+    pub ibc_timeout: Option<u64>,
+
+    /// If true, rewards and unstaked tokens are only accepted from the
+    /// ibc-hooks intermediate sender derived from the `reward_collector_address`
+    /// and `staker_address` on `ibc_channel_id`.
+    pub authenticate_ibc_hooks_sender: Option<bool>,
+
+    /// Contracts to which the rates are pushed, in addition to `oracle_address`.
+    /// If `None`, the rates are only pushed to `oracle_address`.
+    pub oracle_targets: Option<Vec<OracleTarget>>,
+}
+
+impl ProtocolChainConfig {
+    /// Returns the contracts to which the rates are pushed, starting with `oracle_address`.
+    pub fn oracle_targets(&self) -> Vec<OracleTarget> {
+        self.oracle_address
+            .iter()
+            .map(|address| OracleTarget {
+                address: address.clone(),
+                adapter: OracleAdapter::MilkyWay,
+                min_update_interval: None,
+            })
+            .chain(self.oracle_targets.iter().flatten().cloned())
+            .collect()
+    }
+}
+
+/// Format of the rates pushed to an oracle target.
+#[cw_serde]
+pub enum OracleAdapter {
+    /// MilkyWay oracle, receives [crate::oracle::Oracle::PostRates].
+    MilkyWay,
+    /// Contract receiving [crate::oracle::UpdateRateMsg::UpdateRate] with the redemption rate.
+    UpdateRate,
+    /// Price feed of a lending protocol, receives [crate::oracle::PriceFeedMsg::SetPrice]
+    /// with the price of the liquid stake token in native tokens.
+    PriceFeed,
+}
+
+/// Contract to which the rates are pushed.
+#[cw_serde]
+pub struct OracleTarget {
+    pub address: Addr,
+    pub adapter: OracleAdapter,
+    /// Minimum time in seconds between two updates of the target.
+    /// If `None`, the target is updated every time the rates change.
+    pub min_update_interval: Option<u64>,
+}
+
+/// Time of the last update of each oracle target.
+pub const ORACLE_TARGET_LAST_UPDATE: Map<&Addr, Timestamp> = Map::new("oracle_target_last_update");
+
+/// Policy applied by [crate::msg::ExecuteMsg::RetryFailedTransfers].
+#[cw_serde]
+pub struct IbcRetryPolicy {
+    /// Maximum number of times a failed transfer is re-sent before
+    /// it is moved to the dead-letter state, between 1 and 10.
+    pub max_attempts: u32,
+
+    /// Time in seconds to wait before re-sending a transfer that failed
+    /// after a retry, doubled after each attempt. At most 1 day.
+    pub backoff: u64,
+}
+
+impl IbcRetryPolicy {
+    /// Ensures that a failed transfer is retried at least once and that
+    /// the backoff can't overflow the time of the next attempt.
+    pub fn validate(&self) -> ContractResult<()> {
+        if self.max_attempts == 0 || self.max_attempts > MAX_IBC_RETRY_ATTEMPTS {
+            return Err(ContractError::InvalidIbcRetryPolicy {
+                reason: format!("max_attempts must be between 1 and {MAX_IBC_RETRY_ATTEMPTS}"),
+            });
+        }
+        if self.backoff > MAX_IBC_RETRY_BACKOFF {
+            return Err(ContractError::InvalidIbcRetryPolicy {
+                reason: format!("backoff can't exceed {MAX_IBC_RETRY_BACKOFF} seconds"),
+            });
+        }
+        Ok(())
+    }
+}
+
+impl Default for IbcRetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 5,
+            backoff: 600,
+        }
+    }
 }
 
 /// Config related to the fees collected by the contract to
@@ -91,6 +205,118 @@ pub struct ProtocolFeeConfig {
     /// Address where the collected fees are sent.
     /// If this value is None, the fees will be kept in the contract.
     pub treasury_address: Option<Addr>,
+
+    /// Share of the collected fees routed to the insurance fund,
+    /// fee percentage=x/100000.
+    /// If this value is None, no fees are routed to the insurance fund.
+    pub insurance_fund_fee: Option<Uint128>,
+
+    /// Bounty paid from the collected fees to the caller of
+    /// [crate::msg::ExecuteMsg::SubmitBatch]. Only set without a `treasury_address`,
+    /// since the fees are otherwise sent to the treasury.
+    /// If this value is None, no bounty is paid.
+    pub keeper_bounty: Option<KeeperBounty>,
+}
+
+/// Bounty paid to the keepers submitting the batches from the fees held by
+/// the contract, which requires the protocol fee config to have no
+/// `treasury_address`.
+#[cw_serde]
+pub struct KeeperBounty {
+    /// Maximum amount of native tokens paid for each submission, the
+    /// bounty is limited by the fees held by the contract.
+    pub max_amount: Uint128,
+
+    /// Minimum time in seconds between two paid submissions.
+    pub cooldown: u64,
+}
+
+/// Time of the last submission for which a keeper bounty has been paid.
+pub const LAST_KEEPER_BOUNTY_TIME: Item<Timestamp> = Item::new("last_keeper_bounty_time");
+
+/// Maximum deviations allowed when resuming the contract through
+/// [crate::msg::ExecuteMsg::ResumeContract].
+/// Each deviation is relative to its reference value, a value of `0.05`
+/// allows the provided value to be up to 5% lower or higher than the reference.
+#[cw_serde]
+pub struct ResumeContractLimits {
+    /// Maximum deviation of the new `total_native_token` from the current one.
+    pub max_total_native_token_deviation: Decimal,
+
+    /// Maximum deviation of the new `total_liquid_stake_token` from the current one.
+    pub max_total_liquid_stake_token_deviation: Decimal,
+
+    /// Maximum deviation of the new `total_reward_amount` from the current one.
+    pub max_total_reward_amount_deviation: Decimal,
+
+    /// Maximum deviation of the implied redemption rate from both the
+    /// redemption rate before the contract was stopped and the rate of
+    /// the last received batch.
+    pub max_redemption_rate_deviation: Decimal,
+}
+
+impl ResumeContractLimits {
+    /// Ensures that every deviation is positive, a zero deviation would
+    /// prevent the contract from being resumed without `force`.
+    pub fn validate(&self) -> ContractResult<()> {
+        let deviations = [
+            (
+                "max_total_native_token_deviation",
+                self.max_total_native_token_deviation,
+            ),
+            (
+                "max_total_liquid_stake_token_deviation",
+                self.max_total_liquid_stake_token_deviation,
+            ),
+            (
+                "max_total_reward_amount_deviation",
+                self.max_total_reward_amount_deviation,
+            ),
+            (
+                "max_redemption_rate_deviation",
+                self.max_redemption_rate_deviation,
+            ),
+        ];
+        for (field, deviation) in deviations {
+            if deviation.is_zero() {
+                return Err(ContractError::InvalidResumeContractLimits {
+                    reason: format!("{field} must be greater than zero"),
+                });
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Size limits applied to the unstaking batches.
+#[cw_serde]
+pub struct BatchLimits {
+    /// If the batch total reaches this amount of liquid stake tokens,
+    /// the batch can be submitted before its `next_batch_action_time`.
+    pub early_submit_liquid_stake: Option<Uint128>,
+
+    /// If the batch reaches this number of unstake requests,
+    /// the batch can be submitted before its `next_batch_action_time`.
+    pub early_submit_requests_count: Option<u64>,
+
+    /// Maximum amount of liquid stake tokens in a batch, unstake requests
+    /// exceeding it are rolled over into the next batches, up to
+    /// [crate::types::MAX_ROLLED_OVER_BATCHES] batches. Can't be lower than
+    /// the minimum liquid stake amount.
+    /// A full batch can be submitted before its `next_batch_action_time`.
+    pub max_batch_liquid_stake: Option<Uint128>,
+}
+
+impl BatchLimits {
+    /// Returns true if the batch can be submitted before its `next_batch_action_time`.
+    pub fn allows_early_submit(&self, batch: &Batch) -> bool {
+        let total = batch.batch_total_liquid_stake;
+        let count = batch.unstake_requests_count.unwrap_or(0);
+
+        matches!(self.early_submit_liquid_stake, Some(t) if total >= t)
+            || matches!(self.early_submit_requests_count, Some(t) if count >= t)
+            || matches!(self.max_batch_liquid_stake, Some(t) if total >= t)
+    }
 }
 
 #[cw_serde]
@@ -110,6 +336,8 @@ pub const ADMIN: Admin = Admin::new("admin");
 pub const STATE: Item<State> = Item::new("state");
 pub const BATCHES: Map<u64, Batch> = Map::new("batches");
 pub const PENDING_BATCH_ID: Item<u64> = Item::new("pending_batch_id");
+/// Redemption rate recorded when the contract has been stopped by the circuit breaker.
+pub const STOPPED_REDEMPTION_RATE: Item<Decimal> = Item::new("stopped_redemption_rate");
 
 #[cw_serde]
 pub struct UnstakeRequest {
@@ -171,8 +399,116 @@ pub fn remove_unstake_request(
 pub struct IbcWaitingForReply {
     pub amount: Coin,
     pub receiver: String,
+    pub route: Option<ibc::IbcRoute>,
+    pub depositor: Option<Addr>,
+}
+
+/// A chain to which the liquid stake tokens can be delivered.
+#[cw_serde]
+pub struct DestinationChain {
+    /// Bech32 prefix of the chain accounts (e.g. "neutron", "inj").
+    pub bech32_prefix: String,
+    /// IBC channel id from the protocol chain used to reach the chain, or
+    /// the intermediate chain if `hop` is set.
+    pub channel_id: String,
+    /// Packet forward middleware hop through an intermediate chain.
+    pub hop: Option<ibc::IbcHop>,
+}
+
+impl DestinationChain {
+    pub fn route(&self) -> ibc::IbcRoute {
+        ibc::IbcRoute {
+            channel_id: self.channel_id.clone(),
+            hop: self.hop.clone(),
+        }
+    }
+}
+
+/// Registered destination chains by bech32 prefix.
+pub const DESTINATION_CHAINS: Map<&str, DestinationChain> = Map::new("destination_chains");
+
+/// Protocol events for which the subscribed contracts receive a callback.
+#[cw_serde]
+pub enum HookEvent {
+    LiquidStake,
+    LiquidUnstake,
+    SubmitBatch,
+    ReceiveUnstakedTokens,
+    ReceiveRewards,
+}
+
+impl HookEvent {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            HookEvent::LiquidStake => "liquid_stake",
+            HookEvent::LiquidUnstake => "liquid_unstake",
+            HookEvent::SubmitBatch => "submit_batch",
+            HookEvent::ReceiveUnstakedTokens => "receive_unstaked_tokens",
+            HookEvent::ReceiveRewards => "receive_rewards",
+        }
+    }
+}
+
+/// A contract receiving a callback on the subscribed protocol events.
+#[cw_serde]
+pub struct HookSubscription {
+    pub address: Addr,
+    pub events: Vec<HookEvent>,
 }
 
+/// Hook subscriptions by contract address.
+pub const HOOK_SUBSCRIPTIONS: Map<&Addr, HookSubscription> = Map::new("hook_subscriptions");
+
+/// Scheduled transfer of the liquid stake token admin to another account.
+#[cw_serde]
+pub struct DenomAdminTransfer {
+    /// Account that will become the admin of the liquid stake token.
+    pub new_admin: Addr,
+
+    /// Time after which the transfer can be completed.
+    pub min_time: Timestamp,
+}
+
+pub const PENDING_DENOM_ADMIN_TRANSFER: Item<DenomAdminTransfer> =
+    Item::new("pending_denom_admin_transfer");
+
+/// Set once the liquid stake token admin has been handed over, after which
+/// the contract can't mint or burn it anymore.
+pub const DENOM_ADMIN_TRANSFERRED: Item<bool> = Item::new("denom_admin_transferred");
+
+/// Last failed update of the oracle.
+#[cw_serde]
+pub struct OracleUpdateFailure {
+    /// Oracle target whose update failed last.
+    pub target: Addr,
+
+    /// Error returned by the oracle.
+    pub error: String,
+
+    /// Time of the last failure.
+    pub time: Timestamp,
+
+    /// Number of updates that failed since the rates have been pushed,
+    /// across all the oracle targets.
+    pub failures: u64,
+}
+
+/// Recorded when an oracle update fails, until the rates are pushed
+/// with [crate::msg::ExecuteMsg::PushRates].
+pub const ORACLE_UPDATE_FAILURE: Item<OracleUpdateFailure> = Item::new("oracle_update_failure");
+
+/// Set if the contract adopted the liquid stake token of a previous deployment,
+/// until the import of its batches and unstake requests is finished.
+pub const IMPORTING_BATCHES: Item<bool> = Item::new("importing_batches");
+
+/// Sum and number of the unstake requests imported for each batch, checked
+/// against the imported batches as they're imported.
+pub const IMPORTED_REQUESTS_TOTALS: Map<u64, (Uint128, u64)> = Map::new("imported_requests_totals");
+
+/// Ids of the imported batches whose unstake requests don't add up to the
+/// batch totals yet, the import can only be finished once it's empty.
+pub const UNBALANCED_IMPORTED_BATCHES: Map<u64, Empty> = Map::new("unbalanced_imported_batches");
+
 pub mod ibc {
     use super::*;
 
@@ -182,6 +518,19 @@ pub mod ibc {
         AckSuccess,
         AckFailure,
         TimedOut,
+        /// The transfer failed after the maximum number of retries,
+        /// it can only be recovered manually.
+        DeadLetter,
+    }
+
+    /// Retries of a failed transfer re-sent by
+    /// [crate::msg::ExecuteMsg::RetryFailedTransfers].
+    #[cw_serde]
+    pub struct IBCTransferRetry {
+        /// Number of times the transfer has been re-sent.
+        pub attempts: u32,
+        /// Time of the last attempt.
+        pub last_attempt: Timestamp,
     }
 
     /// A transfer packet sent by this contract that is expected to be received but
@@ -192,18 +541,244 @@ pub mod ibc {
         pub amount: Coin,
         pub receiver: String,
         pub status: PacketLifecycleStatus,
+        /// Route of the transfer, if `None` the transfer has been sent
+        /// to the native chain.
+        pub route: Option<IbcRoute>,
+        /// Protocol chain account that deposited the tokens of a liquid
+        /// stake token delivery, refunded if the delivery fails.
+        /// Native chain accounts staking through ibc-hooks have no protocol
+        /// chain account, so their failed deliveries are re-sent to the same
+        /// receiver up to the maximum attempts of the retry policy.
+        pub depositor: Option<Addr>,
+    }
+
+    impl IBCTransfer {
+        /// Returns the channel used to send the transfer.
+        pub fn channel_id<'a>(&'a self, default_channel_id: &'a str) -> &'a str {
+            self.route
+                .as_ref()
+                .map_or(default_channel_id, |route| route.channel_id.as_str())
+        }
+
+        /// Returns true if the transfer failed and its tokens are back in the contract.
+        pub fn failed(&self) -> bool {
+            matches!(
+                self.status,
+                PacketLifecycleStatus::AckFailure
+                    | PacketLifecycleStatus::TimedOut
+                    | PacketLifecycleStatus::DeadLetter
+            )
+        }
+    }
+
+    /// Route used to deliver a transfer to a chain other than the native chain.
+    #[cw_serde]
+    pub struct IbcRoute {
+        /// IBC channel id from the protocol chain used to send the transfer.
+        pub channel_id: String,
+        /// Packet forward middleware hop, if the transfer is forwarded
+        /// by an intermediate chain.
+        pub hop: Option<IbcHop>,
+    }
+
+    /// Packet forward middleware hop through an intermediate chain.
+    #[cw_serde]
+    pub struct IbcHop {
+        /// Receiver on the intermediate chain.
+        pub receiver: String,
+        /// IBC channel id from the intermediate chain to the destination chain.
+        pub channel_id: String,
     }
 }
 
+/// A slash event applied to the contract accounting.
+#[cw_serde]
+pub struct SlashEvent {
+    /// Incremental id of the slash event.
+    pub id: u64,
+
+    /// Validator that has been slashed.
+    pub validator: Addr,
+
+    /// Total amount of native tokens lost due to the slash.
+    pub amount: Uint128,
+
+    /// Height of the native chain at which the infraction happened.
+    pub infraction_height: u64,
+
+    /// Amounts slashed from the submitted batches that were unbonding
+    /// from the validator.
+    pub batches: Vec<BatchSlashAmount>,
+
+    /// Amount slashed from the delegated tokens, this is the slashed
+    /// amount minus the amounts slashed from the batches.
+    pub delegated_amount: Uint128,
+
+    /// Part of the slashed amount that has been covered by the insurance fund.
+    pub insurance_covered_amount: Uint128,
+
+    /// Time at which the slash has been recorded.
+    pub recorded_at: Timestamp,
+}
+
+/// Slash events by id.
+pub const SLASH_EVENTS: Map<u64, SlashEvent> = Map::new("slash_events");
+
+#[cw_serde]
+pub enum InsuranceFundOperation {
+    /// A share of the reward fees has been deposited into the fund.
+    Deposit {},
+    /// The fund has covered the losses of a slash event.
+    CoverSlash { slash_id: u64 },
+}
+
+/// An operation that changed the insurance fund balance.
+#[cw_serde]
+pub struct InsuranceFundEntry {
+    pub id: u64,
+    pub operation: InsuranceFundOperation,
+    pub amount: Uint128,
+    /// Fund balance after the operation.
+    pub balance: Uint128,
+    pub time: Timestamp,
+}
+
+/// Amount of native tokens held by the contract to cover slashing losses.
+pub const INSURANCE_FUND_BALANCE: Item<Uint128> = Item::new("insurance_fund_balance");
+/// Insurance fund operations by id.
+pub const INSURANCE_FUND_HISTORY: Map<u64, InsuranceFundEntry> = Map::new("insurance_fund_history");
+/// Amount covered by the insurance fund for each batch, these tokens are
+/// already held by the contract so they are not expected from the native chain.
+pub const BATCH_INSURANCE_COVERAGE: Map<u64, Uint128> = Map::new("batch_insurance_coverage");
+/// Unstaked tokens of each received batch not withdrawn yet. The payouts are
+/// rounded down, the dust left after the last withdrawal is credited to the fees.
+pub const BATCH_CLAIMABLE: Map<u64, Uint128> = Map::new("batch_claimable");
+/// Sum of [BATCH_INSURANCE_COVERAGE].
+pub const TOTAL_INSURANCE_COVERAGE: Item<Uint128> = Item::new("total_insurance_coverage");
+/// Unstaked tokens held for the batches: the claimable tokens of the received
+/// batches and the tranches received so far by the partially received ones.
+pub const TOTAL_CLAIMABLE: Item<Uint128> = Item::new("total_claimable");
+
+/// Adds `added` to and removes `removed` from a running total.
+pub fn update_total(
+    storage: &mut dyn Storage,
+    total: &Item<Uint128>,
+    added: Uint128,
+    removed: Uint128,
+) -> StdResult<()> {
+    let amount = total.may_load(storage)?.unwrap_or_default();
+    total.save(storage, &(amount + added).saturating_sub(removed))
+}
+
+/// Unstake requests made by native chain accounts through ibc-hooks, by batch id
+/// and native account. Their unstaked tokens are sent back to the native chain.
+pub const NATIVE_UNSTAKE_REQUESTS: Map<(u64, &str), Empty> = Map::new("native_unstake_requests");
+
+/// Updates the insurance fund balance and records the operation in its history.
+pub fn update_insurance_fund(
+    storage: &mut dyn Storage,
+    time: Timestamp,
+    operation: InsuranceFundOperation,
+    amount: Uint128,
+) -> ContractResult<Uint128> {
+    let balance = INSURANCE_FUND_BALANCE
+        .may_load(storage)?
+        .unwrap_or_default();
+    let balance = match operation {
+        InsuranceFundOperation::Deposit {} => {
+            balance.checked_add(amount).map_err(StdError::from)?
+        }
+        InsuranceFundOperation::CoverSlash { .. } => {
+            balance.checked_sub(amount).map_err(StdError::from)?
+        }
+    };
+    INSURANCE_FUND_BALANCE.save(storage, &balance)?;
+
+    let id = INSURANCE_FUND_HISTORY
+        .keys(storage, None, None, Order::Descending)
+        .next()
+        .transpose()?
+        .map_or(1, |id| id + 1);
+    INSURANCE_FUND_HISTORY.save(
+        storage,
+        id,
+        &InsuranceFundEntry {
+            id,
+            operation,
+            amount,
+            balance,
+            time,
+        },
+    )?;
+
+    Ok(balance)
+}
+
 /// In-Flight packets by (source_channel_id, sequence)
-pub const INFLIGHT_PACKETS: Map<u64, ibc::IBCTransfer> = Map::new("inflight");
+pub const INFLIGHT_PACKETS: Map<(&str, u64), ibc::IBCTransfer> = Map::new("channel_inflight");
+/// Amount of the failed in-flight packets by denom, kept up to date by
+/// [save_inflight_packet] and [remove_inflight_packet].
+pub const FAILED_TRANSFERS_AMOUNT: Map<&str, Uint128> = Map::new("failed_transfers_amount");
 pub const IBC_WAITING_FOR_REPLY: Map<u64, IbcWaitingForReply> = Map::new("ibc_waiting_for_reply");
+/// Retries of the in-flight packets by (source_channel_id, sequence).
+pub const INFLIGHT_PACKET_RETRIES: Map<(&str, u64), ibc::IBCTransferRetry> =
+    Map::new("channel_inflight_retries");
+/// Retries of the transfers waiting for the reply by submessage id.
+pub const RETRIES_WAITING_FOR_REPLY: Map<u64, ibc::IBCTransferRetry> =
+    Map::new("retries_waiting_for_reply");
+
+/// Updates the amount of the failed transfers when an in-flight packet
+/// is replaced by `packet`, or removed if `None`.
+fn update_failed_transfers(
+    storage: &mut dyn Storage,
+    key: (&str, u64),
+    packet: Option<&ibc::IBCTransfer>,
+) -> StdResult<()> {
+    let previous = INFLIGHT_PACKETS.may_load(storage, key)?;
+    for (transfer, added) in [(previous.as_ref(), false), (packet, true)] {
+        let Some(transfer) = transfer.filter(|transfer| transfer.failed()) else {
+            continue;
+        };
+        let denom = transfer.amount.denom.as_str();
+        let amount = FAILED_TRANSFERS_AMOUNT
+            .may_load(storage, denom)?
+            .unwrap_or_default();
+        let amount = if added {
+            amount + transfer.amount.amount
+        } else {
+            amount.saturating_sub(transfer.amount.amount)
+        };
+        if amount.is_zero() {
+            FAILED_TRANSFERS_AMOUNT.remove(storage, denom);
+        } else {
+            FAILED_TRANSFERS_AMOUNT.save(storage, denom, &amount)?;
+        }
+    }
+    Ok(())
+}
+
+/// Saves an in-flight packet, keeping the amount of the failed transfers up to date.
+pub fn save_inflight_packet(
+    storage: &mut dyn Storage,
+    key: (&str, u64),
+    packet: &ibc::IBCTransfer,
+) -> StdResult<()> {
+    update_failed_transfers(storage, key, Some(packet))?;
+    INFLIGHT_PACKETS.save(storage, key, packet)
+}
+
+/// Removes an in-flight packet, keeping the amount of the failed transfers up to date.
+pub fn remove_inflight_packet(storage: &mut dyn Storage, key: (&str, u64)) -> StdResult<()> {
+    update_failed_transfers(storage, key, None)?;
+    INFLIGHT_PACKETS.remove(storage, key);
+    Ok(())
+}
 
 pub const MIGRATING: Item<bool> = Item::new("migrating");
 
 /// Checks if the contract is being migrated.
 pub fn assert_not_migrating(deps: Deps) -> ContractResult<()> {
-    if MIGRATING.may_load(deps.storage)?.unwrap_or(false) {
+    if MIGRATING.may_load(deps.storage)?.unwrap_or(false) || migration_in_progress(deps.storage)? {
         Err(ContractError::Migrating {})
     } else {
         Ok(())
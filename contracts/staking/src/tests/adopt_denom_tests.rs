@@ -0,0 +1,455 @@
+use crate::contract::{execute, instantiate};
+use crate::error::ContractError;
+use crate::msg::ExecuteMsg;
+use crate::state::{
+    unstake_requests, BATCHES, BATCH_CLAIMABLE, CONFIG, NATIVE_UNSTAKE_REQUESTS, PENDING_BATCH_ID,
+    STATE, UNBALANCED_IMPORTED_BATCHES,
+};
+use crate::tests::test_helper::{mock_init_msg, ADMIN, CELESTIA1, OSMO1, OSMO3};
+use crate::types::{AdoptedDenom, ImportedUnstakeRequest, MAX_ROLLED_OVER_BATCHES};
+use cosmwasm_std::testing::{
+    mock_dependencies, mock_env, mock_info, MockApi, MockQuerier, MockStorage,
+};
+use cosmwasm_std::{OwnedDeps, Uint128};
+use milky_way::staking::{Batch, BatchStatus};
+
+const ADOPTED_DENOM: &str = "factory/osmo1previousdeployment/umilkTIA";
+
+fn adopted_denom() -> AdoptedDenom {
+    AdoptedDenom {
+        denom: ADOPTED_DENOM.to_string(),
+        total_native_token: Uint128::new(1_100),
+        total_liquid_stake_token: Uint128::new(1_000),
+        total_reward_amount: Uint128::new(100),
+        total_fees: Uint128::new(10),
+        pending_batch_id: 3,
+    }
+}
+
+fn init_adopted() -> OwnedDeps<MockStorage, MockApi, MockQuerier> {
+    let mut deps = mock_dependencies();
+    let mut msg = mock_init_msg();
+    msg.adopted_denom = Some(adopted_denom());
+    let res = instantiate(deps.as_mut(), mock_env(), mock_info(ADMIN, &[]), msg).unwrap();
+
+    // The denom isn't created
+    assert!(res.messages.is_empty());
+    deps
+}
+
+fn submitted_batch(id: u64) -> Batch {
+    let mut batch = Batch::new(id, Uint128::new(100), 0);
+    batch.update_status(BatchStatus::Submitted, Some(1_000));
+    batch.expected_native_unstaked = Some(Uint128::new(110));
+    batch.unstake_requests_count = Some(2);
+    batch
+}
+
+#[test]
+fn init_with_adopted_denom() {
+    let deps = init_adopted();
+
+    let config = CONFIG.load(&deps.storage).unwrap();
+    assert_eq!(ADOPTED_DENOM, config.liquid_stake_token_denom);
+    assert!(config.stopped);
+
+    let state = STATE.load(&deps.storage).unwrap();
+    assert_eq!(Uint128::new(1_100), state.total_native_token);
+    assert_eq!(Uint128::new(1_000), state.total_liquid_stake_token);
+    assert_eq!(Uint128::new(100), state.total_reward_amount);
+    assert_eq!(Uint128::new(10), state.total_fees);
+
+    assert_eq!(3, PENDING_BATCH_ID.load(&deps.storage).unwrap());
+    let pending_batch = BATCHES.load(&deps.storage, 3).unwrap();
+    assert_eq!(BatchStatus::Pending, pending_batch.status);
+    assert!(!BATCHES.has(&deps.storage, 1));
+}
+
+#[test]
+fn init_with_invalid_adopted_denom_fails() {
+    let mut deps = mock_dependencies();
+    let mut msg = mock_init_msg();
+    msg.adopted_denom = Some(AdoptedDenom {
+        pending_batch_id: 0,
+        ..adopted_denom()
+    });
+    let err = instantiate(deps.as_mut(), mock_env(), mock_info(ADMIN, &[]), msg).unwrap_err();
+    assert!(matches!(err, ContractError::InvalidAdoptedDenom { .. }));
+}
+
+#[test]
+fn import_batches() {
+    let mut deps = init_adopted();
+
+    let mut pending_batch = Batch::new(3, Uint128::new(50), 2_000);
+    pending_batch.unstake_requests_count = Some(1);
+    let import_msg = ExecuteMsg::ImportBatches {
+        batches: vec![submitted_batch(2), pending_batch],
+        unstake_requests: vec![
+            ImportedUnstakeRequest {
+                batch_id: 2,
+                user: OSMO3.to_string(),
+                amount: Uint128::new(60),
+                native: false,
+            },
+            ImportedUnstakeRequest {
+                batch_id: 2,
+                user: CELESTIA1.to_string(),
+                amount: Uint128::new(40),
+                native: true,
+            },
+            ImportedUnstakeRequest {
+                batch_id: 3,
+                user: OSMO3.to_string(),
+                amount: Uint128::new(50),
+                native: false,
+            },
+        ],
+        finish: false,
+    };
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("bob", &[]),
+        import_msg.clone(),
+    )
+    .unwrap_err();
+    assert!(matches!(err, ContractError::Admin(_)));
+
+    execute(deps.as_mut(), mock_env(), mock_info(ADMIN, &[]), import_msg).unwrap();
+
+    assert_eq!(submitted_batch(2), BATCHES.load(&deps.storage, 2).unwrap());
+    assert_eq!(
+        Uint128::new(50),
+        BATCHES
+            .load(&deps.storage, 3)
+            .unwrap()
+            .batch_total_liquid_stake
+    );
+    let request = unstake_requests()
+        .load(&deps.storage, (2, CELESTIA1.to_string()))
+        .unwrap();
+    assert_eq!(Uint128::new(40), request.amount);
+    assert!(NATIVE_UNSTAKE_REQUESTS.has(&deps.storage, (2, CELESTIA1)));
+    assert!(!NATIVE_UNSTAKE_REQUESTS.has(&deps.storage, (2, OSMO3)));
+
+    // Requests must belong to an imported batch
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(ADMIN, &[]),
+        ExecuteMsg::ImportBatches {
+            batches: vec![],
+            unstake_requests: vec![ImportedUnstakeRequest {
+                batch_id: 1,
+                user: OSMO3.to_string(),
+                amount: Uint128::new(10),
+                native: false,
+            }],
+            finish: false,
+        },
+    )
+    .unwrap_err();
+    assert!(matches!(
+        err,
+        ContractError::InvalidImportedBatch { id: 1, .. }
+    ));
+
+    // Only the pending batch can be pending
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(ADMIN, &[]),
+        ExecuteMsg::ImportBatches {
+            batches: vec![Batch::new(1, Uint128::zero(), 0)],
+            unstake_requests: vec![],
+            finish: false,
+        },
+    )
+    .unwrap_err();
+    assert!(matches!(
+        err,
+        ContractError::InvalidImportedBatch { id: 1, .. }
+    ));
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(ADMIN, &[]),
+        ExecuteMsg::ImportBatches {
+            batches: vec![submitted_batch(4)],
+            unstake_requests: vec![],
+            finish: false,
+        },
+    )
+    .unwrap_err();
+    assert!(matches!(
+        err,
+        ContractError::InvalidImportedBatch { id: 4, .. }
+    ));
+
+    // Once finished, nothing else can be imported
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(ADMIN, &[]),
+        ExecuteMsg::ImportBatches {
+            batches: vec![submitted_batch(1)],
+            unstake_requests: vec![
+                ImportedUnstakeRequest {
+                    batch_id: 1,
+                    user: OSMO3.to_string(),
+                    amount: Uint128::new(70),
+                    native: false,
+                },
+                ImportedUnstakeRequest {
+                    batch_id: 1,
+                    user: CELESTIA1.to_string(),
+                    amount: Uint128::new(30),
+                    native: true,
+                },
+            ],
+            finish: true,
+        },
+    )
+    .unwrap();
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(ADMIN, &[]),
+        ExecuteMsg::ImportBatches {
+            batches: vec![],
+            unstake_requests: vec![],
+            finish: true,
+        },
+    )
+    .unwrap_err();
+    assert!(matches!(err, ContractError::ImportClosed {}));
+}
+
+#[test]
+fn import_rolled_over_batches() {
+    let mut deps = init_adopted();
+
+    // The pending batch is imported without its schedule
+    let mut pending_batch = Batch::new(3, Uint128::new(100), 0);
+    pending_batch.next_batch_action_time = None;
+    pending_batch.unstake_requests_count = Some(1);
+    let mut rolled_over_batch = Batch::new(5, Uint128::new(30), 0);
+    rolled_over_batch.next_batch_action_time = None;
+    rolled_over_batch.unstake_requests_count = Some(1);
+    let next_batch_action_time = BATCHES
+        .load(&deps.storage, 3)
+        .unwrap()
+        .next_batch_action_time
+        .unwrap();
+    import(
+        &mut deps,
+        vec![pending_batch, Batch::new(4, Uint128::new(100), 1_000)],
+        vec![request(3, OSMO3, 100), request(4, OSMO3, 100)],
+        false,
+    )
+    .unwrap();
+    import(
+        &mut deps,
+        vec![rolled_over_batch],
+        vec![request(5, OSMO3, 30)],
+        false,
+    )
+    .unwrap();
+
+    assert_eq!(
+        Some(next_batch_action_time),
+        BATCHES
+            .load(&deps.storage, 3)
+            .unwrap()
+            .next_batch_action_time
+    );
+    assert_eq!(
+        Some(1_000),
+        BATCHES
+            .load(&deps.storage, 4)
+            .unwrap()
+            .next_batch_action_time
+    );
+    let batch_period = CONFIG.load(&deps.storage).unwrap().batch_period;
+    assert_eq!(
+        Some(next_batch_action_time + 2 * batch_period),
+        BATCHES
+            .load(&deps.storage, 5)
+            .unwrap()
+            .next_batch_action_time
+    );
+
+    // Unstake requests can't be rolled over past the maximum number of batches
+    let err = import(
+        &mut deps,
+        vec![Batch::new(3 + MAX_ROLLED_OVER_BATCHES, Uint128::zero(), 0)],
+        vec![],
+        false,
+    )
+    .unwrap_err();
+    assert!(matches!(
+        err,
+        ContractError::InvalidImportedBatch { id, .. } if id == 3 + MAX_ROLLED_OVER_BATCHES
+    ));
+
+    // The requests of the batch 4 don't add up to its total
+    let err = import(&mut deps, vec![], vec![], true).unwrap_err();
+    assert!(matches!(
+        err,
+        ContractError::InvalidImportedBatch { id: 4, .. }
+    ));
+    let mut batch_4 = BATCHES.load(&deps.storage, 4).unwrap();
+    batch_4.unstake_requests_count = Some(1);
+    import(&mut deps, vec![batch_4], vec![], true).unwrap();
+}
+
+#[test]
+fn import_batches_requires_adopted_denom() {
+    let mut deps = mock_dependencies();
+    instantiate(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(ADMIN, &[]),
+        mock_init_msg(),
+    )
+    .unwrap();
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(ADMIN, &[]),
+        ExecuteMsg::ImportBatches {
+            batches: vec![submitted_batch(1)],
+            unstake_requests: vec![],
+            finish: false,
+        },
+    )
+    .unwrap_err();
+    assert!(matches!(err, ContractError::ImportClosed {}));
+}
+
+#[test]
+fn import_batches_requires_stopped_contract() {
+    let mut deps = init_adopted();
+    let mut config = CONFIG.load(&deps.storage).unwrap();
+    config.stopped = false;
+    CONFIG.save(&mut deps.storage, &config).unwrap();
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(ADMIN, &[]),
+        ExecuteMsg::ImportBatches {
+            batches: vec![submitted_batch(1)],
+            unstake_requests: vec![],
+            finish: false,
+        },
+    )
+    .unwrap_err();
+    assert!(matches!(err, ContractError::NotStopped {}));
+}
+
+fn request(batch_id: u64, user: &str, amount: u128) -> ImportedUnstakeRequest {
+    ImportedUnstakeRequest {
+        batch_id,
+        user: user.to_string(),
+        amount: Uint128::new(amount),
+        native: false,
+    }
+}
+
+fn import(
+    deps: &mut OwnedDeps<MockStorage, MockApi, MockQuerier>,
+    batches: Vec<Batch>,
+    unstake_requests: Vec<ImportedUnstakeRequest>,
+    finish: bool,
+) -> Result<(), ContractError> {
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(ADMIN, &[]),
+        ExecuteMsg::ImportBatches {
+            batches,
+            unstake_requests,
+            finish,
+        },
+    )
+    .map(|_| ())
+}
+
+#[test]
+fn import_batches_checks_unstake_requests() {
+    let mut deps = init_adopted();
+
+    import(
+        &mut deps,
+        vec![submitted_batch(2)],
+        vec![request(2, OSMO3, 60)],
+        false,
+    )
+    .unwrap();
+
+    // The same request can't be imported twice
+    let err = import(&mut deps, vec![], vec![request(2, OSMO3, 40)], false).unwrap_err();
+    assert!(matches!(
+        err,
+        ContractError::InvalidImportedBatch { id: 2, .. }
+    ));
+
+    // The requests of a submitted batch must match its total
+    let err = import(&mut deps, vec![], vec![], true).unwrap_err();
+    assert!(matches!(
+        err,
+        ContractError::InvalidImportedBatch { id: 2, .. }
+    ));
+
+    // The import must be finished before resuming
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(ADMIN, &[]),
+        ExecuteMsg::ResumeContract {
+            total_native_token: Uint128::new(1_100),
+            total_liquid_stake_token: Uint128::new(1_000),
+            total_reward_amount: Uint128::new(100),
+            force: None,
+        },
+    )
+    .unwrap_err();
+    assert!(matches!(err, ContractError::ImportNotFinished {}));
+
+    // Received batches only keep the requests that haven't been withdrawn
+    let mut received_batch = submitted_batch(1);
+    received_batch.received_native_unstaked = Some(Uint128::new(110));
+    received_batch.update_status(BatchStatus::Received, None);
+    import(
+        &mut deps,
+        vec![received_batch],
+        vec![request(2, OSMO1, 40), request(1, OSMO3, 30)],
+        true,
+    )
+    .unwrap();
+
+    assert_eq!(
+        Uint128::new(33),
+        BATCH_CLAIMABLE.load(&deps.storage, 1).unwrap()
+    );
+    assert!(!BATCH_CLAIMABLE.has(&deps.storage, 2));
+    assert!(UNBALANCED_IMPORTED_BATCHES.is_empty(&deps.storage));
+
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(ADMIN, &[]),
+        ExecuteMsg::ResumeContract {
+            total_native_token: Uint128::new(1_100),
+            total_liquid_stake_token: Uint128::new(1_000),
+            total_reward_amount: Uint128::new(100),
+            force: None,
+        },
+    )
+    .unwrap();
+}
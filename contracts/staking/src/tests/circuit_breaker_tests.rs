@@ -47,6 +47,7 @@ fn circuit_breaker() {
         mint_to: None,
         transfer_to_native_chain: None,
         expected_mint_amount: None,
+        native_sender: None,
     };
     let res = execute(deps.as_mut(), mock_env(), info.clone(), msg);
     assert!(res.is_err());
@@ -56,7 +57,9 @@ fn circuit_breaker() {
     state.total_native_token = Uint128::from(300_000u128);
     STATE.save(&mut deps.storage, &state).unwrap();
     let info = mock_info("bob", &coins(1000, "factory/cosmos2contract/stTIA"));
-    let msg = ExecuteMsg::LiquidUnstake {};
+    let msg = ExecuteMsg::LiquidUnstake {
+        native_sender: None,
+    };
     let res = execute(deps.as_mut(), mock_env(), info.clone(), msg);
     assert!(res.is_err());
 
@@ -118,6 +121,7 @@ fn circuit_breaker() {
         total_native_token: Uint128::from(100000u128),
         total_liquid_stake_token: Uint128::from(200000u128),
         total_reward_amount: Uint128::from(10000u128),
+        force: None,
     };
 
     // not correct sender
@@ -149,6 +153,7 @@ fn circuit_breaker() {
         expected_mint_amount: None,
         transfer_to_native_chain: None,
         mint_to: None,
+        native_sender: None,
     };
     let res = execute(deps.as_mut(), mock_env(), info.clone(), msg);
     assert!(res.is_ok());
@@ -0,0 +1,202 @@
+use crate::chain::{Backend, Chain};
+use crate::contract::{execute, instantiate, reply, INSTANTIATE_CW20_REPLY_ID};
+use crate::error::ContractError;
+use crate::msg::{Cw20ReceiveMsg, ExecuteMsg, ReceiveMsg};
+use crate::state::{unstake_requests, CONFIG, STATE};
+use crate::tests::test_helper::{mock_init_msg, ADMIN, CELESTIA1, NATIVE_TOKEN, OSMO1, OSMO3};
+use crate::tokenfactory::Cw20ExecuteMsg;
+use cosmwasm_std::testing::{
+    mock_dependencies, mock_env, mock_info, MockApi, MockQuerier, MOCK_CONTRACT_ADDR,
+};
+use cosmwasm_std::{
+    coins, from_json, to_json_binary, Binary, CosmosMsg, MemoryStorage, OwnedDeps, Reply,
+    SubMsgResponse, SubMsgResult, Uint128, WasmMsg,
+};
+use osmosis_std::types::cosmwasm::wasm::v1::MsgInstantiateContractResponse;
+use prost::Message;
+use std::vec::Vec;
+
+const CW20_ADDRESS: &str = "cw20token";
+
+fn init_cw20() -> OwnedDeps<MemoryStorage, MockApi, MockQuerier> {
+    let mut deps = mock_dependencies();
+    let res = instantiate(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(ADMIN, &[]),
+        mock_init_msg(),
+    )
+    .unwrap();
+
+    // The token is instantiated with the contract as minter
+    let create_denom_msg = &res.messages[0];
+    assert_eq!(INSTANTIATE_CW20_REPLY_ID, create_denom_msg.id);
+    let CosmosMsg::Wasm(WasmMsg::Instantiate { code_id, .. }) = &create_denom_msg.msg else {
+        panic!("unexpected message {:?}", create_denom_msg.msg);
+    };
+    assert_eq!(7, *code_id);
+
+    reply(
+        deps.as_mut(),
+        mock_env(),
+        Reply {
+            id: INSTANTIATE_CW20_REPLY_ID,
+            result: SubMsgResult::Ok(SubMsgResponse {
+                events: Vec::new(),
+                data: Some(Binary::from(
+                    MsgInstantiateContractResponse {
+                        address: CW20_ADDRESS.to_string(),
+                        data: vec![],
+                    }
+                    .encode_to_vec(),
+                )),
+            }),
+        },
+    )
+    .unwrap();
+
+    let mut config = CONFIG.load(&deps.storage).unwrap();
+    assert_eq!(CW20_ADDRESS, config.liquid_stake_token_denom);
+    config.stopped = false;
+    CONFIG.save(&mut deps.storage, &config).unwrap();
+
+    deps
+}
+
+#[test]
+fn instantiate_requires_cw20_code_id() {
+    let mut deps = mock_dependencies();
+    let mut msg = mock_init_msg();
+    msg.cw20_code_id = None;
+    let err = instantiate(deps.as_mut(), mock_env(), mock_info(ADMIN, &[]), msg).unwrap_err();
+    assert!(matches!(err, ContractError::Cw20CodeIdRequired {}));
+}
+
+#[test]
+fn liquid_stake_mints_cw20() {
+    let mut deps = init_cw20();
+
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(OSMO3, &coins(1000, NATIVE_TOKEN)),
+        ExecuteMsg::LiquidStake {
+            mint_to: None,
+            transfer_to_native_chain: None,
+            expected_mint_amount: None,
+            native_sender: None,
+        },
+    )
+    .unwrap();
+
+    let cw20_msgs: Vec<Cw20ExecuteMsg> = res
+        .messages
+        .iter()
+        .filter_map(|m| match &m.msg {
+            CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr, msg, ..
+            }) if contract_addr == CW20_ADDRESS => Some(from_json(msg).unwrap()),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(
+        vec![
+            Cw20ExecuteMsg::Mint {
+                recipient: mock_env().contract.address.to_string(),
+                amount: Uint128::new(1000),
+            },
+            Cw20ExecuteMsg::Transfer {
+                recipient: OSMO3.to_string(),
+                amount: Uint128::new(1000),
+            },
+        ],
+        cw20_msgs
+    );
+
+    // The CW20 token can't be delivered over IBC
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(OSMO3, &coins(1000, NATIVE_TOKEN)),
+        ExecuteMsg::LiquidStake {
+            mint_to: Some(CELESTIA1.to_string()),
+            transfer_to_native_chain: None,
+            expected_mint_amount: None,
+            native_sender: None,
+        },
+    )
+    .unwrap_err();
+    assert!(matches!(err, ContractError::IbcTransferNotSupported {}));
+}
+
+#[test]
+fn liquid_unstake_through_receive() {
+    let mut deps = init_cw20();
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(OSMO3, &coins(1000, NATIVE_TOKEN)),
+        ExecuteMsg::LiquidStake {
+            mint_to: None,
+            transfer_to_native_chain: None,
+            expected_mint_amount: None,
+            native_sender: None,
+        },
+    )
+    .unwrap();
+
+    let receive_msg = ExecuteMsg::Receive(Cw20ReceiveMsg {
+        sender: OSMO3.to_string(),
+        amount: Uint128::new(400),
+        msg: to_json_binary(&ReceiveMsg::LiquidUnstake {}).unwrap(),
+    });
+
+    // Only the liquid stake token is accepted
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("othertoken", &[]),
+        receive_msg.clone(),
+    )
+    .unwrap_err();
+    assert!(matches!(err, ContractError::Unauthorized { .. }));
+
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(CW20_ADDRESS, &[]),
+        receive_msg,
+    )
+    .unwrap();
+    let request = unstake_requests()
+        .load(&deps.storage, (1, OSMO3.to_string()))
+        .unwrap();
+    assert_eq!(Uint128::new(400), request.amount);
+}
+
+#[test]
+fn fee_withdraw_uses_chain_bank_send() {
+    let mut deps = init_cw20();
+    let mut state = STATE.load(&deps.storage).unwrap();
+    state.total_fees = Uint128::new(100);
+    STATE.save(&mut deps.storage, &state).unwrap();
+
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(ADMIN, &[]),
+        ExecuteMsg::FeeWithdraw {
+            amount: Uint128::new(100),
+        },
+    )
+    .unwrap();
+    assert_eq!(
+        Backend::bank_send(
+            MOCK_CONTRACT_ADDR.to_string(),
+            OSMO1.to_string(),
+            coins(100, NATIVE_TOKEN)
+        )
+        .unwrap(),
+        res.messages[0].msg
+    );
+}
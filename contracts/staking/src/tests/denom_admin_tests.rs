@@ -0,0 +1,255 @@
+use crate::contract::{execute, query};
+use crate::error::ContractError;
+use crate::msg::{ExecuteMsg, QueryMsg};
+use crate::state::{
+    DenomAdminTransfer, BATCHES, CONFIG, DENOM_ADMIN_TRANSFERRED, PENDING_BATCH_ID,
+};
+use crate::tests::test_helper::{init, liquid_stake_token_denom, ADMIN, NATIVE_TOKEN, OSMO3};
+use crate::tokenfactory;
+use cosmwasm_std::testing::{mock_env, mock_info};
+use cosmwasm_std::{coins, from_json, Addr, Uint128};
+use milky_way::staking::Batch;
+
+const NEW_ADMIN: &str = "new_denom_admin";
+
+fn pending_transfer(deps: cosmwasm_std::Deps) -> Option<DenomAdminTransfer> {
+    from_json(query(deps, mock_env(), QueryMsg::PendingDenomAdminTransfer {}).unwrap()).unwrap()
+}
+
+#[test]
+fn non_admin_transfer_denom_admin() {
+    let mut deps = init();
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("bob", &[]),
+        ExecuteMsg::TransferDenomAdmin {
+            new_admin: NEW_ADMIN.to_string(),
+        },
+    )
+    .unwrap_err();
+    assert!(matches!(err, ContractError::Admin(_)));
+    assert_eq!(None, pending_transfer(deps.as_ref()));
+}
+
+#[test]
+fn proper_complete_denom_admin_transfer() {
+    let mut deps = init();
+    let mut env = mock_env();
+
+    // Nothing to complete yet
+    let err = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(ADMIN, &[]),
+        ExecuteMsg::CompleteDenomAdminTransfer {},
+    )
+    .unwrap_err();
+    assert!(matches!(err, ContractError::NoPendingDenomAdminTransfer {}));
+
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(ADMIN, &[]),
+        ExecuteMsg::TransferDenomAdmin {
+            new_admin: NEW_ADMIN.to_string(),
+        },
+    )
+    .unwrap();
+    assert!(res.messages.is_empty());
+
+    let min_time = env.block.time.plus_seconds(60 * 60 * 24 * 7);
+    assert_eq!(
+        Some(DenomAdminTransfer {
+            new_admin: Addr::unchecked(NEW_ADMIN),
+            min_time,
+        }),
+        pending_transfer(deps.as_ref())
+    );
+
+    // The timelock must be over
+    let err = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(ADMIN, &[]),
+        ExecuteMsg::CompleteDenomAdminTransfer {},
+    )
+    .unwrap_err();
+    assert!(matches!(
+        err,
+        ContractError::DenomAdminTransferNotReady { time_to_complete } if time_to_complete == min_time
+    ));
+
+    env.block.time = min_time;
+
+    let err = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("bob", &[]),
+        ExecuteMsg::CompleteDenomAdminTransfer {},
+    )
+    .unwrap_err();
+    assert!(matches!(err, ContractError::Admin(_)));
+
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(ADMIN, &[]),
+        ExecuteMsg::CompleteDenomAdminTransfer {},
+    )
+    .unwrap();
+    assert_eq!(1, res.messages.len());
+    assert_eq!(
+        tokenfactory::change_admin(
+            env.contract.address.to_string(),
+            liquid_stake_token_denom(),
+            NEW_ADMIN.to_string(),
+        )
+        .unwrap(),
+        res.messages[0].msg
+    );
+
+    // The contract can't mint or burn anymore
+    assert!(CONFIG.load(&deps.storage).unwrap().stopped);
+    assert!(DENOM_ADMIN_TRANSFERRED.load(&deps.storage).unwrap());
+    assert_eq!(None, pending_transfer(deps.as_ref()));
+
+    // Nor be resumed
+    let err = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(ADMIN, &[]),
+        ExecuteMsg::ResumeContract {
+            total_native_token: Uint128::zero(),
+            total_liquid_stake_token: Uint128::zero(),
+            total_reward_amount: Uint128::zero(),
+            force: Some(true),
+        },
+    )
+    .unwrap_err();
+    assert!(matches!(err, ContractError::DenomAdminTransferred {}));
+}
+
+#[test]
+fn complete_denom_admin_transfer_with_unstake_requests_fails() {
+    let mut deps = init();
+    let mut env = mock_env();
+
+    execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(ADMIN, &[]),
+        ExecuteMsg::TransferDenomAdmin {
+            new_admin: NEW_ADMIN.to_string(),
+        },
+    )
+    .unwrap();
+    env.block.time = env.block.time.plus_seconds(60 * 60 * 24 * 7);
+
+    // Unstake requests rolled over from the pending batch
+    let pending_batch_id = PENDING_BATCH_ID.load(&deps.storage).unwrap();
+    let rolled_over_batch = Batch::new(pending_batch_id + 2, Uint128::new(1_000), 0);
+    BATCHES
+        .save(&mut deps.storage, rolled_over_batch.id, &rolled_over_batch)
+        .unwrap();
+
+    let err = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(ADMIN, &[]),
+        ExecuteMsg::CompleteDenomAdminTransfer {},
+    )
+    .unwrap_err();
+    assert!(matches!(
+        err,
+        ContractError::PendingUnstakeRequests { batch_id } if batch_id == pending_batch_id + 2
+    ));
+
+    // Unstake requests in the pending batch
+    let mut pending_batch = BATCHES.load(&deps.storage, pending_batch_id).unwrap();
+    pending_batch.batch_total_liquid_stake = Uint128::new(1_000);
+    BATCHES
+        .save(&mut deps.storage, pending_batch_id, &pending_batch)
+        .unwrap();
+
+    let err = execute(
+        deps.as_mut(),
+        env,
+        mock_info(ADMIN, &[]),
+        ExecuteMsg::CompleteDenomAdminTransfer {},
+    )
+    .unwrap_err();
+    assert!(matches!(
+        err,
+        ContractError::PendingUnstakeRequests { batch_id } if batch_id == pending_batch_id
+    ));
+    assert!(!DENOM_ADMIN_TRANSFERRED
+        .load(&deps.storage)
+        .unwrap_or_default());
+    assert!(!CONFIG.load(&deps.storage).unwrap().stopped);
+}
+
+#[test]
+fn liquid_stake_after_denom_admin_transfer_fails() {
+    let mut deps = init();
+    DENOM_ADMIN_TRANSFERRED
+        .save(&mut deps.storage, &true)
+        .unwrap();
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(OSMO3, &coins(1_000, NATIVE_TOKEN)),
+        ExecuteMsg::LiquidStake {
+            mint_to: None,
+            transfer_to_native_chain: None,
+            expected_mint_amount: None,
+            native_sender: None,
+        },
+    )
+    .unwrap_err();
+    assert!(matches!(err, ContractError::DenomAdminTransferred {}));
+}
+
+#[test]
+fn proper_revoke_denom_admin_transfer() {
+    let mut deps = init();
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(ADMIN, &[]),
+        ExecuteMsg::RevokeDenomAdminTransfer {},
+    )
+    .unwrap_err();
+    assert!(matches!(err, ContractError::NoPendingDenomAdminTransfer {}));
+
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(ADMIN, &[]),
+        ExecuteMsg::TransferDenomAdmin {
+            new_admin: NEW_ADMIN.to_string(),
+        },
+    )
+    .unwrap();
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("bob", &[]),
+        ExecuteMsg::RevokeDenomAdminTransfer {},
+    )
+    .unwrap_err();
+    assert!(matches!(err, ContractError::Admin(_)));
+
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(ADMIN, &[]),
+        ExecuteMsg::RevokeDenomAdminTransfer {},
+    )
+    .unwrap();
+    assert_eq!(None, pending_transfer(deps.as_ref()));
+    assert!(!CONFIG.load(&deps.storage).unwrap().stopped);
+}
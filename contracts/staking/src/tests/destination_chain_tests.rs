@@ -0,0 +1,243 @@
+use crate::contract::{execute, query};
+#[cfg(not(any(feature = "cw20", feature = "cosmwasm-chain")))]
+use crate::contract::{reply, sudo};
+use crate::error::ContractError;
+use crate::msg::{DestinationChainsResponse, ExecuteMsg, QueryMsg};
+#[cfg(not(any(feature = "cw20", feature = "cosmwasm-chain")))]
+use crate::msg::{IBCLifecycleComplete, SudoMsg};
+use crate::state::ibc;
+#[cfg(not(any(feature = "cw20", feature = "cosmwasm-chain")))]
+use crate::state::INFLIGHT_PACKETS;
+#[cfg(not(any(feature = "cw20", feature = "cosmwasm-chain")))]
+use crate::tests::test_helper::LIQUID_STAKE_TOKEN_DENOM;
+use crate::tests::test_helper::{init, ADMIN, NATIVE_TOKEN, OSMO3};
+#[cfg(not(any(feature = "cw20", feature = "cosmwasm-chain")))]
+use crate::tokenfactory;
+#[cfg(not(any(feature = "cw20", feature = "cosmwasm-chain")))]
+use cosmwasm_std::testing::MOCK_CONTRACT_ADDR;
+use cosmwasm_std::testing::{mock_env, mock_info};
+use cosmwasm_std::{coins, from_json};
+#[cfg(not(any(feature = "cw20", feature = "cosmwasm-chain")))]
+use cosmwasm_std::{Binary, Coin, CosmosMsg, Reply, SubMsgResponse, SubMsgResult};
+#[cfg(not(any(feature = "cw20", feature = "cosmwasm-chain")))]
+use osmosis_std::types::ibc::applications::transfer::v1::{MsgTransfer, MsgTransferResponse};
+#[cfg(not(any(feature = "cw20", feature = "cosmwasm-chain")))]
+use std::vec::Vec;
+
+fn neutron_address() -> String {
+    bech32::encode::<bech32::Bech32>(bech32::Hrp::parse("neutron").unwrap(), &[1u8; 20]).unwrap()
+}
+
+fn set_neutron(
+    deps: &mut cosmwasm_std::OwnedDeps<
+        cosmwasm_std::MemoryStorage,
+        cosmwasm_std::testing::MockApi,
+        cosmwasm_std::testing::MockQuerier,
+    >,
+) {
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(ADMIN, &[]),
+        ExecuteMsg::SetDestinationChain {
+            bech32_prefix: "neutron".to_string(),
+            channel_id: "channel-5".to_string(),
+            hop: Some(ibc::IbcHop {
+                receiver: "pfm".to_string(),
+                channel_id: "channel-2".to_string(),
+            }),
+        },
+    )
+    .unwrap();
+}
+
+#[test]
+fn set_and_remove_destination_chain() {
+    let mut deps = init();
+
+    let set_msg = ExecuteMsg::SetDestinationChain {
+        bech32_prefix: "neutron".to_string(),
+        channel_id: "channel-5".to_string(),
+        hop: None,
+    };
+    let err = execute(deps.as_mut(), mock_env(), mock_info(OSMO3, &[]), set_msg).unwrap_err();
+    assert!(matches!(err, ContractError::Admin(_)));
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(ADMIN, &[]),
+        ExecuteMsg::SetDestinationChain {
+            bech32_prefix: "neutron".to_string(),
+            channel_id: "channel5".to_string(),
+            hop: None,
+        },
+    )
+    .unwrap_err();
+    assert!(matches!(err, ContractError::Std(_)));
+
+    set_neutron(&mut deps);
+    let res: DestinationChainsResponse = from_json(
+        query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::DestinationChains {
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(1, res.chains.len());
+    assert_eq!("channel-5", res.chains[0].channel_id);
+
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(ADMIN, &[]),
+        ExecuteMsg::RemoveDestinationChain {
+            bech32_prefix: "neutron".to_string(),
+        },
+    )
+    .unwrap();
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(ADMIN, &[]),
+        ExecuteMsg::RemoveDestinationChain {
+            bech32_prefix: "neutron".to_string(),
+        },
+    )
+    .unwrap_err();
+    assert!(matches!(
+        err,
+        ContractError::DestinationChainNotFound { .. }
+    ));
+}
+
+#[test]
+fn liquid_stake_to_unregistered_chain_fails() {
+    let mut deps = init();
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(OSMO3, &coins(1000, NATIVE_TOKEN)),
+        ExecuteMsg::LiquidStake {
+            mint_to: Some(neutron_address()),
+            transfer_to_native_chain: None,
+            expected_mint_amount: None,
+            native_sender: None,
+        },
+    )
+    .unwrap_err();
+    assert!(matches!(err, ContractError::InvalidAddress {}));
+}
+
+// CW20 liquid stake tokens can't be sent over IBC and `IbcMsg::Transfer` can't
+// carry the packet forward memo
+#[test]
+#[cfg(not(any(feature = "cw20", feature = "cosmwasm-chain")))]
+fn liquid_stake_to_destination_chain() {
+    let mut deps = init();
+    let env = mock_env();
+    set_neutron(&mut deps);
+
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(OSMO3, &coins(1000, NATIVE_TOKEN)),
+        ExecuteMsg::LiquidStake {
+            mint_to: Some(neutron_address()),
+            transfer_to_native_chain: None,
+            expected_mint_amount: None,
+            native_sender: None,
+        },
+    )
+    .unwrap();
+
+    // The minted tokens are sent to the hop receiver, which forwards them
+    let lst_transfer = res.messages.last().unwrap();
+    let CosmosMsg::Stargate { value, .. } = &lst_transfer.msg else {
+        panic!("unexpected message {:?}", lst_transfer.msg);
+    };
+    let msg = MsgTransfer::try_from(value.clone()).unwrap();
+    assert_eq!("channel-5", msg.source_channel);
+    assert_eq!("pfm", msg.receiver);
+    assert!(msg.memo.contains(&format!(
+        "\"forward\":{{\"receiver\":\"{}\",\"port\":\"transfer\",\"channel\":\"channel-2\"}}",
+        neutron_address()
+    )));
+
+    // The delivery is tracked as an in-flight packet
+    let sequence = 1;
+    reply(
+        deps.as_mut(),
+        env.clone(),
+        Reply {
+            id: lst_transfer.id,
+            result: SubMsgResult::Ok(SubMsgResponse {
+                data: Some(Binary::from(MsgTransferResponse { sequence })),
+                events: Vec::new(),
+            }),
+        },
+    )
+    .unwrap();
+    let packet = INFLIGHT_PACKETS
+        .load(&deps.storage, ("channel-5", sequence))
+        .unwrap();
+    assert_eq!(neutron_address(), packet.receiver);
+    assert_eq!("channel-5", packet.route.as_ref().unwrap().channel_id);
+
+    // A timeout on the routed channel refunds the depositor
+    let res = sudo(
+        deps.as_mut(),
+        env,
+        SudoMsg::IBCLifecycleComplete(IBCLifecycleComplete::IBCTimeout {
+            channel: "channel-5".to_string(),
+            sequence,
+        }),
+    )
+    .unwrap();
+    assert_eq!(
+        tokenfactory::send(
+            MOCK_CONTRACT_ADDR.to_string(),
+            Coin::new(
+                1000,
+                format!(
+                    "factory/{}/{}",
+                    MOCK_CONTRACT_ADDR, LIQUID_STAKE_TOKEN_DENOM
+                )
+            ),
+            OSMO3.to_string(),
+        )
+        .unwrap(),
+        res.messages[0].msg
+    );
+    assert!(INFLIGHT_PACKETS
+        .may_load(&deps.storage, ("channel-5", sequence))
+        .unwrap()
+        .is_none());
+}
+
+#[test]
+#[cfg(all(feature = "cosmwasm-chain", not(feature = "cw20")))]
+fn forwarded_transfer_is_rejected_by_cosmwasm_chain() {
+    let mut deps = init();
+    set_neutron(&mut deps);
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(OSMO3, &coins(1000, NATIVE_TOKEN)),
+        ExecuteMsg::LiquidStake {
+            mint_to: Some(neutron_address()),
+            transfer_to_native_chain: None,
+            expected_mint_amount: None,
+            native_sender: None,
+        },
+    )
+    .unwrap_err();
+    assert!(matches!(err, ContractError::Std(_)));
+}
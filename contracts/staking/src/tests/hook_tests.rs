@@ -0,0 +1,296 @@
+use crate::chain::{Backend, Chain};
+use crate::contract::{execute, query, reply, HOOK_GAS_LIMIT, HOOK_REPLY_ID};
+use crate::error::ContractError;
+use crate::hooks::{HookExecuteMsg, HookMsg};
+use crate::msg::{ExecuteMsg, HookSubscriptionsResponse, QueryMsg};
+use crate::state::{HookEvent, HookSubscription};
+use crate::tests::test_helper::{init, liquid_unstake, ADMIN, NATIVE_TOKEN, OSMO1, OSMO2, OSMO3};
+use crate::types::MAX_HOOK_SUBSCRIPTIONS;
+use cosmwasm_std::testing::{mock_env, mock_info};
+use cosmwasm_std::{
+    coins, from_json, to_json_binary, Addr, Decimal, Deps, Reply, ReplyOn, SubMsg, SubMsgResult,
+    Uint128,
+};
+
+fn hook_subscriptions(deps: Deps) -> Vec<HookSubscription> {
+    let res: HookSubscriptionsResponse = from_json(
+        query(
+            deps,
+            mock_env(),
+            QueryMsg::HookSubscriptions {
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    res.subscriptions
+}
+
+fn hook_msg(subscriber: &str, id: u64, msg: HookMsg) -> SubMsg {
+    SubMsg::reply_on_error(
+        Backend::execute_contract(
+            mock_env().contract.address.to_string(),
+            subscriber.to_string(),
+            to_json_binary(&HookExecuteMsg::MilkywayHook {
+                msg,
+                redemption_rate: Decimal::one(),
+                purchase_rate: Decimal::one(),
+            })
+            .unwrap(),
+            vec![],
+        )
+        .unwrap(),
+        id,
+    )
+    .with_gas_limit(HOOK_GAS_LIMIT)
+}
+
+#[test]
+fn subscribe_and_unsubscribe_hooks() {
+    let mut deps = init();
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(OSMO3, &[]),
+        ExecuteMsg::SubscribeHooks {
+            address: OSMO1.to_string(),
+            events: vec![HookEvent::LiquidStake],
+        },
+    )
+    .unwrap_err();
+    assert!(matches!(err, ContractError::Admin(_)));
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(ADMIN, &[]),
+        ExecuteMsg::SubscribeHooks {
+            address: OSMO1.to_string(),
+            events: vec![],
+        },
+    )
+    .unwrap_err();
+    assert!(matches!(err, ContractError::EmptyHookEvents {}));
+
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(ADMIN, &[]),
+        ExecuteMsg::SubscribeHooks {
+            address: OSMO1.to_string(),
+            events: vec![
+                HookEvent::LiquidStake,
+                HookEvent::ReceiveRewards,
+                HookEvent::LiquidStake,
+            ],
+        },
+    )
+    .unwrap();
+    assert_eq!("milkyway-subscribe_hooks", res.events[0].ty);
+    assert_eq!(
+        "liquid_stake,receive_rewards",
+        res.events[0]
+            .attributes
+            .iter()
+            .find(|a| a.key == "events")
+            .unwrap()
+            .value
+    );
+
+    // Subscribing again replaces the events
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(ADMIN, &[]),
+        ExecuteMsg::SubscribeHooks {
+            address: OSMO1.to_string(),
+            events: vec![HookEvent::SubmitBatch],
+        },
+    )
+    .unwrap();
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(ADMIN, &[]),
+        ExecuteMsg::SubscribeHooks {
+            address: OSMO2.to_string(),
+            events: vec![HookEvent::LiquidUnstake],
+        },
+    )
+    .unwrap();
+    assert_eq!(
+        vec![
+            HookSubscription {
+                address: Addr::unchecked(OSMO1),
+                events: vec![HookEvent::SubmitBatch],
+            },
+            HookSubscription {
+                address: Addr::unchecked(OSMO2),
+                events: vec![HookEvent::LiquidUnstake],
+            },
+        ],
+        hook_subscriptions(deps.as_ref())
+    );
+
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(ADMIN, &[]),
+        ExecuteMsg::UnsubscribeHooks {
+            address: OSMO1.to_string(),
+        },
+    )
+    .unwrap();
+    assert_eq!(1, hook_subscriptions(deps.as_ref()).len());
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(ADMIN, &[]),
+        ExecuteMsg::UnsubscribeHooks {
+            address: OSMO1.to_string(),
+        },
+    )
+    .unwrap_err();
+    assert!(matches!(
+        err,
+        ContractError::HookSubscriptionNotFound { .. }
+    ));
+}
+
+#[test]
+fn subscribe_too_many_hooks_fails() {
+    let mut deps = init();
+
+    for i in 0..MAX_HOOK_SUBSCRIPTIONS {
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(ADMIN, &[]),
+            ExecuteMsg::SubscribeHooks {
+                address: format!("subscriber{}", i),
+                events: vec![HookEvent::LiquidStake],
+            },
+        )
+        .unwrap();
+    }
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(ADMIN, &[]),
+        ExecuteMsg::SubscribeHooks {
+            address: OSMO1.to_string(),
+            events: vec![HookEvent::LiquidStake],
+        },
+    )
+    .unwrap_err();
+    assert!(matches!(err, ContractError::ValueTooBig { .. }));
+
+    // Existing subscriptions can still be updated
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(ADMIN, &[]),
+        ExecuteMsg::SubscribeHooks {
+            address: "subscriber0".to_string(),
+            events: vec![HookEvent::SubmitBatch],
+        },
+    )
+    .unwrap();
+}
+
+#[test]
+fn hook_callbacks_are_non_blocking() {
+    let mut deps = init();
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(ADMIN, &[]),
+        ExecuteMsg::SubscribeHooks {
+            address: OSMO1.to_string(),
+            events: vec![HookEvent::LiquidStake],
+        },
+    )
+    .unwrap();
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(ADMIN, &[]),
+        ExecuteMsg::SubscribeHooks {
+            address: OSMO2.to_string(),
+            events: vec![HookEvent::LiquidStake, HookEvent::LiquidUnstake],
+        },
+    )
+    .unwrap();
+
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(OSMO3, &coins(1000, NATIVE_TOKEN)),
+        ExecuteMsg::LiquidStake {
+            mint_to: None,
+            transfer_to_native_chain: None,
+            expected_mint_amount: None,
+            native_sender: None,
+        },
+    )
+    .unwrap();
+    let stake_msg = HookMsg::LiquidStake {
+        user: OSMO3.to_string(),
+        amount: Uint128::new(1000),
+        mint_amount: Uint128::new(1000),
+    };
+    assert!(res
+        .messages
+        .contains(&hook_msg(OSMO1, HOOK_REPLY_ID, stake_msg.clone())));
+    assert!(res
+        .messages
+        .contains(&hook_msg(OSMO2, HOOK_REPLY_ID - 1, stake_msg)));
+    assert!(res
+        .messages
+        .iter()
+        .filter(|m| m.id >= HOOK_REPLY_ID - 1 && m.id <= HOOK_REPLY_ID)
+        .all(|m| m.reply_on == ReplyOn::Error));
+
+    let res = liquid_unstake(deps.as_mut(), mock_env(), OSMO3, 400).unwrap();
+    assert_eq!(
+        vec![hook_msg(
+            OSMO2,
+            HOOK_REPLY_ID - 1,
+            HookMsg::LiquidUnstake {
+                user: OSMO3.to_string(),
+                batch_id: 1,
+                amount: Uint128::new(400),
+            },
+        )],
+        res.messages
+    );
+
+    // A failed callback is reported without reverting the operation
+    let res = reply(
+        deps.as_mut(),
+        mock_env(),
+        Reply {
+            id: HOOK_REPLY_ID - 1,
+            result: SubMsgResult::Err("hook error".to_string()),
+        },
+    )
+    .unwrap();
+    assert_eq!("milkyway-hook_failed", res.events[0].ty);
+    assert_eq!(OSMO2, res.events[0].attributes[0].value);
+
+    let err = reply(
+        deps.as_mut(),
+        mock_env(),
+        Reply {
+            id: HOOK_REPLY_ID - 2,
+            result: SubMsgResult::Err("hook error".to_string()),
+        },
+    )
+    .unwrap_err();
+    assert!(matches!(err, ContractError::InvalidReplyID { .. }));
+}
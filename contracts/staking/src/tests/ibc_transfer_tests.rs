@@ -1,16 +1,31 @@
-use crate::contract::{execute, reply, sudo, IBC_TIMEOUT};
+use crate::chain::{Backend, Chain, IbcTransferMsg};
+use crate::contract::{execute, instantiate, reply, sudo, IBC_TIMEOUT};
 use crate::error::ContractError;
+use crate::execute::ibc_transfer_msg;
+#[cfg(not(feature = "cw20"))]
+use crate::helpers::derive_intermediate_sender;
 use crate::msg::{ExecuteMsg, IBCLifecycleComplete, SudoMsg};
 use crate::query::query_ibc_queue;
-use crate::state::{ibc, IbcWaitingForReply, IBC_WAITING_FOR_REPLY, INFLIGHT_PACKETS};
-use crate::tests::test_helper::{init, ADMIN, CHANNEL_ID, NATIVE_TOKEN, OSMO3, STAKER_ADDRESS};
-use cosmwasm_std::testing::{mock_env, mock_info};
+use crate::state::{
+    ibc, IbcRetryPolicy, IbcWaitingForReply, CONFIG, IBC_WAITING_FOR_REPLY, INFLIGHT_PACKETS,
+    INFLIGHT_PACKET_RETRIES,
+};
+#[cfg(not(feature = "cw20"))]
+use crate::tests::test_helper::LIQUID_STAKE_TOKEN_DENOM;
+use crate::tests::test_helper::{
+    decode_ibc_transfer, init, mock_init_msg, ADMIN, CELESTIA1, CELESTIA2, CHANNEL_ID,
+    NATIVE_TOKEN, OSMO3, STAKER_ADDRESS,
+};
+#[cfg(not(feature = "cw20"))]
+use crate::tokenfactory;
+use crate::types::{MAX_IBC_RETRY_ATTEMPTS, MAX_IBC_RETRY_BACKOFF, MAX_IBC_TIMEOUT};
+#[cfg(not(feature = "cw20"))]
+use cosmwasm_std::testing::MOCK_CONTRACT_ADDR;
+use cosmwasm_std::testing::{mock_dependencies, mock_env, mock_info};
 use cosmwasm_std::{
-    attr, coins, Addr, Coin, CosmosMsg, IbcTimeout, Reply, ReplyOn, SubMsg, SubMsgResponse,
-    SubMsgResult, Timestamp,
+    attr, coins, Addr, Coin, IbcTimeout, Reply, ReplyOn, SubMsg, SubMsgResponse, SubMsgResult,
+    Timestamp, Uint128,
 };
-use osmosis_std::types::cosmos::base::v1beta1::Coin as OsmosisCoin;
-use osmosis_std::types::ibc::applications::transfer::v1::MsgTransfer;
 use osmosis_std::types::ibc::applications::transfer::v1::MsgTransferResponse;
 use std::vec::Vec;
 
@@ -23,6 +38,7 @@ fn success_ibc_queue() {
         mint_to: None,
         transfer_to_native_chain: None,
         expected_mint_amount: None,
+        native_sender: None,
     };
     let res = execute(deps.as_mut(), mock_env(), info.clone(), msg.clone());
 
@@ -30,10 +46,7 @@ fn success_ibc_queue() {
         env.block.time.nanos() + IBC_TIMEOUT.nanos(),
     ));
 
-    let ibc_coin = OsmosisCoin {
-        denom: NATIVE_TOKEN.to_string(),
-        amount: "1000".to_string(),
-    };
+    let ibc_coin = Coin::new(1000, NATIVE_TOKEN);
 
     let ibc_sub_msg_id = env.block.time.nanos() + env.transaction.unwrap().index as u64;
     match res {
@@ -51,16 +64,15 @@ fn success_ibc_queue() {
                 result.messages[2],
                 SubMsg {
                     id: ibc_sub_msg_id,
-                    msg: <MsgTransfer as Into<CosmosMsg>>::into(MsgTransfer {
+                    msg: Backend::ibc_transfer(IbcTransferMsg {
                         source_channel: CHANNEL_ID.to_string(),
-                        source_port: "transfer".to_string(),
+                        token: ibc_coin,
                         sender: env.contract.address.to_string(),
                         receiver: Addr::unchecked(STAKER_ADDRESS).to_string(),
-                        token: Some(ibc_coin),
-                        timeout_height: None,
                         timeout_timestamp: timeout.timestamp().unwrap().nanos(),
                         memo: format!("{{\"ibc_callback\":\"{}\"}}", env.contract.address),
-                    }),
+                    })
+                    .unwrap(),
                     gas_limit: None,
                     reply_on: ReplyOn::Always,
                 }
@@ -81,10 +93,14 @@ fn success_ibc_queue() {
         Some(IbcWaitingForReply {
             amount: Coin::new(1000, NATIVE_TOKEN),
             receiver: STAKER_ADDRESS.to_string(),
+            route: None,
+            depositor: None,
         })
     );
 
-    let inflight_packet = INFLIGHT_PACKETS.may_load(&deps.storage, sequence).unwrap();
+    let inflight_packet = INFLIGHT_PACKETS
+        .may_load(&deps.storage, (CHANNEL_ID, sequence))
+        .unwrap();
     assert_eq!(Some(inflight_packet), Some(None));
 
     let res = query_ibc_queue(deps.as_ref(), None, None);
@@ -110,14 +126,18 @@ fn success_ibc_queue() {
     println!("test {:?}", ibc_waiting_for_reply);
     assert_eq!(Some(ibc_waiting_for_reply), Some(None));
 
-    let inflight_packet = INFLIGHT_PACKETS.may_load(&deps.storage, sequence).unwrap();
+    let inflight_packet = INFLIGHT_PACKETS
+        .may_load(&deps.storage, (CHANNEL_ID, sequence))
+        .unwrap();
     assert_eq!(
         inflight_packet,
         Some(ibc::IBCTransfer {
             sequence,
             amount: Coin::new(1000, NATIVE_TOKEN),
             receiver: STAKER_ADDRESS.to_string(),
-            status: ibc::PacketLifecycleStatus::Sent
+            status: ibc::PacketLifecycleStatus::Sent,
+            route: None,
+            depositor: None,
         })
     );
 
@@ -133,14 +153,18 @@ fn success_ibc_queue() {
     let _res = execute(deps.as_mut(), mock_env(), info.clone(), msg);
 
     // still the same
-    let inflight_packet = INFLIGHT_PACKETS.may_load(&deps.storage, sequence).unwrap();
+    let inflight_packet = INFLIGHT_PACKETS
+        .may_load(&deps.storage, (CHANNEL_ID, sequence))
+        .unwrap();
     assert_eq!(
         inflight_packet,
         Some(ibc::IBCTransfer {
             sequence,
             amount: Coin::new(1000, NATIVE_TOKEN),
             receiver: STAKER_ADDRESS.to_string(),
-            status: ibc::PacketLifecycleStatus::Sent
+            status: ibc::PacketLifecycleStatus::Sent,
+            route: None,
+            depositor: None,
         })
     );
 
@@ -158,7 +182,9 @@ fn success_ibc_queue() {
         }),
     );
 
-    let inflight_packet = INFLIGHT_PACKETS.may_load(&deps.storage, sequence).unwrap();
+    let inflight_packet = INFLIGHT_PACKETS
+        .may_load(&deps.storage, (CHANNEL_ID, sequence))
+        .unwrap();
     assert_eq!(Some(inflight_packet), Some(None));
 
     let res = query_ibc_queue(deps.as_ref(), None, None);
@@ -174,6 +200,7 @@ fn fail_ibc_queue() {
         mint_to: None,
         transfer_to_native_chain: None,
         expected_mint_amount: None,
+        native_sender: None,
     };
     let res = execute(deps.as_mut(), mock_env(), info.clone(), msg.clone());
     let ibc_sub_msg_id = env.block.time.nanos() + env.transaction.unwrap().index as u64;
@@ -219,14 +246,18 @@ fn fail_ibc_queue() {
         }),
     );
 
-    let inflight_packet = INFLIGHT_PACKETS.may_load(&deps.storage, sequence).unwrap();
+    let inflight_packet = INFLIGHT_PACKETS
+        .may_load(&deps.storage, (CHANNEL_ID, sequence))
+        .unwrap();
     assert_eq!(
         inflight_packet,
         Some(ibc::IBCTransfer {
             sequence,
             amount: Coin::new(1000, NATIVE_TOKEN),
             receiver: STAKER_ADDRESS.to_string(),
-            status: ibc::PacketLifecycleStatus::AckFailure
+            status: ibc::PacketLifecycleStatus::AckFailure,
+            route: None,
+            depositor: None,
         })
     );
 
@@ -241,7 +272,9 @@ fn fail_ibc_queue() {
     };
     let _res = execute(deps.as_mut(), mock_env(), info.clone(), msg);
 
-    let inflight_packet = INFLIGHT_PACKETS.may_load(&deps.storage, sequence).unwrap();
+    let inflight_packet = INFLIGHT_PACKETS
+        .may_load(&deps.storage, (CHANNEL_ID, sequence))
+        .unwrap();
     assert_eq!(Some(inflight_packet), Some(None));
 
     let res = query_ibc_queue(deps.as_ref(), None, None);
@@ -257,6 +290,7 @@ fn timeout_ibc_queue() {
         mint_to: None,
         transfer_to_native_chain: None,
         expected_mint_amount: None,
+        native_sender: None,
     };
     let res = execute(deps.as_mut(), mock_env(), info.clone(), msg.clone());
     let ibc_sub_msg_id = env.block.time.nanos() + env.transaction.unwrap().index as u64;
@@ -300,14 +334,18 @@ fn timeout_ibc_queue() {
         }),
     );
 
-    let inflight_packet = INFLIGHT_PACKETS.may_load(&deps.storage, sequence).unwrap();
+    let inflight_packet = INFLIGHT_PACKETS
+        .may_load(&deps.storage, (CHANNEL_ID, sequence))
+        .unwrap();
     assert_eq!(
         inflight_packet,
         Some(ibc::IBCTransfer {
             sequence,
             amount: Coin::new(1000, NATIVE_TOKEN),
             receiver: STAKER_ADDRESS.to_string(),
-            status: ibc::PacketLifecycleStatus::TimedOut
+            status: ibc::PacketLifecycleStatus::TimedOut,
+            route: None,
+            depositor: None,
         })
     );
 
@@ -322,7 +360,9 @@ fn timeout_ibc_queue() {
     };
     let _res = execute(deps.as_mut(), mock_env(), info.clone(), msg);
 
-    let inflight_packet = INFLIGHT_PACKETS.may_load(&deps.storage, sequence).unwrap();
+    let inflight_packet = INFLIGHT_PACKETS
+        .may_load(&deps.storage, (CHANNEL_ID, sequence))
+        .unwrap();
     assert_eq!(Some(inflight_packet), Some(None));
 
     let res = query_ibc_queue(deps.as_ref(), None, None);
@@ -337,12 +377,14 @@ fn recover_non_paginated() {
     for i in 1..=15 {
         let res = INFLIGHT_PACKETS.save(
             &mut deps.storage,
-            i,
+            (CHANNEL_ID, i),
             &ibc::IBCTransfer {
                 sequence: i,
                 amount: Coin::new(1000, NATIVE_TOKEN),
                 receiver: STAKER_ADDRESS.to_string(),
                 status: ibc::PacketLifecycleStatus::AckFailure,
+                route: None,
+                depositor: None,
             },
         );
         assert!(res.is_ok());
@@ -368,12 +410,14 @@ fn recover_paginated() {
     for i in 1..=15 {
         let res = INFLIGHT_PACKETS.save(
             &mut deps.storage,
-            i,
+            (CHANNEL_ID, i),
             &ibc::IBCTransfer {
                 sequence: i,
                 amount: Coin::new(1000, NATIVE_TOKEN),
                 receiver: STAKER_ADDRESS.to_string(),
                 status: ibc::PacketLifecycleStatus::AckFailure,
+                route: None,
+                depositor: None,
             },
         );
         assert!(res.is_ok());
@@ -398,12 +442,14 @@ fn duplicated_packets_are_ignored_when_recovering() {
     for i in 1..=15 {
         let res = INFLIGHT_PACKETS.save(
             &mut deps.storage,
-            i,
+            (CHANNEL_ID, i),
             &ibc::IBCTransfer {
                 sequence: i,
                 amount: Coin::new(1000, NATIVE_TOKEN),
                 receiver: STAKER_ADDRESS.to_string(),
                 status: ibc::PacketLifecycleStatus::AckFailure,
+                route: None,
+                depositor: None,
             },
         );
         assert!(res.is_ok());
@@ -412,7 +458,15 @@ fn duplicated_packets_are_ignored_when_recovering() {
     // send recover message
     let msg = ExecuteMsg::RecoverPendingIbcTransfers {
         paginated: Some(true),
-        selected_packets: Some(vec![1, 2, 3, 3, 3, 2, 1]),
+        selected_packets: Some(vec![
+            (CHANNEL_ID.to_string(), 1),
+            (CHANNEL_ID.to_string(), 2),
+            (CHANNEL_ID.to_string(), 3),
+            (CHANNEL_ID.to_string(), 3),
+            (CHANNEL_ID.to_string(), 3),
+            (CHANNEL_ID.to_string(), 2),
+            (CHANNEL_ID.to_string(), 1),
+        ]),
         receiver: None,
     };
 
@@ -429,12 +483,14 @@ fn recover_forced() {
     for i in 1..=15 {
         let res = INFLIGHT_PACKETS.save(
             &mut deps.storage,
-            i,
+            (CHANNEL_ID, i),
             &ibc::IBCTransfer {
                 sequence: i,
                 amount: Coin::new(1000, NATIVE_TOKEN),
                 receiver: STAKER_ADDRESS.to_string(),
                 status: ibc::PacketLifecycleStatus::TimedOut,
+                route: None,
+                depositor: None,
             },
         );
         assert!(res.is_ok());
@@ -443,7 +499,11 @@ fn recover_forced() {
     // send recover message
     let msg = ExecuteMsg::RecoverPendingIbcTransfers {
         paginated: Some(true),
-        selected_packets: Some(vec![1, 2, 3]),
+        selected_packets: Some(vec![
+            (CHANNEL_ID.to_string(), 1),
+            (CHANNEL_ID.to_string(), 2),
+            (CHANNEL_ID.to_string(), 3),
+        ]),
         receiver: None,
     };
 
@@ -461,12 +521,14 @@ fn recover_sent_packet_fails() {
     INFLIGHT_PACKETS
         .save(
             &mut deps.storage,
-            1,
+            (CHANNEL_ID, 1),
             &ibc::IBCTransfer {
                 sequence: 1,
                 amount: Coin::new(1000, NATIVE_TOKEN),
                 receiver: STAKER_ADDRESS.to_string(),
                 status: ibc::PacketLifecycleStatus::Sent,
+                route: None,
+                depositor: None,
             },
         )
         .unwrap();
@@ -474,7 +536,7 @@ fn recover_sent_packet_fails() {
     // send recover message
     let msg = ExecuteMsg::RecoverPendingIbcTransfers {
         paginated: Some(true),
-        selected_packets: Some(vec![1]),
+        selected_packets: Some(vec![(CHANNEL_ID.to_string(), 1)]),
         receiver: None,
     };
 
@@ -494,23 +556,27 @@ fn recover_multiple() {
 
     let res = INFLIGHT_PACKETS.save(
         &mut deps.storage,
-        1,
+        (CHANNEL_ID, 1),
         &ibc::IBCTransfer {
             sequence: 1,
             amount: Coin::new(1000, NATIVE_TOKEN),
             receiver: STAKER_ADDRESS.to_string(),
             status: ibc::PacketLifecycleStatus::TimedOut,
+            route: None,
+            depositor: None,
         },
     );
     assert!(res.is_ok());
     let res = INFLIGHT_PACKETS.save(
         &mut deps.storage,
-        2,
+        (CHANNEL_ID, 2),
         &ibc::IBCTransfer {
             sequence: 2,
             amount: Coin::new(2000, NATIVE_TOKEN),
             receiver: STAKER_ADDRESS.to_string(),
             status: ibc::PacketLifecycleStatus::AckFailure,
+            route: None,
+            depositor: None,
         },
     );
     assert!(res.is_ok());
@@ -527,19 +593,15 @@ fn recover_multiple() {
     assert_eq!(res.messages.len(), 1);
     assert_eq!(
         res.messages[0].msg,
-        CosmosMsg::from(MsgTransfer {
+        Backend::ibc_transfer(IbcTransferMsg {
             source_channel: CHANNEL_ID.to_string(),
-            source_port: "transfer".to_string(),
+            token: Coin::new(3000, NATIVE_TOKEN.to_string()),
             sender: env.contract.address.to_string(),
             receiver: Addr::unchecked(STAKER_ADDRESS).to_string(),
-            token: Some(OsmosisCoin {
-                denom: NATIVE_TOKEN.to_string(),
-                amount: "3000".to_string(),
-            }),
-            timeout_height: None,
             timeout_timestamp: env.block.time.nanos() + IBC_TIMEOUT.nanos(),
             memo: format!("{{\"ibc_callback\":\"{}\"}}", env.contract.address),
         })
+        .unwrap()
     );
 
     let inflight_packet =
@@ -554,12 +616,14 @@ fn recover_recursive() {
 
     let res = INFLIGHT_PACKETS.save(
         &mut deps.storage,
-        1,
+        (CHANNEL_ID, 1),
         &ibc::IBCTransfer {
             sequence: 1,
             amount: Coin::new(1000, NATIVE_TOKEN),
             receiver: STAKER_ADDRESS.to_string(),
             status: ibc::PacketLifecycleStatus::TimedOut,
+            route: None,
+            depositor: None,
         },
     );
     assert!(res.is_ok());
@@ -614,3 +678,499 @@ fn recover_recursive() {
     let res = res.unwrap();
     assert_eq!(res.messages.len(), 1);
 }
+
+#[test]
+fn retry_failed_transfers() {
+    let mut deps = init();
+    let mut env = mock_env();
+
+    let mut config = CONFIG.load(&deps.storage).unwrap();
+    config.protocol_chain_config.ibc_retry_policy = Some(IbcRetryPolicy {
+        max_attempts: 2,
+        backoff: 600,
+    });
+    CONFIG.save(&mut deps.storage, &config).unwrap();
+
+    let receiver = "celestia1user".to_string();
+    INFLIGHT_PACKETS
+        .save(
+            &mut deps.storage,
+            (CHANNEL_ID, 1),
+            &ibc::IBCTransfer {
+                sequence: 1,
+                amount: Coin::new(1000, NATIVE_TOKEN),
+                receiver: receiver.clone(),
+                status: ibc::PacketLifecycleStatus::TimedOut,
+                route: None,
+                depositor: None,
+            },
+        )
+        .unwrap();
+
+    let retry = |deps: &mut cosmwasm_std::OwnedDeps<_, _, _>, env: &cosmwasm_std::Env| {
+        execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("anyone", &[]),
+            ExecuteMsg::RetryFailedTransfers { limit: None },
+        )
+    };
+    let fail = |deps: &mut cosmwasm_std::OwnedDeps<_, _, _>, reply_id: u64, sequence: u64| {
+        reply(
+            deps.as_mut(),
+            mock_env(),
+            Reply {
+                id: reply_id,
+                result: SubMsgResult::Ok(SubMsgResponse {
+                    data: Some(cosmwasm_std::Binary::from(MsgTransferResponse { sequence })),
+                    events: Vec::new(),
+                }),
+            },
+        )
+        .unwrap();
+        sudo(
+            deps.as_mut(),
+            mock_env(),
+            SudoMsg::IBCLifecycleComplete(IBCLifecycleComplete::IBCTimeout {
+                channel: CHANNEL_ID.to_string(),
+                sequence,
+            }),
+        )
+        .unwrap();
+    };
+
+    // The first retry is sent to the original receiver
+    let res = retry(&mut deps, &env).unwrap();
+    assert_eq!(1, res.messages.len());
+    assert_eq!(2, res.messages[0].id);
+    let (_, transfer_receiver, _) = decode_ibc_transfer(&res.messages[0].msg);
+    assert_eq!(receiver, transfer_receiver);
+    assert!(INFLIGHT_PACKETS
+        .may_load(&deps.storage, (CHANNEL_ID, 1))
+        .unwrap()
+        .is_none());
+
+    fail(&mut deps, 2, 2);
+    let retries = INFLIGHT_PACKET_RETRIES
+        .load(&deps.storage, (CHANNEL_ID, 2))
+        .unwrap();
+    assert_eq!(1, retries.attempts);
+
+    // The second retry waits for the backoff
+    let err = retry(&mut deps, &env).unwrap_err();
+    assert!(matches!(err, ContractError::NoInflightPackets {}));
+
+    env.block.time = env.block.time.plus_seconds(600);
+    let res = retry(&mut deps, &env).unwrap();
+    assert_eq!(3, res.messages[0].id);
+    fail(&mut deps, 3, 3);
+    assert_eq!(
+        2,
+        INFLIGHT_PACKET_RETRIES
+            .load(&deps.storage, (CHANNEL_ID, 3))
+            .unwrap()
+            .attempts
+    );
+
+    // After the maximum attempts the packet is moved to the dead letters
+    env.block.time = env.block.time.plus_seconds(1200);
+    let res = retry(&mut deps, &env).unwrap();
+    assert!(res.messages.is_empty());
+    assert_eq!(
+        ibc::PacketLifecycleStatus::DeadLetter,
+        INFLIGHT_PACKETS
+            .load(&deps.storage, (CHANNEL_ID, 3))
+            .unwrap()
+            .status
+    );
+    let err = retry(&mut deps, &env).unwrap_err();
+    assert!(matches!(err, ContractError::NoInflightPackets {}));
+}
+
+// CW20 liquid stake tokens can't be sent over IBC
+#[test]
+#[cfg(not(feature = "cw20"))]
+fn failed_lst_delivery_is_refunded_to_depositor() {
+    let mut deps = init();
+    let env = mock_env();
+
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(OSMO3, &coins(1000, NATIVE_TOKEN)),
+        ExecuteMsg::LiquidStake {
+            mint_to: Some(CELESTIA2.to_string()),
+            transfer_to_native_chain: None,
+            expected_mint_amount: None,
+            native_sender: None,
+        },
+    )
+    .unwrap();
+
+    let sequence = 7;
+    reply(
+        deps.as_mut(),
+        env.clone(),
+        Reply {
+            id: res.messages.last().unwrap().id,
+            result: SubMsgResult::Ok(SubMsgResponse {
+                data: Some(cosmwasm_std::Binary::from(MsgTransferResponse { sequence })),
+                events: Vec::new(),
+            }),
+        },
+    )
+    .unwrap();
+    let packet = INFLIGHT_PACKETS
+        .load(&deps.storage, (CHANNEL_ID, sequence))
+        .unwrap();
+    assert_eq!(Some(Addr::unchecked(OSMO3)), packet.depositor);
+
+    // The failed delivery is sent back to the depositor on the protocol chain
+    let res = sudo(
+        deps.as_mut(),
+        env,
+        SudoMsg::IBCLifecycleComplete(IBCLifecycleComplete::IBCAck {
+            channel: CHANNEL_ID.to_string(),
+            sequence,
+            ack: "ack".to_string(),
+            success: false,
+        }),
+    )
+    .unwrap();
+    assert_eq!(
+        tokenfactory::send(
+            MOCK_CONTRACT_ADDR.to_string(),
+            Coin::new(
+                1000,
+                format!(
+                    "factory/{}/{}",
+                    MOCK_CONTRACT_ADDR, LIQUID_STAKE_TOKEN_DENOM
+                )
+            ),
+            OSMO3.to_string(),
+        )
+        .unwrap(),
+        res.messages[0].msg
+    );
+    assert!(res.attributes.contains(&attr("refunded_to", OSMO3)));
+    assert!(INFLIGHT_PACKETS
+        .may_load(&deps.storage, (CHANNEL_ID, sequence))
+        .unwrap()
+        .is_none());
+}
+
+#[test]
+#[cfg(not(feature = "cw20"))]
+fn failed_lst_delivery_to_native_sender_is_retried() {
+    let mut deps = init();
+    let env = mock_env();
+
+    let intermediate_sender = derive_intermediate_sender(CHANNEL_ID, CELESTIA2, "osmo").unwrap();
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(&intermediate_sender, &coins(1000, NATIVE_TOKEN)),
+        ExecuteMsg::LiquidStake {
+            mint_to: None,
+            transfer_to_native_chain: None,
+            expected_mint_amount: None,
+            native_sender: Some(CELESTIA2.to_string()),
+        },
+    )
+    .unwrap();
+
+    // Each failed delivery is re-sent to the native sender, at most 5
+    // times with the default retry policy
+    let mut reply_id = res.messages.last().unwrap().id;
+    for sequence in 1..=6 {
+        reply(
+            deps.as_mut(),
+            env.clone(),
+            Reply {
+                id: reply_id,
+                result: SubMsgResult::Ok(SubMsgResponse {
+                    data: Some(cosmwasm_std::Binary::from(MsgTransferResponse { sequence })),
+                    events: Vec::new(),
+                }),
+            },
+        )
+        .unwrap();
+        assert_eq!(
+            sequence - 1,
+            INFLIGHT_PACKET_RETRIES
+                .may_load(&deps.storage, (CHANNEL_ID, sequence))
+                .unwrap()
+                .map_or(0, |retry| retry.attempts as u64)
+        );
+
+        let res = sudo(
+            deps.as_mut(),
+            env.clone(),
+            SudoMsg::IBCLifecycleComplete(IBCLifecycleComplete::IBCTimeout {
+                channel: CHANNEL_ID.to_string(),
+                sequence,
+            }),
+        )
+        .unwrap();
+        if sequence == 6 {
+            assert!(res.messages.is_empty());
+            break;
+        }
+        assert!(!INFLIGHT_PACKETS.has(&deps.storage, (CHANNEL_ID, sequence)));
+        reply_id = res.messages[0].id;
+        let waiting = IBC_WAITING_FOR_REPLY.load(&deps.storage, reply_id).unwrap();
+        assert_eq!(CELESTIA2, waiting.receiver);
+        assert_eq!(None, waiting.depositor);
+    }
+
+    // The last failure is left to be recovered to the native sender
+    let packet = INFLIGHT_PACKETS
+        .load(&deps.storage, (CHANNEL_ID, 6))
+        .unwrap();
+    assert_eq!(ibc::PacketLifecycleStatus::TimedOut, packet.status);
+    assert_eq!(CELESTIA2, packet.receiver);
+}
+
+#[test]
+fn ibc_transfer_with_configured_timeout_and_route() {
+    let mut deps = init();
+    let env = mock_env();
+
+    let mut config = CONFIG.load(&deps.storage).unwrap();
+    config.protocol_chain_config.ibc_timeout = Some(3_600);
+    CONFIG.save(&mut deps.storage, &config).unwrap();
+
+    let msg = ibc_transfer_msg(
+        &deps.as_ref(),
+        &env,
+        "neutron1receiver",
+        Coin::new(1000, NATIVE_TOKEN),
+        Some(&ibc::IbcRoute {
+            channel_id: "channel-5".to_string(),
+            hop: Some(ibc::IbcHop {
+                receiver: "pfm".to_string(),
+                channel_id: "channel-2".to_string(),
+            }),
+        }),
+    )
+    .unwrap();
+
+    assert_eq!(
+        env.block.time.plus_seconds(3_600).nanos(),
+        msg.timeout_timestamp
+    );
+    assert_eq!("channel-5", msg.source_channel);
+    assert_eq!("pfm", msg.receiver);
+    assert_eq!(
+        format!(
+            "{{\"ibc_callback\":\"{}\",\"forward\":{{\"receiver\":\"neutron1receiver\",\"port\":\"transfer\",\"channel\":\"channel-2\"}}}}",
+            env.contract.address
+        ),
+        msg.memo
+    );
+}
+
+#[test]
+fn invalid_ibc_timeout_fails() {
+    for timeout in [0, MAX_IBC_TIMEOUT + 1, u64::MAX] {
+        let mut msg = mock_init_msg();
+        msg.protocol_chain_config.ibc_timeout = Some(timeout);
+        let err = instantiate(
+            mock_dependencies().as_mut(),
+            mock_env(),
+            mock_info(ADMIN, &[]),
+            msg,
+        )
+        .unwrap_err();
+        assert!(matches!(err, ContractError::InvalidIbcTimeout {}));
+    }
+}
+
+#[test]
+fn recover_to_original_receivers() {
+    let mut deps = init();
+
+    for (sequence, receiver) in [(1, CELESTIA1), (2, CELESTIA2), (3, CELESTIA1)] {
+        INFLIGHT_PACKETS
+            .save(
+                &mut deps.storage,
+                (CHANNEL_ID, sequence),
+                &ibc::IBCTransfer {
+                    sequence,
+                    amount: Coin::new(100, NATIVE_TOKEN),
+                    receiver: receiver.to_string(),
+                    status: ibc::PacketLifecycleStatus::AckFailure,
+                    route: None,
+                    depositor: None,
+                },
+            )
+            .unwrap();
+    }
+
+    // Without a receiver each packet goes back to its original receiver
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("anyone", &[]),
+        ExecuteMsg::RecoverPendingIbcTransfers {
+            paginated: None,
+            selected_packets: None,
+            receiver: None,
+        },
+    )
+    .unwrap();
+    assert_eq!(2, res.messages.len());
+    assert!(!res.attributes.iter().any(|a| a.key == "receiver"));
+
+    let waiting = |id: u64| IBC_WAITING_FOR_REPLY.load(&deps.storage, id).unwrap();
+    assert_eq!(CELESTIA1, waiting(res.messages[0].id).receiver);
+    assert_eq!(Uint128::new(200), waiting(res.messages[0].id).amount.amount);
+    assert_eq!(CELESTIA2, waiting(res.messages[1].id).receiver);
+    assert_eq!(Uint128::new(100), waiting(res.messages[1].id).amount.amount);
+}
+
+#[test]
+fn retry_skips_packets_in_backoff() {
+    let mut deps = init();
+    let env = mock_env();
+
+    for sequence in [1, 2] {
+        INFLIGHT_PACKETS
+            .save(
+                &mut deps.storage,
+                (CHANNEL_ID, sequence),
+                &ibc::IBCTransfer {
+                    sequence,
+                    amount: Coin::new(100, NATIVE_TOKEN),
+                    receiver: CELESTIA1.to_string(),
+                    status: ibc::PacketLifecycleStatus::TimedOut,
+                    route: None,
+                    depositor: None,
+                },
+            )
+            .unwrap();
+    }
+    // The first packet has just been retried
+    INFLIGHT_PACKET_RETRIES
+        .save(
+            &mut deps.storage,
+            (CHANNEL_ID, 1),
+            &ibc::IBCTransferRetry {
+                attempts: 1,
+                last_attempt: env.block.time,
+            },
+        )
+        .unwrap();
+
+    let res = execute(
+        deps.as_mut(),
+        env,
+        mock_info("anyone", &[]),
+        ExecuteMsg::RetryFailedTransfers { limit: Some(1) },
+    )
+    .unwrap();
+    assert_eq!(1, res.messages.len());
+    assert!(INFLIGHT_PACKETS.has(&deps.storage, (CHANNEL_ID, 1)));
+    assert!(!INFLIGHT_PACKETS.has(&deps.storage, (CHANNEL_ID, 2)));
+}
+
+#[test]
+fn invalid_ibc_retry_policy_fails() {
+    let policy = |max_attempts, backoff| IbcRetryPolicy {
+        max_attempts,
+        backoff,
+    };
+    assert!(policy(1, 0).validate().is_ok());
+    assert!(policy(MAX_IBC_RETRY_ATTEMPTS, MAX_IBC_RETRY_BACKOFF)
+        .validate()
+        .is_ok());
+
+    for invalid in [
+        policy(0, 600),
+        policy(MAX_IBC_RETRY_ATTEMPTS + 1, 600),
+        policy(5, MAX_IBC_RETRY_BACKOFF + 1),
+    ] {
+        let mut msg = mock_init_msg();
+        msg.protocol_chain_config.ibc_retry_policy = Some(invalid);
+        let err = instantiate(
+            mock_dependencies().as_mut(),
+            mock_env(),
+            mock_info(ADMIN, &[]),
+            msg,
+        )
+        .unwrap_err();
+        assert!(matches!(err, ContractError::InvalidIbcRetryPolicy { .. }));
+    }
+}
+
+#[test]
+fn same_sequence_on_different_channels() {
+    let mut deps = init();
+    let env = mock_env();
+
+    // A transfer to the native chain and one routed through another
+    // channel get the same sequence on their own channel
+    let sequence = 1;
+    for (id, route) in [
+        (10, None),
+        (
+            11,
+            Some(ibc::IbcRoute {
+                channel_id: "channel-5".to_string(),
+                hop: None,
+            }),
+        ),
+    ] {
+        IBC_WAITING_FOR_REPLY
+            .save(
+                &mut deps.storage,
+                id,
+                &IbcWaitingForReply {
+                    amount: Coin::new(1000, NATIVE_TOKEN),
+                    receiver: STAKER_ADDRESS.to_string(),
+                    route,
+                    depositor: None,
+                },
+            )
+            .unwrap();
+        reply(
+            deps.as_mut(),
+            env.clone(),
+            Reply {
+                id,
+                result: SubMsgResult::Ok(SubMsgResponse {
+                    data: Some(cosmwasm_std::Binary::from(MsgTransferResponse { sequence })),
+                    events: Vec::new(),
+                }),
+            },
+        )
+        .unwrap();
+    }
+    assert!(INFLIGHT_PACKETS.has(&deps.storage, (CHANNEL_ID, sequence)));
+    assert!(INFLIGHT_PACKETS.has(&deps.storage, ("channel-5", sequence)));
+
+    // The timeout only fails the packet sent through its channel
+    sudo(
+        deps.as_mut(),
+        env,
+        SudoMsg::IBCLifecycleComplete(IBCLifecycleComplete::IBCTimeout {
+            channel: "channel-5".to_string(),
+            sequence,
+        }),
+    )
+    .unwrap();
+    assert_eq!(
+        ibc::PacketLifecycleStatus::TimedOut,
+        INFLIGHT_PACKETS
+            .load(&deps.storage, ("channel-5", sequence))
+            .unwrap()
+            .status
+    );
+    assert_eq!(
+        ibc::PacketLifecycleStatus::Sent,
+        INFLIGHT_PACKETS
+            .load(&deps.storage, (CHANNEL_ID, sequence))
+            .unwrap()
+            .status
+    );
+}
@@ -1,14 +1,28 @@
 use crate::state::{NativeChainConfig, ProtocolChainConfig, ProtocolFeeConfig, BATCHES, CONFIG};
+use crate::tests::test_helper::{mock_init_msg, CELESTIA1, OSMO1, OSMO3};
 use crate::tests::test_helper::{
-    mock_init_msg, CELESTIA1, CELESTIA2, CELESTIAVAL1, CELESTIAVAL2, CHANNEL_ID, NATIVE_TOKEN,
-    OSMO1, OSMO2, OSMO3, OSMO4, STAKER_ADDRESS,
+    CELESTIA2, CELESTIAVAL1, CELESTIAVAL2, CHANNEL_ID, NATIVE_TOKEN, OSMO2, OSMO4, STAKER_ADDRESS,
 };
 use crate::types::MAX_UNBONDING_PERIOD;
-
 use cosmwasm_std::testing::{mock_dependencies, mock_info};
 use cosmwasm_std::{Addr, Order, Uint128};
 use milky_way::staking::BatchStatus;
 
+#[cfg(not(feature = "cw20"))]
+use crate::contract::execute;
+#[cfg(not(feature = "cw20"))]
+use crate::error::ContractError;
+#[cfg(not(feature = "cw20"))]
+use crate::msg::ExecuteMsg;
+#[cfg(not(feature = "cw20"))]
+use crate::tests::test_helper::{init, liquid_stake_token_denom, ADMIN};
+#[cfg(not(feature = "cw20"))]
+use crate::tokenfactory;
+#[cfg(not(feature = "cw20"))]
+use crate::types::DenomMetadata;
+#[cfg(not(feature = "cw20"))]
+use cosmwasm_std::testing::MOCK_CONTRACT_ADDR;
+
 #[test]
 fn invalid_native_chain_account_address_prefix_fails() {
     let mut deps = mock_dependencies();
@@ -265,6 +279,7 @@ fn init_properly() {
             unbonding_period: 1209600,
             staker_address: Addr::unchecked(STAKER_ADDRESS),
             reward_collector_address: Addr::unchecked(CELESTIA2),
+            unbonding_receipt_tolerance: None,
         },
         config.native_chain_config
     );
@@ -275,6 +290,10 @@ fn init_properly() {
             ibc_channel_id: CHANNEL_ID.to_string(),
             oracle_address: Some(Addr::unchecked(OSMO4)),
             minimum_liquid_stake_amount: Uint128::from(100u128),
+            authenticate_ibc_hooks_sender: None,
+            oracle_targets: None,
+            ibc_retry_policy: None,
+            ibc_timeout: None,
         },
         config.protocol_chain_config
     );
@@ -282,6 +301,8 @@ fn init_properly() {
         ProtocolFeeConfig {
             dao_treasury_fee: Uint128::from(10000u128),
             treasury_address: Some(Addr::unchecked(OSMO1)),
+            insurance_fund_fee: None,
+            keeper_bounty: None,
         },
         config.protocol_fee_config
     );
@@ -291,3 +312,88 @@ fn init_properly() {
     );
     assert_eq!(86400, config.batch_period);
 }
+
+// The metadata of a CW20 token is set when it is instantiated
+#[test]
+#[cfg(not(feature = "cw20"))]
+fn init_with_denom_metadata() {
+    let mut deps = mock_dependencies();
+    let mut msg = mock_init_msg();
+    msg.denom_metadata = Some(milktia_metadata());
+
+    let res = crate::contract::instantiate(
+        deps.as_mut(),
+        cosmwasm_std::testing::mock_env(),
+        mock_info(OSMO3, &[]),
+        msg.clone(),
+    )
+    .unwrap();
+    assert_eq!(
+        tokenfactory::set_denom_metadata(
+            MOCK_CONTRACT_ADDR.to_string(),
+            liquid_stake_token_denom(),
+            milktia_metadata(),
+        )
+        .unwrap(),
+        res.messages[1].msg
+    );
+
+    msg.denom_metadata.as_mut().unwrap().exponent = 0;
+    let err = crate::contract::instantiate(
+        deps.as_mut(),
+        cosmwasm_std::testing::mock_env(),
+        mock_info(OSMO3, &[]),
+        msg,
+    )
+    .unwrap_err();
+    assert!(matches!(err, ContractError::InvalidDenomMetadata { .. }));
+}
+
+#[test]
+#[cfg(not(feature = "cw20"))]
+fn set_denom_metadata() {
+    let mut deps = init();
+    let msg = ExecuteMsg::SetDenomMetadata {
+        metadata: milktia_metadata(),
+    };
+
+    let err = execute(
+        deps.as_mut(),
+        cosmwasm_std::testing::mock_env(),
+        mock_info(OSMO3, &[]),
+        msg.clone(),
+    )
+    .unwrap_err();
+    assert!(matches!(err, ContractError::Admin(_)));
+
+    let res = execute(
+        deps.as_mut(),
+        cosmwasm_std::testing::mock_env(),
+        mock_info(ADMIN, &[]),
+        msg,
+    )
+    .unwrap();
+    assert_eq!(
+        vec![tokenfactory::set_denom_metadata(
+            MOCK_CONTRACT_ADDR.to_string(),
+            liquid_stake_token_denom(),
+            milktia_metadata(),
+        )
+        .unwrap()],
+        res.messages
+            .into_iter()
+            .map(|sub_msg| sub_msg.msg)
+            .collect::<Vec<_>>()
+    );
+}
+
+#[cfg(not(feature = "cw20"))]
+fn milktia_metadata() -> DenomMetadata {
+    DenomMetadata {
+        display: "milkTIA".to_string(),
+        symbol: "milkTIA".to_string(),
+        exponent: 6,
+        description: Some("Liquid staked TIA".to_string()),
+        uri: None,
+    }
+}
@@ -0,0 +1,261 @@
+use cosmwasm_std::testing::{mock_env, mock_info, MockApi, MockQuerier, MockStorage};
+use cosmwasm_std::{coins, from_json, OwnedDeps, Uint128};
+use milky_way::staking::{Batch, BatchStatus};
+
+use crate::contract::{execute, query};
+use crate::error::ContractError;
+use crate::helpers::derive_intermediate_sender;
+use crate::msg::{
+    ExecuteMsg, InsuranceFundHistoryResponse, InsuranceFundResponse, QueryMsg, SlashHistoryResponse,
+};
+use crate::state::{
+    InsuranceFundOperation, BATCHES, BATCH_INSURANCE_COVERAGE, CONFIG, INSURANCE_FUND_BALANCE,
+    STATE, TOTAL_CLAIMABLE, TOTAL_INSURANCE_COVERAGE,
+};
+use crate::tests::test_helper::{init, ADMIN, CELESTIAVAL1, NATIVE_TOKEN};
+use crate::types::BatchSlashAmount;
+
+fn init_with_insurance_fund() -> OwnedDeps<MockStorage, MockApi, MockQuerier> {
+    let mut deps = init();
+
+    // Half of the fees go to the insurance fund
+    let mut config = CONFIG.load(&deps.storage).unwrap();
+    config.protocol_fee_config.insurance_fund_fee = Some(Uint128::new(50_000));
+    config.protocol_fee_config.treasury_address = None;
+    CONFIG.save(&mut deps.storage, &config).unwrap();
+
+    let mut state = STATE.load(&deps.storage).unwrap();
+    state.total_native_token = Uint128::new(100_000);
+    state.total_liquid_stake_token = Uint128::new(100_000);
+    STATE.save(&mut deps.storage, &state).unwrap();
+
+    deps
+}
+
+fn receive_rewards(deps: &mut OwnedDeps<MockStorage, MockApi, MockQuerier>, amount: u128) {
+    let config = CONFIG.load(&deps.storage).unwrap();
+    let sender = derive_intermediate_sender(
+        &config.protocol_chain_config.ibc_channel_id,
+        config.native_chain_config.reward_collector_address.as_str(),
+        config.protocol_chain_config.account_address_prefix.as_str(),
+    )
+    .unwrap();
+
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(&sender, &coins(amount, NATIVE_TOKEN)),
+        ExecuteMsg::ReceiveRewards {},
+    )
+    .unwrap();
+}
+
+fn set_stopped(deps: &mut OwnedDeps<MockStorage, MockApi, MockQuerier>, stopped: bool) {
+    let mut config = CONFIG.load(&deps.storage).unwrap();
+    config.stopped = stopped;
+    CONFIG.save(&mut deps.storage, &config).unwrap();
+}
+
+fn query_balance(deps: &OwnedDeps<MockStorage, MockApi, MockQuerier>) -> Uint128 {
+    let res: InsuranceFundResponse =
+        from_json(query(deps.as_ref(), mock_env(), QueryMsg::InsuranceFund {}).unwrap()).unwrap();
+    res.balance
+}
+
+#[test]
+fn rewards_fee_share_is_deposited() {
+    let mut deps = init_with_insurance_fund();
+
+    receive_rewards(&mut deps, 1_000);
+
+    // 10% fee = 100, half of it goes to the insurance fund
+    assert_eq!(Uint128::new(50), query_balance(&deps));
+    let state = STATE.load(&deps.storage).unwrap();
+    assert_eq!(Uint128::new(50), state.total_fees);
+    assert_eq!(Uint128::new(100_900), state.total_native_token);
+
+    let res: InsuranceFundHistoryResponse = from_json(
+        query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::InsuranceFundHistory {
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(1, res.entries.len());
+    assert_eq!(InsuranceFundOperation::Deposit {}, res.entries[0].operation);
+    assert_eq!(Uint128::new(50), res.entries[0].amount);
+    assert_eq!(Uint128::new(50), res.entries[0].balance);
+}
+
+#[test]
+fn insurance_fund_covers_delegated_slash() {
+    let mut deps = init_with_insurance_fund();
+    set_stopped(&mut deps, true);
+    INSURANCE_FUND_BALANCE
+        .save(&mut deps.storage, &Uint128::new(300))
+        .unwrap();
+
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(ADMIN, &[]),
+        ExecuteMsg::RecordSlash {
+            validator: CELESTIAVAL1.to_string(),
+            amount: Uint128::new(1_000),
+            infraction_height: 10,
+            batches: vec![],
+        },
+    )
+    .unwrap();
+
+    // The covered tokens are sent to be staked
+    assert_eq!(2, res.messages.len());
+
+    // Only the uncovered part is lost
+    let state = STATE.load(&deps.storage).unwrap();
+    assert_eq!(Uint128::new(99_300), state.total_native_token);
+    assert_eq!(Uint128::zero(), query_balance(&deps));
+
+    let res: SlashHistoryResponse = from_json(
+        query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::SlashHistory {
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(Uint128::new(300), res.slashes[0].insurance_covered_amount);
+}
+
+#[test]
+fn insurance_fund_covers_batch_slash() {
+    let mut deps = init_with_insurance_fund();
+    set_stopped(&mut deps, true);
+    INSURANCE_FUND_BALANCE
+        .save(&mut deps.storage, &Uint128::new(1_000))
+        .unwrap();
+
+    let mut batch = Batch::new(1, Uint128::new(10_000), 1000);
+    batch.expected_native_unstaked = Some(Uint128::new(10_000));
+    batch.status = BatchStatus::Submitted;
+    BATCHES.save(&mut deps.storage, 1, &batch).unwrap();
+
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(ADMIN, &[]),
+        ExecuteMsg::RecordSlash {
+            validator: CELESTIAVAL1.to_string(),
+            amount: Uint128::new(500),
+            infraction_height: 10,
+            batches: vec![BatchSlashAmount {
+                batch_id: 1,
+                amount: Uint128::new(500),
+            }],
+        },
+    )
+    .unwrap();
+    // Only the oracle update
+    assert_eq!(1, res.messages.len());
+
+    let batch = BATCHES.load(&deps.storage, 1).unwrap();
+    assert_eq!(
+        Uint128::new(10_000),
+        batch.expected_native_unstaked.unwrap()
+    );
+    assert_eq!(Uint128::new(500), query_balance(&deps));
+    assert_eq!(
+        Uint128::new(500),
+        TOTAL_INSURANCE_COVERAGE.load(&deps.storage).unwrap()
+    );
+
+    // The native chain only sends the amount left after the slash
+    set_stopped(&mut deps, false);
+    let config = CONFIG.load(&deps.storage).unwrap();
+    let sender = derive_intermediate_sender(
+        &config.protocol_chain_config.ibc_channel_id,
+        config.native_chain_config.staker_address.as_str(),
+        config.protocol_chain_config.account_address_prefix.as_str(),
+    )
+    .unwrap();
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(&sender, &coins(9_000, NATIVE_TOKEN)),
+        ExecuteMsg::ReceiveUnstakedTokens { batch_id: 1 },
+    )
+    .unwrap();
+    let batch = BATCHES.load(&deps.storage, 1).unwrap();
+    assert_eq!(BatchStatus::PartiallyReceived, batch.status);
+    assert_eq!(
+        Uint128::new(9_000),
+        TOTAL_CLAIMABLE.load(&deps.storage).unwrap()
+    );
+
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(&sender, &coins(500, NATIVE_TOKEN)),
+        ExecuteMsg::ReceiveUnstakedTokens { batch_id: 1 },
+    )
+    .unwrap();
+
+    let batch = BATCHES.load(&deps.storage, 1).unwrap();
+    assert_eq!(BatchStatus::Received, batch.status);
+    assert_eq!(
+        Uint128::new(10_000),
+        batch.received_native_unstaked.unwrap()
+    );
+    assert!(BATCH_INSURANCE_COVERAGE
+        .may_load(&deps.storage, 1)
+        .unwrap()
+        .is_none());
+    assert!(TOTAL_INSURANCE_COVERAGE
+        .load(&deps.storage)
+        .unwrap()
+        .is_zero());
+    assert_eq!(
+        Uint128::new(10_000),
+        TOTAL_CLAIMABLE.load(&deps.storage).unwrap()
+    );
+}
+
+#[test]
+fn batch_slash_with_coverage_above_expected_amount_fails() {
+    let mut deps = init_with_insurance_fund();
+    set_stopped(&mut deps, true);
+
+    let mut batch = Batch::new(1, Uint128::new(10_000), 1000);
+    batch.expected_native_unstaked = Some(Uint128::new(400));
+    batch.status = BatchStatus::Submitted;
+    BATCHES.save(&mut deps.storage, 1, &batch).unwrap();
+    BATCH_INSURANCE_COVERAGE
+        .save(&mut deps.storage, 1, &Uint128::new(500))
+        .unwrap();
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(ADMIN, &[]),
+        ExecuteMsg::RecordSlash {
+            validator: CELESTIAVAL1.to_string(),
+            amount: Uint128::new(100),
+            infraction_height: 10,
+            batches: vec![BatchSlashAmount {
+                batch_id: 1,
+                amount: Uint128::new(100),
+            }],
+        },
+    )
+    .unwrap_err();
+    assert!(matches!(err, ContractError::SlashExceedsAvailable { .. }));
+}
@@ -1 +1,3 @@
+mod resumable;
 mod v1_1_0;
+mod v1_5_0;
@@ -0,0 +1,229 @@
+use cosmwasm_std::testing::{mock_env, mock_info};
+use cosmwasm_std::{from_json, Deps, DepsMut, Env, Order, StdError, StdResult};
+use cw2::{get_contract_version, set_contract_version};
+use cw_storage_plus::{Bound, Map};
+use milky_way::migration::{Migration, MigrationStatus, MigrationStep, StepProgress};
+
+use crate::{
+    contract::{execute, query, CONTRACT_NAME},
+    error::{ContractError, ContractResult},
+    msg::{ExecuteMsg, QueryMsg},
+    tests::test_helper::{init, ADMIN},
+};
+
+const OLD_AMOUNTS: Map<u64, u64> = Map::new("old_amounts");
+const AMOUNTS: Map<u64, u128> = Map::new("amounts");
+
+/// Moves the amounts to a new map with a wider type.
+struct MoveAmounts;
+
+impl MigrationStep<ContractError> for MoveAmounts {
+    type Cursor = u64;
+
+    fn name(&self) -> &'static str {
+        "move_amounts"
+    }
+
+    fn migrate(
+        &self,
+        deps: DepsMut,
+        _env: &Env,
+        cursor: Option<u64>,
+        limit: usize,
+    ) -> ContractResult<StepProgress<u64>> {
+        let mut amounts = OLD_AMOUNTS
+            .range(
+                deps.storage,
+                cursor.map(Bound::exclusive),
+                None,
+                Order::Ascending,
+            )
+            .take(limit.saturating_add(1))
+            .collect::<StdResult<Vec<_>>>()?;
+        let completed = amounts.len() <= limit;
+        amounts.truncate(limit);
+
+        for (key, amount) in &amounts {
+            AMOUNTS.save(deps.storage, *key, &u128::from(*amount))?;
+        }
+
+        Ok(StepProgress {
+            cursor: amounts.last().map(|(key, _)| *key),
+            migrated: amounts.len(),
+            completed,
+        })
+    }
+}
+
+fn all_amounts_moved(deps: Deps) -> ContractResult<()> {
+    let moved = AMOUNTS
+        .keys(deps.storage, None, None, Order::Ascending)
+        .count();
+    let total = OLD_AMOUNTS
+        .keys(deps.storage, None, None, Order::Ascending)
+        .count();
+    if moved != total {
+        return Err(StdError::generic_err("amounts not moved").into());
+    }
+    Ok(())
+}
+
+fn migration() -> Migration<'static, ContractError> {
+    Migration::new(CONTRACT_NAME, "1.0.0", "1.1.0")
+        .step(MoveAmounts)
+        .invariant(all_amounts_moved)
+}
+
+fn migration_status(deps: Deps) -> Option<MigrationStatus> {
+    from_json(query(deps, mock_env(), QueryMsg::MigrationStatus {}).unwrap()).unwrap()
+}
+
+#[test]
+fn resumable_migration() {
+    let mut deps = init();
+    set_contract_version(&mut deps.storage, CONTRACT_NAME, "1.0.0").unwrap();
+    for i in 1..=5 {
+        OLD_AMOUNTS.save(&mut deps.storage, i, &(i * 100)).unwrap();
+    }
+    assert_eq!(None, migration_status(deps.as_ref()));
+
+    let status = migration()
+        .run(deps.as_mut(), &mock_env(), Some(2))
+        .unwrap();
+    assert_eq!(
+        MigrationStatus {
+            from_version: "1.0.0".to_string(),
+            to_version: "1.1.0".to_string(),
+            step: "move_amounts".to_string(),
+            step_index: 0,
+            steps: 1,
+            cursor: Some("2".to_string()),
+            migrated: 2,
+            completed: false,
+        },
+        status
+    );
+    assert_eq!(Some(status), migration_status(deps.as_ref()));
+
+    // The contract can't be used while the migration is in progress
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(ADMIN, &[]),
+        ExecuteMsg::TransferOwnership {
+            new_owner: ADMIN.to_string(),
+        },
+    )
+    .unwrap_err();
+    assert!(matches!(err, ContractError::Migrating {}));
+
+    // Another migration can't be started
+    let err = Migration::<ContractError>::new(CONTRACT_NAME, "1.0.0", "1.2.0")
+        .run(deps.as_mut(), &mock_env(), None)
+        .unwrap_err();
+    assert!(matches!(err, ContractError::Std(_)));
+
+    // An exactly consumed limit leaves the step in progress
+    let status = migration()
+        .run(deps.as_mut(), &mock_env(), Some(2))
+        .unwrap();
+    assert!(!status.completed);
+    assert_eq!(Some("4".to_string()), status.cursor);
+
+    let status = migration()
+        .run(deps.as_mut(), &mock_env(), Some(2))
+        .unwrap();
+    assert!(status.completed);
+    assert_eq!(5, status.migrated);
+    assert_eq!(500, AMOUNTS.load(&deps.storage, 5).unwrap());
+    assert_eq!(
+        "1.1.0",
+        get_contract_version(&deps.storage).unwrap().version
+    );
+
+    // Completed migrations can't run again
+    migration()
+        .run(deps.as_mut(), &mock_env(), None)
+        .unwrap_err();
+}
+
+#[test]
+fn migration_fails_if_invariant_is_broken() {
+    let mut deps = init();
+    set_contract_version(&mut deps.storage, CONTRACT_NAME, "1.0.0").unwrap();
+    for i in 1..=3 {
+        OLD_AMOUNTS.save(&mut deps.storage, i, &i).unwrap();
+    }
+
+    let err = Migration::<ContractError>::new(CONTRACT_NAME, "1.0.0", "1.1.0")
+        .step(MoveAmounts)
+        .invariant(all_amounts_moved)
+        .invariant(|deps| {
+            if AMOUNTS.has(deps.storage, 3) {
+                return Err(StdError::generic_err("unexpected amount").into());
+            }
+            Ok(())
+        })
+        .run(deps.as_mut(), &mock_env(), None)
+        .unwrap_err();
+    assert!(matches!(
+        err,
+        ContractError::Std(StdError::GenericErr { msg, .. }) if msg == "unexpected amount"
+    ));
+}
+
+#[test]
+fn migration_from_wrong_version_fails() {
+    let mut deps = init();
+    set_contract_version(&mut deps.storage, CONTRACT_NAME, "0.4.20").unwrap();
+
+    let err = migration()
+        .run(deps.as_mut(), &mock_env(), None)
+        .unwrap_err();
+    assert!(matches!(err, ContractError::Version(_)));
+}
+
+/// Migrates a single entry regardless of the limit, like a config conversion.
+struct ConvertConfig;
+
+impl MigrationStep<ContractError> for ConvertConfig {
+    type Cursor = ();
+
+    fn name(&self) -> &'static str {
+        "convert_config"
+    }
+
+    fn migrate(
+        &self,
+        _deps: DepsMut,
+        _env: &Env,
+        _cursor: Option<()>,
+        _limit: usize,
+    ) -> ContractResult<StepProgress<()>> {
+        Ok(StepProgress {
+            cursor: Some(()),
+            migrated: 1,
+            completed: true,
+        })
+    }
+}
+
+#[test]
+fn step_exceeding_the_limit_ends_the_execution() {
+    let mut deps = init();
+    set_contract_version(&mut deps.storage, CONTRACT_NAME, "1.0.0").unwrap();
+    OLD_AMOUNTS.save(&mut deps.storage, 1, &100).unwrap();
+
+    let migration = Migration::<ContractError>::new(CONTRACT_NAME, "1.0.0", "1.1.0")
+        .step(ConvertConfig)
+        .step(MoveAmounts);
+    let status = migration.run(deps.as_mut(), &mock_env(), Some(0)).unwrap();
+    assert_eq!("move_amounts", status.step);
+    assert_eq!(1, status.step_index);
+    assert_eq!(1, status.migrated);
+    assert!(!status.completed);
+
+    let status = migration.run(deps.as_mut(), &mock_env(), Some(1)).unwrap();
+    assert!(status.completed);
+    assert_eq!(100, AMOUNTS.load(&deps.storage, 1).unwrap());
+}
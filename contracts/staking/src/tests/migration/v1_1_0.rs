@@ -3,12 +3,12 @@ use cosmwasm_std::{Addr, Coin, Uint128};
 use cw2::set_contract_version;
 
 use crate::contract::{migrate, CONTRACT_NAME};
-use crate::migrations::states::v1_0_0;
+use crate::migrations::states::{v1_0_0, v1_4_0::INFLIGHT_PACKETS};
 use crate::msg::MigrateMsg;
 use crate::state::ibc::{IBCTransfer, PacketLifecycleStatus};
 use crate::state::{
     Config, IbcWaitingForReply, NativeChainConfig, ProtocolChainConfig, ProtocolFeeConfig, CONFIG,
-    IBC_WAITING_FOR_REPLY, INFLIGHT_PACKETS, MIGRATING,
+    IBC_WAITING_FOR_REPLY, MIGRATING,
 };
 use crate::tests::test_helper::{
     CELESTIA2, CHANNEL_ID, LIQUID_STAKE_TOKEN_DENOM, NATIVE_TOKEN, OSMO1, OSMO4, STAKER_ADDRESS,
@@ -48,6 +48,7 @@ fn paginated_migration() {
                     unbonding_period: 1209600,
                     staker_address: Addr::unchecked(STAKER_ADDRESS),
                     reward_collector_address: Addr::unchecked(CELESTIA2),
+                    unbonding_receipt_tolerance: None,
                 },
                 protocol_chain_config: ProtocolChainConfig {
                     account_address_prefix: "osmo".to_string(),
@@ -55,6 +56,10 @@ fn paginated_migration() {
                     ibc_channel_id: CHANNEL_ID.to_string(),
                     oracle_address: Some(Addr::unchecked(OSMO4)),
                     minimum_liquid_stake_amount: Uint128::from(100u128),
+                    authenticate_ibc_hooks_sender: None,
+                    oracle_targets: None,
+                    ibc_retry_policy: None,
+                    ibc_timeout: None,
                 },
                 liquid_stake_token_denom: LIQUID_STAKE_TOKEN_DENOM.to_string(),
                 monitors: vec![],
@@ -62,8 +67,12 @@ fn paginated_migration() {
                 protocol_fee_config: ProtocolFeeConfig {
                     dao_treasury_fee: Uint128::from(10000u128),
                     treasury_address: Some(Addr::unchecked(OSMO1)),
+                    insurance_fund_fee: None,
+                    keeper_bounty: None,
                 },
                 stopped: false,
+                resume_contract_limits: None,
+                batch_limits: None,
             },
         )
         .unwrap();
@@ -129,6 +138,8 @@ fn paginated_migration() {
             IbcWaitingForReply {
                 receiver: STAKER_ADDRESS.to_string(),
                 amount: Coin::new(1000 + u128::from(i), NATIVE_TOKEN),
+                route: None,
+                depositor: None,
             },
             packet,
         );
@@ -142,6 +153,8 @@ fn paginated_migration() {
                 receiver: STAKER_ADDRESS.to_string(),
                 amount: Coin::new(1000 + u128::from(i), NATIVE_TOKEN),
                 status: PacketLifecycleStatus::Sent,
+                route: None,
+                depositor: None,
             },
             packet,
         );
@@ -166,6 +179,7 @@ fn migrate_all_packets() {
                     unbonding_period: 1209600,
                     staker_address: Addr::unchecked(STAKER_ADDRESS),
                     reward_collector_address: Addr::unchecked(CELESTIA2),
+                    unbonding_receipt_tolerance: None,
                 },
                 protocol_chain_config: ProtocolChainConfig {
                     account_address_prefix: "osmo".to_string(),
@@ -173,6 +187,10 @@ fn migrate_all_packets() {
                     ibc_channel_id: CHANNEL_ID.to_string(),
                     oracle_address: Some(Addr::unchecked(OSMO4)),
                     minimum_liquid_stake_amount: Uint128::from(100u128),
+                    authenticate_ibc_hooks_sender: None,
+                    oracle_targets: None,
+                    ibc_retry_policy: None,
+                    ibc_timeout: None,
                 },
                 liquid_stake_token_denom: LIQUID_STAKE_TOKEN_DENOM.to_string(),
                 monitors: vec![],
@@ -180,8 +198,12 @@ fn migrate_all_packets() {
                 protocol_fee_config: ProtocolFeeConfig {
                     dao_treasury_fee: Uint128::from(10000u128),
                     treasury_address: Some(Addr::unchecked(OSMO1)),
+                    insurance_fund_fee: None,
+                    keeper_bounty: None,
                 },
                 stopped: false,
+                resume_contract_limits: None,
+                batch_limits: None,
             },
         )
         .unwrap();
@@ -230,6 +252,8 @@ fn migrate_all_packets() {
             IbcWaitingForReply {
                 receiver: STAKER_ADDRESS.to_string(),
                 amount: Coin::new(1000 + u128::from(i), NATIVE_TOKEN),
+                route: None,
+                depositor: None,
             },
             packet,
         );
@@ -243,6 +267,8 @@ fn migrate_all_packets() {
                 receiver: STAKER_ADDRESS.to_string(),
                 amount: Coin::new(1000 + u128::from(i), NATIVE_TOKEN),
                 status: PacketLifecycleStatus::Sent,
+                route: None,
+                depositor: None,
             },
             packet,
         );
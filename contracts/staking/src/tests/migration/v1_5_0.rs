@@ -0,0 +1,164 @@
+use cosmwasm_std::testing::mock_env;
+use cosmwasm_std::{Coin, Timestamp, Uint128};
+use cw2::{get_contract_version, set_contract_version};
+
+use crate::contract::{migrate, CONTRACT_NAME};
+use crate::migrations::states::v1_4_0;
+use crate::msg::MigrateMsg;
+use crate::state::ibc::{IBCTransfer, IBCTransferRetry, IbcRoute, PacketLifecycleStatus};
+use crate::state::{
+    new_unstake_request, BATCHES, BATCH_CLAIMABLE, FAILED_TRANSFERS_AMOUNT, INFLIGHT_PACKETS,
+    INFLIGHT_PACKET_RETRIES, TOTAL_CLAIMABLE,
+};
+use crate::tests::test_helper::{init, CHANNEL_ID, NATIVE_TOKEN, STAKER_ADDRESS};
+use milky_way::staking::{Batch, BatchStatus};
+
+fn packet(sequence: u64, route: Option<IbcRoute>) -> IBCTransfer {
+    IBCTransfer {
+        sequence,
+        amount: Coin::new(1000, NATIVE_TOKEN),
+        receiver: STAKER_ADDRESS.to_string(),
+        status: PacketLifecycleStatus::TimedOut,
+        route,
+        depositor: None,
+    }
+}
+
+#[test]
+fn inflight_packets_are_keyed_by_channel() {
+    let mut deps = init();
+    set_contract_version(&mut deps.storage, CONTRACT_NAME, "1.4.0").unwrap();
+
+    let route = IbcRoute {
+        channel_id: "channel-5".to_string(),
+        hop: None,
+    };
+    let retry = IBCTransferRetry {
+        attempts: 1,
+        last_attempt: Timestamp::from_seconds(1),
+    };
+    for sequence in 1..=3 {
+        v1_4_0::INFLIGHT_PACKETS
+            .save(&mut deps.storage, sequence, &packet(sequence, None))
+            .unwrap();
+    }
+    v1_4_0::INFLIGHT_PACKETS
+        .save(&mut deps.storage, 4, &packet(4, Some(route.clone())))
+        .unwrap();
+    for sequence in [2, 4] {
+        v1_4_0::INFLIGHT_PACKET_RETRIES
+            .save(&mut deps.storage, sequence, &retry)
+            .unwrap();
+    }
+
+    // The retries are moved first, then the packets
+    let res = migrate(
+        deps.as_mut(),
+        mock_env(),
+        MigrateMsg::V1_4_0ToV1_5_0 { limit: Some(3) },
+    )
+    .unwrap();
+    assert!(res
+        .attributes
+        .iter()
+        .any(|attr| attr.key == "completed" && attr.value == "false"));
+    assert_eq!(
+        "1.4.0",
+        get_contract_version(&deps.storage).unwrap().version
+    );
+
+    migrate(
+        deps.as_mut(),
+        mock_env(),
+        MigrateMsg::V1_4_0ToV1_5_0 { limit: Some(3) },
+    )
+    .unwrap();
+    assert_eq!(
+        "1.5.0",
+        get_contract_version(&deps.storage).unwrap().version
+    );
+
+    for sequence in 1..=3 {
+        assert_eq!(
+            packet(sequence, None),
+            INFLIGHT_PACKETS
+                .load(&deps.storage, (CHANNEL_ID, sequence))
+                .unwrap()
+        );
+    }
+    assert_eq!(
+        packet(4, Some(route)),
+        INFLIGHT_PACKETS
+            .load(&deps.storage, ("channel-5", 4))
+            .unwrap()
+    );
+    assert_eq!(
+        retry,
+        INFLIGHT_PACKET_RETRIES
+            .load(&deps.storage, (CHANNEL_ID, 2))
+            .unwrap()
+    );
+    assert_eq!(
+        retry,
+        INFLIGHT_PACKET_RETRIES
+            .load(&deps.storage, ("channel-5", 4))
+            .unwrap()
+    );
+    assert!(v1_4_0::INFLIGHT_PACKETS.is_empty(&deps.storage));
+    assert!(v1_4_0::INFLIGHT_PACKET_RETRIES.is_empty(&deps.storage));
+    assert_eq!(
+        Uint128::new(4000),
+        FAILED_TRANSFERS_AMOUNT
+            .load(&deps.storage, NATIVE_TOKEN)
+            .unwrap()
+    );
+}
+
+#[test]
+fn claimable_tokens_are_computed_from_the_requests() {
+    let mut deps = init();
+    set_contract_version(&mut deps.storage, CONTRACT_NAME, "1.4.0").unwrap();
+
+    let mut batch = Batch::new(1, Uint128::new(3), 0);
+    batch.received_native_unstaked = Some(Uint128::new(100));
+    batch.update_status(BatchStatus::Received, None);
+    BATCHES.save(&mut deps.storage, 1, &batch).unwrap();
+    let mut batch = Batch::new(2, Uint128::new(3), 0);
+    batch.update_status(BatchStatus::Submitted, None);
+    BATCHES.save(&mut deps.storage, 2, &batch).unwrap();
+    for batch_id in [1, 2] {
+        for user in ["bob", "tom"] {
+            new_unstake_request(
+                &mut deps.as_mut(),
+                user.to_string(),
+                batch_id,
+                Uint128::one(),
+            )
+            .unwrap();
+        }
+    }
+
+    for _ in 0..2 {
+        migrate(
+            deps.as_mut(),
+            mock_env(),
+            MigrateMsg::V1_4_0ToV1_5_0 { limit: Some(3) },
+        )
+        .unwrap();
+    }
+    assert_eq!(
+        "1.5.0",
+        get_contract_version(&deps.storage).unwrap().version
+    );
+
+    // Each request of the received batch can withdraw 33 tokens
+    assert_eq!(
+        Uint128::new(66),
+        BATCH_CLAIMABLE.load(&deps.storage, 1).unwrap()
+    );
+    assert!(!BATCH_CLAIMABLE.has(&deps.storage, 2));
+    assert_eq!(
+        Uint128::new(66),
+        TOTAL_CLAIMABLE.load(&deps.storage).unwrap()
+    );
+}
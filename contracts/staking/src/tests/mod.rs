@@ -1,10 +1,21 @@
+#[cfg(not(feature = "cw20"))]
+mod adopt_denom_tests;
 mod circuit_breaker_tests;
+#[cfg(feature = "cw20")]
+mod cw20_tests;
+mod denom_admin_tests;
+mod destination_chain_tests;
 mod helper_tests;
+mod hook_tests;
 mod ibc_transfer_tests;
 mod instantiate_tests;
+mod insurance_fund_tests;
 mod migration;
+mod oracle_tests;
 mod ownership_tests;
 mod query_tests;
+mod record_slash_tests;
+mod resume_contract_tests;
 mod reward_tests;
 mod slash_batches;
 mod stake_tests;
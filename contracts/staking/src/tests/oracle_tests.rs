@@ -0,0 +1,285 @@
+use crate::chain::{Backend, Chain};
+use crate::contract::{execute, query, reply, UPDATE_ORACLE_GAS_LIMIT, UPDATE_ORACLE_REPLY_ID};
+use crate::error::ContractError;
+use crate::msg::{ExecuteMsg, QueryMsg};
+use crate::oracle::{Oracle, PriceFeedMsg, UpdateRateMsg};
+use crate::state::{OracleAdapter, OracleTarget, OracleUpdateFailure, CONFIG, STATE};
+use crate::tests::test_helper::{init, NATIVE_TOKEN, OSMO1, OSMO2, OSMO3, OSMO4};
+use cosmwasm_std::testing::{mock_env, mock_info, MockApi, MockQuerier, MockStorage};
+use cosmwasm_std::{
+    coins, from_json, to_json_binary, Addr, CosmosMsg, Decimal, Deps, Env, OwnedDeps, Reply,
+    ReplyOn, SubMsgResponse, SubMsgResult, Uint128,
+};
+use serde::Serialize;
+
+fn oracle_update_failure(deps: Deps) -> Option<OracleUpdateFailure> {
+    from_json(query(deps, mock_env(), QueryMsg::OracleUpdateFailure {}).unwrap()).unwrap()
+}
+
+fn failed_oracle_reply(error: &str) -> Reply {
+    Reply {
+        id: UPDATE_ORACLE_REPLY_ID,
+        result: SubMsgResult::Err(error.to_string()),
+    }
+}
+
+fn failed_oracle_reply_id(id: u64) -> Reply {
+    Reply {
+        id,
+        result: SubMsgResult::Err("oracle error".to_string()),
+    }
+}
+
+/// Message the contract sends to update an oracle target.
+fn oracle_update_msg(target: &str, msg: &impl Serialize) -> CosmosMsg {
+    Backend::execute_contract(
+        mock_env().contract.address.to_string(),
+        target.to_string(),
+        to_json_binary(msg).unwrap(),
+        vec![],
+    )
+    .unwrap()
+}
+
+#[test]
+fn oracle_update_is_non_blocking() {
+    let mut deps = init();
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(OSMO3, &coins(1000, NATIVE_TOKEN)),
+        ExecuteMsg::LiquidStake {
+            mint_to: None,
+            transfer_to_native_chain: None,
+            expected_mint_amount: None,
+            native_sender: None,
+        },
+    )
+    .unwrap();
+
+    let oracle_msg = res
+        .messages
+        .iter()
+        .find(|m| m.id == UPDATE_ORACLE_REPLY_ID)
+        .unwrap();
+    assert_eq!(ReplyOn::Error, oracle_msg.reply_on);
+    assert_eq!(Some(UPDATE_ORACLE_GAS_LIMIT), oracle_msg.gas_limit);
+    assert_eq!(
+        oracle_update_msg(
+            OSMO4,
+            &Oracle::PostRates {
+                denom: CONFIG.load(&deps.storage).unwrap().liquid_stake_token_denom,
+                purchase_rate: "1".to_string(),
+                redemption_rate: "1".to_string(),
+            }
+        ),
+        oracle_msg.msg
+    );
+
+    // The failure is recorded without reverting the liquid stake
+    let mut env = mock_env();
+    reply(
+        deps.as_mut(),
+        env.clone(),
+        failed_oracle_reply("out of gas"),
+    )
+    .unwrap();
+    env.block.time = env.block.time.plus_seconds(10);
+    let res = reply(
+        deps.as_mut(),
+        env.clone(),
+        failed_oracle_reply("oracle error"),
+    )
+    .unwrap();
+    assert_eq!("milkyway-oracle_update_failed", res.events[0].ty);
+
+    assert_eq!(
+        Some(OracleUpdateFailure {
+            target: Addr::unchecked(OSMO4),
+            error: "oracle error".to_string(),
+            time: env.block.time,
+            failures: 2,
+        }),
+        oracle_update_failure(deps.as_ref())
+    );
+    assert_eq!(
+        Uint128::new(1000),
+        STATE.load(&deps.storage).unwrap().total_native_token
+    );
+
+    // Successful updates don't reply
+    let res = reply(
+        deps.as_mut(),
+        mock_env(),
+        Reply {
+            id: UPDATE_ORACLE_REPLY_ID,
+            result: SubMsgResult::Ok(SubMsgResponse {
+                events: vec![],
+                data: None,
+            }),
+        },
+    )
+    .unwrap();
+    assert!(res.events.is_empty());
+}
+
+#[test]
+fn push_rates() {
+    let mut deps = init();
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(OSMO3, &[]),
+        ExecuteMsg::PushRates {},
+    )
+    .unwrap_err();
+    assert!(matches!(err, ContractError::NoFailedOracleUpdate {}));
+
+    reply(
+        deps.as_mut(),
+        mock_env(),
+        failed_oracle_reply("oracle error"),
+    )
+    .unwrap();
+
+    // Anyone can push the rates
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(OSMO3, &[]),
+        ExecuteMsg::PushRates {},
+    )
+    .unwrap();
+    assert_eq!(1, res.messages.len());
+    assert_eq!(UPDATE_ORACLE_REPLY_ID, res.messages[0].id);
+    assert_eq!(ReplyOn::Error, res.messages[0].reply_on);
+    assert_eq!(None, oracle_update_failure(deps.as_ref()));
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(OSMO3, &[]),
+        ExecuteMsg::PushRates {},
+    )
+    .unwrap_err();
+    assert!(matches!(err, ContractError::NoFailedOracleUpdate {}));
+}
+
+fn liquid_stake_oracle_msgs(
+    deps: &mut OwnedDeps<MockStorage, MockApi, MockQuerier>,
+    env: Env,
+) -> Vec<(u64, CosmosMsg)> {
+    let res = execute(
+        deps.as_mut(),
+        env,
+        mock_info(OSMO3, &coins(1000, NATIVE_TOKEN)),
+        ExecuteMsg::LiquidStake {
+            mint_to: None,
+            transfer_to_native_chain: None,
+            expected_mint_amount: None,
+            native_sender: None,
+        },
+    )
+    .unwrap();
+    res.messages
+        .into_iter()
+        .filter(|m| m.reply_on == ReplyOn::Error)
+        .map(|m| (m.id, m.msg))
+        .collect()
+}
+
+#[test]
+fn oracle_targets_adapters_and_throttling() {
+    let mut deps = init();
+    let mut config = CONFIG.load(&deps.storage).unwrap();
+    config.protocol_chain_config.oracle_targets = Some(vec![
+        OracleTarget {
+            address: Addr::unchecked(OSMO1),
+            adapter: OracleAdapter::UpdateRate,
+            min_update_interval: None,
+        },
+        OracleTarget {
+            address: Addr::unchecked(OSMO2),
+            adapter: OracleAdapter::PriceFeed,
+            min_update_interval: Some(3_600),
+        },
+    ]);
+    CONFIG.save(&mut deps.storage, &config).unwrap();
+
+    let msgs = liquid_stake_oracle_msgs(&mut deps, mock_env());
+    assert_eq!(
+        vec![
+            (
+                UPDATE_ORACLE_REPLY_ID,
+                oracle_update_msg(
+                    OSMO4,
+                    &Oracle::PostRates {
+                        denom: config.liquid_stake_token_denom.clone(),
+                        purchase_rate: "1".to_string(),
+                        redemption_rate: "1".to_string(),
+                    }
+                )
+            ),
+            (
+                UPDATE_ORACLE_REPLY_ID - 1,
+                oracle_update_msg(
+                    OSMO1,
+                    &UpdateRateMsg::UpdateRate {
+                        denom: config.liquid_stake_token_denom.clone(),
+                        rate: Decimal::one(),
+                    }
+                )
+            ),
+            (
+                UPDATE_ORACLE_REPLY_ID - 2,
+                oracle_update_msg(
+                    OSMO2,
+                    &PriceFeedMsg::SetPrice {
+                        denom: config.liquid_stake_token_denom.clone(),
+                        base_denom: NATIVE_TOKEN.to_string(),
+                        price: Decimal::one(),
+                    }
+                )
+            ),
+        ],
+        msgs
+    );
+
+    // The price feed is throttled
+    let mut env = mock_env();
+    env.block.time = env.block.time.plus_seconds(60);
+    let reply_ids: Vec<u64> = liquid_stake_oracle_msgs(&mut deps, env.clone())
+        .into_iter()
+        .map(|(id, _)| id)
+        .collect();
+    assert_eq!(
+        vec![UPDATE_ORACLE_REPLY_ID, UPDATE_ORACLE_REPLY_ID - 1],
+        reply_ids
+    );
+
+    env.block.time = mock_env().block.time.plus_seconds(3_600);
+    assert_eq!(3, liquid_stake_oracle_msgs(&mut deps, env.clone()).len());
+
+    // The failed target is recorded
+    reply(
+        deps.as_mut(),
+        env.clone(),
+        failed_oracle_reply_id(UPDATE_ORACLE_REPLY_ID - 2),
+    )
+    .unwrap();
+    assert_eq!(
+        Addr::unchecked(OSMO2),
+        oracle_update_failure(deps.as_ref()).unwrap().target
+    );
+
+    // Pushing the rates ignores the throttling
+    let res = execute(
+        deps.as_mut(),
+        env,
+        mock_info(OSMO3, &[]),
+        ExecuteMsg::PushRates {},
+    )
+    .unwrap();
+    assert_eq!(3, res.messages.len());
+}
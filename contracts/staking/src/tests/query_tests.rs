@@ -1,15 +1,21 @@
 use crate::contract::{execute, query};
-use crate::msg::{
-    BatchResponse, BatchesResponse, ConfigResponse, ExecuteMsg, QueryMsg, StateResponse,
+use crate::msg::{BatchResponse, BatchesResponse, ConfigResponse};
+use crate::msg::{ExecuteMsg, QueryMsg, StateResponse, UnallocatedBalanceResponse};
+use crate::query::query_admin;
+use crate::query::query_pending_batch;
+use crate::state::ibc::{IBCTransfer, PacketLifecycleStatus};
+use crate::state::CONFIG;
+use crate::state::{
+    save_inflight_packet, INSURANCE_FUND_BALANCE, STATE, TOTAL_CLAIMABLE, TOTAL_INSURANCE_COVERAGE,
 };
-use crate::query::{query_admin, query_pending_batch};
-use crate::state::{CONFIG, STATE};
 use crate::tests::test_helper::{
-    init, ADMIN, CELESTIA2, CELESTIAVAL1, CELESTIAVAL2, CHANNEL_ID, LIQUID_STAKE_TOKEN_DENOM,
-    NATIVE_TOKEN, OSMO2, OSMO3, OSMO4, STAKER_ADDRESS,
+    init, ADMIN, CHANNEL_ID, LIQUID_STAKE_TOKEN_DENOM, NATIVE_TOKEN, OSMO3, STAKER_ADDRESS,
 };
-use cosmwasm_std::testing::{mock_env, mock_info};
-use cosmwasm_std::{coins, from_json, Addr, Decimal, Uint128};
+use crate::tests::test_helper::{liquid_stake_token_denom, liquid_unstake};
+use crate::tests::test_helper::{CELESTIA2, CELESTIAVAL1, CELESTIAVAL2, OSMO2, OSMO4};
+use cosmwasm_std::testing::{mock_env, mock_info, MOCK_CONTRACT_ADDR};
+use cosmwasm_std::Addr;
+use cosmwasm_std::{coins, from_json, Coin, Decimal, Uint128};
 
 #[test]
 fn get_config() {
@@ -53,10 +59,7 @@ fn get_config() {
     );
 
     // other Config struct fields
-    assert_eq!(
-        result.liquid_stake_token_denom,
-        format!("factory/cosmos2contract/{}", LIQUID_STAKE_TOKEN_DENOM)
-    );
+    assert_eq!(result.liquid_stake_token_denom, liquid_stake_token_denom());
     assert_eq!(result.monitors, vec![OSMO2.to_string(), OSMO3.to_string()]);
     assert_eq!(result.batch_period, 86400);
     assert!(!result.stopped);
@@ -87,6 +90,7 @@ fn get_state() {
         mint_to: None,
         transfer_to_native_chain: None,
         expected_mint_amount: None,
+        native_sender: None,
     };
     let res = execute(deps.as_mut(), mock_env(), info, stake_msg);
     assert!(res.is_ok());
@@ -135,26 +139,11 @@ fn get_batch() {
     assert!(result.is_err()); //not found
 
     // unStake 1
-    let info = mock_info(
-        "bob",
-        &coins(
-            500,
-            format!("factory/cosmos2contract/{}", LIQUID_STAKE_TOKEN_DENOM),
-        ),
-    );
-    let unstake_msg = ExecuteMsg::LiquidUnstake {};
-    let res = execute(deps.as_mut(), mock_env(), info, unstake_msg.clone());
+    let res = liquid_unstake(deps.as_mut(), mock_env(), "bob", 500);
     assert!(res.is_ok());
 
     // unStake 2
-    let info = mock_info(
-        "alice",
-        &coins(
-            1500,
-            format!("factory/cosmos2contract/{}", LIQUID_STAKE_TOKEN_DENOM),
-        ),
-    );
-    let res = execute(deps.as_mut(), mock_env(), info, unstake_msg);
+    let res = liquid_unstake(deps.as_mut(), mock_env(), "alice", 1500);
     assert!(res.is_ok());
 
     let msg = QueryMsg::Batch { id: 1 };
@@ -202,15 +191,7 @@ fn get_batches() {
     }
 
     // unStake 1
-    let info = mock_info(
-        "bob",
-        &coins(
-            500,
-            format!("factory/cosmos2contract/{}", LIQUID_STAKE_TOKEN_DENOM),
-        ),
-    );
-    let unstake_msg = ExecuteMsg::LiquidUnstake {};
-    let res = execute(deps.as_mut(), env.clone(), info, unstake_msg.clone());
+    let res = liquid_unstake(deps.as_mut(), env.clone(), "bob", 500);
     assert!(res.is_ok());
 
     // submit batch
@@ -223,14 +204,7 @@ fn get_batches() {
     assert!(res.is_ok());
 
     // unStake 2 - for the next batch
-    let info = mock_info(
-        "alice",
-        &coins(
-            1500,
-            format!("factory/cosmos2contract/{}", LIQUID_STAKE_TOKEN_DENOM),
-        ),
-    );
-    let res = execute(deps.as_mut(), env.clone(), info, unstake_msg);
+    let res = liquid_unstake(deps.as_mut(), env.clone(), "alice", 1500);
     assert!(res.is_ok());
 
     // check the state
@@ -301,15 +275,7 @@ fn get_pending_batch() {
     state.total_native_token = Uint128::from(300_000u128);
     STATE.save(&mut deps.storage, &state).unwrap();
 
-    let info = mock_info(
-        "bob",
-        &coins(
-            1000,
-            format!("factory/cosmos2contract/{}", LIQUID_STAKE_TOKEN_DENOM),
-        ),
-    );
-    let msg = ExecuteMsg::LiquidUnstake {};
-    let _res = execute(deps.as_mut(), mock_env(), info.clone(), msg);
+    let _res = liquid_unstake(deps.as_mut(), mock_env(), "bob", 1000);
 
     env.block.time = env.block.time.plus_seconds(config.batch_period + 1);
     let submit_batch_msg = ExecuteMsg::SubmitBatch {};
@@ -334,3 +300,61 @@ fn get_admin() {
     let admin_response = query_admin(deps.as_ref()).unwrap();
     assert_eq!(ADMIN, admin_response.admin.unwrap().as_str())
 }
+
+#[test]
+fn get_unallocated_balance() {
+    let mut deps = init();
+    deps.querier
+        .update_balance(MOCK_CONTRACT_ADDR, coins(10_000, NATIVE_TOKEN));
+    let mut state = STATE.load(&deps.storage).unwrap();
+    state.total_fees = Uint128::new(100);
+    STATE.save(&mut deps.storage, &state).unwrap();
+    INSURANCE_FUND_BALANCE
+        .save(&mut deps.storage, &Uint128::new(200))
+        .unwrap();
+    TOTAL_INSURANCE_COVERAGE
+        .save(&mut deps.storage, &Uint128::new(50))
+        .unwrap();
+    TOTAL_CLAIMABLE
+        .save(&mut deps.storage, &Uint128::new(466))
+        .unwrap();
+
+    for (sequence, denom, status) in [
+        (1, NATIVE_TOKEN, PacketLifecycleStatus::TimedOut),
+        (2, NATIVE_TOKEN, PacketLifecycleStatus::Sent),
+        (
+            3,
+            LIQUID_STAKE_TOKEN_DENOM,
+            PacketLifecycleStatus::AckFailure,
+        ),
+    ] {
+        save_inflight_packet(
+            &mut deps.storage,
+            (CHANNEL_ID, sequence),
+            &IBCTransfer {
+                sequence,
+                amount: Coin::new(300, denom),
+                receiver: STAKER_ADDRESS.to_string(),
+                status,
+                route: None,
+                depositor: None,
+            },
+        )
+        .unwrap();
+    }
+
+    let res: UnallocatedBalanceResponse =
+        from_json(query(deps.as_ref(), mock_env(), QueryMsg::UnallocatedBalance {}).unwrap())
+            .unwrap();
+    assert_eq!(
+        UnallocatedBalanceResponse {
+            balance: Uint128::new(10_000),
+            total_fees: Uint128::new(100),
+            insurance_fund: Uint128::new(250),
+            claimable: Uint128::new(466),
+            failed_transfers: Uint128::new(300),
+            unallocated: Uint128::new(8_884),
+        },
+        res
+    );
+}
@@ -0,0 +1,228 @@
+use cosmwasm_std::testing::{mock_env, mock_info, MockApi, MockQuerier, MockStorage};
+use cosmwasm_std::{from_json, Addr, OwnedDeps, Uint128};
+use milky_way::staking::{Batch, BatchStatus};
+
+use crate::contract::{execute, query};
+use crate::error::ContractError;
+use crate::msg::{ExecuteMsg, QueryMsg, SlashHistoryResponse};
+use crate::state::{BATCHES, CONFIG, STATE};
+use crate::tests::test_helper::{init, ADMIN, CELESTIAVAL1};
+use crate::types::BatchSlashAmount;
+
+fn setup_submitted_batch(deps: &mut OwnedDeps<MockStorage, MockApi, MockQuerier>) {
+    let mut config = CONFIG.load(&deps.storage).unwrap();
+    config.stopped = true;
+    CONFIG.save(&mut deps.storage, &config).unwrap();
+
+    let mut state = STATE.load(&deps.storage).unwrap();
+    state.total_native_token = Uint128::new(100_000);
+    state.total_liquid_stake_token = Uint128::new(100_000);
+    STATE.save(&mut deps.storage, &state).unwrap();
+
+    let mut batch = Batch::new(2, Uint128::new(10_000), 1000);
+    batch.expected_native_unstaked = Some(Uint128::new(10_000));
+    batch.status = BatchStatus::Submitted;
+    BATCHES.save(&mut deps.storage, 2, &batch).unwrap();
+}
+
+#[test]
+fn only_admin_can_record_slash() {
+    let mut deps = init();
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("bob", &[]),
+        ExecuteMsg::RecordSlash {
+            validator: CELESTIAVAL1.to_string(),
+            amount: Uint128::new(100),
+            infraction_height: 10,
+            batches: vec![],
+        },
+    )
+    .unwrap_err();
+    assert!(matches!(err, ContractError::Admin { .. }));
+}
+
+#[test]
+fn record_slash_updates_state_and_batches() {
+    let mut deps = init();
+    setup_submitted_batch(&mut deps);
+
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(ADMIN, &[]),
+        ExecuteMsg::RecordSlash {
+            validator: CELESTIAVAL1.to_string(),
+            amount: Uint128::new(1_500),
+            infraction_height: 10,
+            batches: vec![BatchSlashAmount {
+                batch_id: 2,
+                amount: Uint128::new(500),
+            }],
+        },
+    )
+    .unwrap();
+
+    let state = STATE.load(&deps.storage).unwrap();
+    assert_eq!(Uint128::new(99_000), state.total_native_token);
+
+    let batch = BATCHES.load(&deps.storage, 2).unwrap();
+    assert_eq!(Uint128::new(9_500), batch.expected_native_unstaked.unwrap());
+
+    let res: SlashHistoryResponse = from_json(
+        query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::SlashHistory {
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(1, res.slashes.len());
+    let slash = &res.slashes[0];
+    assert_eq!(1, slash.id);
+    assert_eq!(Addr::unchecked(CELESTIAVAL1), slash.validator);
+    assert_eq!(Uint128::new(1_500), slash.amount);
+    assert_eq!(Uint128::new(1_000), slash.delegated_amount);
+    assert_eq!(10, slash.infraction_height);
+    assert_eq!(mock_env().block.time, slash.recorded_at);
+}
+
+#[test]
+fn record_slash_with_batches_exceeding_amount_fails() {
+    let mut deps = init();
+    setup_submitted_batch(&mut deps);
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(ADMIN, &[]),
+        ExecuteMsg::RecordSlash {
+            validator: CELESTIAVAL1.to_string(),
+            amount: Uint128::new(100),
+            infraction_height: 10,
+            batches: vec![BatchSlashAmount {
+                batch_id: 2,
+                amount: Uint128::new(500),
+            }],
+        },
+    )
+    .unwrap_err();
+    assert!(matches!(err, ContractError::SlashExceedsAvailable { .. }));
+}
+
+#[test]
+fn record_slash_on_pending_batch_fails() {
+    let mut deps = init();
+    setup_submitted_batch(&mut deps);
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(ADMIN, &[]),
+        ExecuteMsg::RecordSlash {
+            validator: CELESTIAVAL1.to_string(),
+            amount: Uint128::new(100),
+            infraction_height: 10,
+            batches: vec![BatchSlashAmount {
+                batch_id: 1,
+                amount: Uint128::new(100),
+            }],
+        },
+    )
+    .unwrap_err();
+    assert!(matches!(err, ContractError::UnexpecedBatchStatus { .. }));
+}
+
+#[test]
+fn slash_history_is_paginated() {
+    let mut deps = init();
+    setup_submitted_batch(&mut deps);
+
+    for height in 1..=3 {
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(ADMIN, &[]),
+            ExecuteMsg::RecordSlash {
+                validator: CELESTIAVAL1.to_string(),
+                amount: Uint128::new(10),
+                infraction_height: height,
+                batches: vec![],
+            },
+        )
+        .unwrap();
+    }
+
+    let res: SlashHistoryResponse = from_json(
+        query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::SlashHistory {
+                start_after: Some(1),
+                limit: Some(1),
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(1, res.slashes.len());
+    assert_eq!(2, res.slashes[0].id);
+    assert_eq!(2, res.slashes[0].infraction_height);
+}
+
+#[test]
+fn record_slash_requires_stopped_contract() {
+    let mut deps = init();
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(ADMIN, &[]),
+        ExecuteMsg::RecordSlash {
+            validator: CELESTIAVAL1.to_string(),
+            amount: Uint128::new(100),
+            infraction_height: 10,
+            batches: vec![],
+        },
+    )
+    .unwrap_err();
+    assert!(matches!(err, ContractError::NotStopped {}));
+}
+
+#[test]
+fn record_slash_with_duplicate_batches_fails() {
+    let mut deps = init();
+    setup_submitted_batch(&mut deps);
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(ADMIN, &[]),
+        ExecuteMsg::RecordSlash {
+            validator: CELESTIAVAL1.to_string(),
+            amount: Uint128::new(1_000),
+            infraction_height: 10,
+            batches: vec![
+                BatchSlashAmount {
+                    batch_id: 2,
+                    amount: Uint128::new(500),
+                },
+                BatchSlashAmount {
+                    batch_id: 2,
+                    amount: Uint128::new(500),
+                },
+            ],
+        },
+    )
+    .unwrap_err();
+    assert!(matches!(
+        err,
+        ContractError::DuplicateSlashBatch { batch_id: 2 }
+    ));
+}
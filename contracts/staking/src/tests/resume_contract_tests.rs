@@ -0,0 +1,256 @@
+use cosmwasm_std::testing::{mock_env, mock_info, MockApi, MockQuerier, MockStorage};
+use cosmwasm_std::{Decimal, OwnedDeps, Uint128};
+use milky_way::staking::{Batch, BatchStatus};
+
+use crate::contract::execute;
+use crate::error::ContractError;
+use crate::msg::ExecuteMsg;
+use crate::state::{ResumeContractLimits, BATCHES, CONFIG, STATE, STOPPED_REDEMPTION_RATE};
+use crate::tests::test_helper::{init, ADMIN, OSMO2};
+
+fn init_with_limits() -> OwnedDeps<MockStorage, MockApi, MockQuerier> {
+    let mut deps = init();
+
+    let mut config = CONFIG.load(&deps.storage).unwrap();
+    config.resume_contract_limits = Some(ResumeContractLimits {
+        max_total_native_token_deviation: Decimal::percent(10),
+        max_total_liquid_stake_token_deviation: Decimal::percent(10),
+        max_total_reward_amount_deviation: Decimal::percent(10),
+        max_redemption_rate_deviation: Decimal::percent(5),
+    });
+    CONFIG.save(&mut deps.storage, &config).unwrap();
+
+    let mut state = STATE.load(&deps.storage).unwrap();
+    state.total_native_token = Uint128::new(200_000);
+    state.total_liquid_stake_token = Uint128::new(100_000);
+    state.total_reward_amount = Uint128::new(10_000);
+    STATE.save(&mut deps.storage, &state).unwrap();
+
+    // Stop the contract so that the redemption rate is recorded
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(OSMO2, &[]),
+        ExecuteMsg::CircuitBreaker {},
+    )
+    .unwrap();
+
+    deps
+}
+
+fn resume_msg(
+    total_native_token: u128,
+    total_liquid_stake_token: u128,
+    force: Option<bool>,
+) -> ExecuteMsg {
+    ExecuteMsg::ResumeContract {
+        total_native_token: Uint128::new(total_native_token),
+        total_liquid_stake_token: Uint128::new(total_liquid_stake_token),
+        total_reward_amount: Uint128::new(10_000),
+        force,
+    }
+}
+
+#[test]
+fn circuit_breaker_records_redemption_rate() {
+    let deps = init_with_limits();
+
+    let rate = STOPPED_REDEMPTION_RATE.load(&deps.storage).unwrap();
+    assert_eq!(Decimal::from_ratio(2u128, 1u128), rate);
+}
+
+#[test]
+fn resume_within_limits() {
+    let mut deps = init_with_limits();
+
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(ADMIN, &[]),
+        resume_msg(190_000, 96_000, None),
+    )
+    .unwrap();
+    assert_eq!(1, res.events.len());
+    assert_eq!("milkyway-resume_contract", res.events[0].ty);
+
+    let state = STATE.load(&deps.storage).unwrap();
+    assert_eq!(Uint128::new(190_000), state.total_native_token);
+    assert_eq!(Uint128::new(96_000), state.total_liquid_stake_token);
+    assert!(!CONFIG.load(&deps.storage).unwrap().stopped);
+    assert!(STOPPED_REDEMPTION_RATE
+        .may_load(&deps.storage)
+        .unwrap()
+        .is_none());
+}
+
+#[test]
+fn resume_with_total_out_of_bounds_fails() {
+    let mut deps = init_with_limits();
+
+    // total_native_token deviates by 50%
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(ADMIN, &[]),
+        resume_msg(100_000, 50_000, None),
+    )
+    .unwrap_err();
+    assert!(matches!(
+        err,
+        ContractError::ResumeContractLimitExceeded { field, .. } if field == "total_native_token"
+    ));
+    assert!(CONFIG.load(&deps.storage).unwrap().stopped);
+}
+
+#[test]
+fn resume_with_redemption_rate_out_of_bounds_fails() {
+    let mut deps = init_with_limits();
+
+    // Totals are within bounds but the rate moves from 2 to ~2.2
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(ADMIN, &[]),
+        resume_msg(210_000, 95_000, None),
+    )
+    .unwrap_err();
+    assert!(matches!(
+        err,
+        ContractError::ResumeContractLimitExceeded { field, .. } if field == "redemption_rate"
+    ));
+}
+
+#[test]
+fn resume_with_last_batch_rate_out_of_bounds_fails() {
+    let mut deps = init_with_limits();
+
+    // Received batch with a redemption rate of 1.5
+    let mut batch = Batch::new(1, Uint128::new(1_000), 0);
+    batch.expected_native_unstaked = Some(Uint128::new(1_500));
+    batch.received_native_unstaked = Some(Uint128::new(1_500));
+    batch.status = BatchStatus::Received;
+    BATCHES.save(&mut deps.storage, 1, &batch).unwrap();
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(ADMIN, &[]),
+        resume_msg(200_000, 100_000, None),
+    )
+    .unwrap_err();
+    assert!(matches!(
+        err,
+        ContractError::ResumeContractLimitExceeded { field, .. } if field == "last_batch_redemption_rate"
+    ));
+}
+
+#[test]
+fn forced_resume_emits_exceeded_limits() {
+    let mut deps = init_with_limits();
+
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(ADMIN, &[]),
+        resume_msg(100_000, 100_000, Some(true)),
+    )
+    .unwrap();
+
+    // total_native_token and redemption rate are out of bounds
+    assert_eq!(3, res.events.len());
+    assert!(res.events[0]
+        .attributes
+        .iter()
+        .any(|a| a.key == "forced" && a.value == "true"));
+    assert_eq!("milkyway-resume_contract_limit_exceeded", res.events[1].ty);
+    assert!(res.events[1]
+        .attributes
+        .iter()
+        .any(|a| a.key == "field" && a.value == "total_native_token"));
+    assert!(res.events[2]
+        .attributes
+        .iter()
+        .any(|a| a.key == "field" && a.value == "redemption_rate"));
+    assert!(res
+        .attributes
+        .iter()
+        .any(|a| a.key == "forced" && a.value == "true"));
+
+    let state = STATE.load(&deps.storage).unwrap();
+    assert_eq!(Uint128::new(100_000), state.total_native_token);
+    assert!(!CONFIG.load(&deps.storage).unwrap().stopped);
+}
+
+#[test]
+fn update_and_clear_resume_contract_limits() {
+    let mut deps = init_with_limits();
+    let update_msg =
+        |limits: Option<ResumeContractLimits>, clear: Option<bool>| ExecuteMsg::UpdateConfig {
+            native_chain_config: None,
+            protocol_chain_config: None,
+            protocol_fee_config: None,
+            monitors: None,
+            batch_period: None,
+            resume_contract_limits: limits,
+            clear_resume_contract_limits: clear,
+            batch_limits: None,
+        };
+    let limits = CONFIG
+        .load(&deps.storage)
+        .unwrap()
+        .resume_contract_limits
+        .unwrap();
+
+    // A zero deviation would prevent resuming without force
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(ADMIN, &[]),
+        update_msg(
+            Some(ResumeContractLimits {
+                max_redemption_rate_deviation: Decimal::zero(),
+                ..limits.clone()
+            }),
+            None,
+        ),
+    )
+    .unwrap_err();
+    assert!(matches!(
+        err,
+        ContractError::InvalidResumeContractLimits { .. }
+    ));
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(ADMIN, &[]),
+        update_msg(Some(limits), Some(true)),
+    )
+    .unwrap_err();
+    assert!(matches!(
+        err,
+        ContractError::InvalidResumeContractLimits { .. }
+    ));
+
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(ADMIN, &[]),
+        update_msg(None, Some(true)),
+    )
+    .unwrap();
+    assert!(CONFIG
+        .load(&deps.storage)
+        .unwrap()
+        .resume_contract_limits
+        .is_none());
+
+    // Without limits any value is accepted
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(ADMIN, &[]),
+        resume_msg(400_000, 100_000, None),
+    )
+    .unwrap();
+}
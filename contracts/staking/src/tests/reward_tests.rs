@@ -1,3 +1,4 @@
+use crate::chain::{Backend, Chain, IbcTransferMsg};
 use crate::contract::{execute, IBC_TIMEOUT};
 use crate::error::ContractError;
 use crate::helpers::derive_intermediate_sender;
@@ -6,8 +7,7 @@ use crate::state::{CONFIG, STATE};
 use crate::tests::test_helper::{init, CHANNEL_ID, NATIVE_TOKEN, STAKER_ADDRESS};
 
 use cosmwasm_std::testing::{mock_env, mock_info};
-use cosmwasm_std::{Addr, CosmosMsg, ReplyOn, Uint128};
-use osmosis_std::types::ibc::applications::transfer::v1::MsgTransfer;
+use cosmwasm_std::{Addr, Coin, ReplyOn, Uint128};
 
 #[test]
 fn receive_rewards() {
@@ -58,19 +58,15 @@ fn receive_rewards() {
     assert_eq!(res.messages[1].reply_on, ReplyOn::Always);
     assert_eq!(
         res.messages[1].msg,
-        CosmosMsg::from(MsgTransfer {
+        Backend::ibc_transfer(IbcTransferMsg {
             source_channel: CHANNEL_ID.to_string(),
-            source_port: "transfer".to_string(),
+            token: Coin::new(90, NATIVE_TOKEN.to_string()),
             sender: env.contract.address.to_string(),
             receiver: Addr::unchecked(STAKER_ADDRESS).to_string(),
-            token: Some(osmosis_std::types::cosmos::base::v1beta1::Coin {
-                denom: NATIVE_TOKEN.to_string(),
-                amount: "90".to_string(),
-            }),
-            timeout_height: None,
             timeout_timestamp: env.block.time.nanos() + IBC_TIMEOUT.nanos(),
             memo: format!("{{\"ibc_callback\":\"{}\"}}", env.contract.address),
         })
+        .unwrap()
     );
 
     let state = STATE.load(&deps.storage).unwrap();
@@ -115,30 +111,28 @@ fn receive_rewards_and_send_fees_to_treasury() {
     assert_eq!(res.messages[1].reply_on, ReplyOn::Always);
     assert_eq!(
         res.messages[1].msg,
-        CosmosMsg::from(MsgTransfer {
+        Backend::ibc_transfer(IbcTransferMsg {
             source_channel: CHANNEL_ID.to_string(),
-            source_port: "transfer".to_string(),
+            token: Coin::new(90, NATIVE_TOKEN.to_string()),
             sender: env.contract.address.to_string(),
             receiver: Addr::unchecked(STAKER_ADDRESS).to_string(),
-            token: Some(osmosis_std::types::cosmos::base::v1beta1::Coin {
-                denom: NATIVE_TOKEN.to_string(),
-                amount: "90".to_string(),
-            }),
-            timeout_height: None,
             timeout_timestamp: env.block.time.nanos() + IBC_TIMEOUT.nanos(),
             memo: format!("{{\"ibc_callback\":\"{}\"}}", env.contract.address),
         })
+        .unwrap()
     );
     assert_eq!(
         res.messages[2].msg,
-        CosmosMsg::from(cosmwasm_std::BankMsg::Send {
-            to_address: config
+        Backend::bank_send(
+            env.contract.address.to_string(),
+            config
                 .protocol_fee_config
                 .treasury_address
                 .unwrap()
                 .to_string(),
-            amount: vec![cosmwasm_std::Coin::new(10u128, NATIVE_TOKEN)],
-        })
+            vec![cosmwasm_std::Coin::new(10u128, NATIVE_TOKEN)],
+        )
+        .unwrap()
     );
 
     let state = STATE.load(&deps.storage).unwrap();
@@ -183,3 +177,47 @@ fn receive_rewards_with_zero_fees_fails() {
         _ => false,
     });
 }
+
+#[test]
+fn receive_rewards_from_unauthenticated_sender() {
+    let mut deps = init();
+    let env = mock_env();
+
+    let mut state = STATE.load(&deps.storage).unwrap();
+    let mut config = CONFIG.load(&deps.storage).unwrap();
+
+    state.total_liquid_stake_token = Uint128::from(100_000u128);
+    state.total_native_token = Uint128::from(100_000u128);
+    STATE.save(&mut deps.storage, &state).unwrap();
+
+    config.protocol_chain_config.authenticate_ibc_hooks_sender = Some(true);
+    CONFIG.save(&mut deps.storage, &config).unwrap();
+
+    let funds = [cosmwasm_std::Coin {
+        amount: Uint128::from(1000u128),
+        denom: config.protocol_chain_config.ibc_token_denom.clone(),
+    }];
+
+    let err = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("bob", &funds),
+        ExecuteMsg::ReceiveRewards {},
+    )
+    .unwrap_err();
+    assert!(matches!(err, ContractError::Unauthorized { .. }));
+
+    let sender = derive_intermediate_sender(
+        &config.protocol_chain_config.ibc_channel_id,
+        config.native_chain_config.reward_collector_address.as_str(),
+        config.protocol_chain_config.account_address_prefix.as_str(),
+    )
+    .unwrap();
+    execute(
+        deps.as_mut(),
+        env,
+        mock_info(&sender, &funds),
+        ExecuteMsg::ReceiveRewards {},
+    )
+    .unwrap();
+}
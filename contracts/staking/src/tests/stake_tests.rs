@@ -1,22 +1,27 @@
-use crate::contract::{execute, reply, IBC_TIMEOUT};
+use crate::chain::{Backend, Chain, IbcTransferMsg};
+use crate::contract::execute;
+use crate::contract::{reply, IBC_TIMEOUT};
 use crate::error::ContractError;
-use crate::helpers::{derive_intermediate_sender, get_rates};
+use crate::helpers::derive_intermediate_sender;
+use crate::helpers::get_rates;
 use crate::msg::ExecuteMsg;
-use crate::state::{State, BATCHES, CONFIG, STATE};
-use crate::tests::test_helper::{
-    init, CELESTIA1, CELESTIA2, CHANNEL_ID, LIQUID_STAKE_TOKEN_DENOM, NATIVE_TOKEN, OSMO3,
-    STAKER_ADDRESS,
-};
-use cosmwasm_std::testing::{mock_env, mock_info, MOCK_CONTRACT_ADDR};
+use crate::state::BATCHES;
+#[cfg(not(feature = "cw20"))]
+use crate::state::IBC_WAITING_FOR_REPLY;
+use crate::state::{State, CONFIG, STATE};
+#[cfg(not(feature = "cw20"))]
+use crate::tests::test_helper::{decode_ibc_transfer, CELESTIA2};
+use crate::tests::test_helper::{init, CELESTIA1, CHANNEL_ID, NATIVE_TOKEN, OSMO3};
+use crate::tests::test_helper::{liquid_stake_token_denom, STAKER_ADDRESS};
+use crate::tokenfactory;
+use cosmwasm_std::testing::MOCK_CONTRACT_ADDR;
+use cosmwasm_std::testing::{mock_env, mock_info};
 use cosmwasm_std::{
-    attr, coins, Addr, CosmosMsg, Decimal, IbcTimeout, Order, Reply, ReplyOn, StdError, SubMsg,
-    SubMsgResponse, SubMsgResult, Timestamp, Uint128,
+    attr, Addr, Coin, Decimal, IbcTimeout, Order, Reply, ReplyOn, StdError, SubMsg, SubMsgResponse,
+    SubMsgResult, Timestamp,
 };
+use cosmwasm_std::{coins, Uint128};
 use milky_way::staking::BatchStatus;
-use osmosis_std::types::cosmos::bank::v1beta1::MsgSend;
-use osmosis_std::types::cosmos::base::v1beta1::Coin;
-use osmosis_std::types::ibc::applications::transfer::v1::MsgTransfer;
-use osmosis_std::types::osmosis::tokenfactory::v1beta1::MsgMint;
 use std::vec::Vec;
 
 #[test]
@@ -28,6 +33,7 @@ fn proper_liquid_stake() {
         mint_to: None,
         transfer_to_native_chain: None,
         expected_mint_amount: None,
+        native_sender: None,
     };
     let res = execute(deps.as_mut(), mock_env(), info, msg.clone());
 
@@ -35,10 +41,7 @@ fn proper_liquid_stake() {
         env.block.time.nanos() + IBC_TIMEOUT.nanos(),
     ));
 
-    let ibc_coin = Coin {
-        denom: NATIVE_TOKEN.to_string(),
-        amount: "1000".to_string(),
-    };
+    let ibc_coin = Coin::new(1000, NATIVE_TOKEN);
 
     let ibc_sub_msg_id = env.block.time.nanos() + env.transaction.unwrap().index as u64;
     match res {
@@ -59,14 +62,12 @@ fn proper_liquid_stake() {
                 result.messages[0],
                 SubMsg {
                     id: 0,
-                    msg: <MsgMint as Into<CosmosMsg>>::into(MsgMint {
-                        sender: MOCK_CONTRACT_ADDR.to_string(),
-                        amount: Some(Coin {
-                            denom: format!("factory/cosmos2contract/{}", LIQUID_STAKE_TOKEN_DENOM),
-                            amount: "1000".to_string(),
-                        }),
-                        mint_to_address: MOCK_CONTRACT_ADDR.to_string(),
-                    }),
+                    msg: tokenfactory::mint(
+                        MOCK_CONTRACT_ADDR.to_string(),
+                        Coin::new(1000, liquid_stake_token_denom()),
+                        MOCK_CONTRACT_ADDR.to_string()
+                    )
+                    .unwrap(),
                     gas_limit: None,
                     reply_on: ReplyOn::Never,
                 }
@@ -78,16 +79,15 @@ fn proper_liquid_stake() {
                 result.messages[2],
                 SubMsg {
                     id: ibc_sub_msg_id,
-                    msg: <MsgTransfer as Into<CosmosMsg>>::into(MsgTransfer {
+                    msg: Backend::ibc_transfer(IbcTransferMsg {
                         source_channel: CHANNEL_ID.to_string(),
-                        source_port: "transfer".to_string(),
+                        token: ibc_coin,
                         sender: env.contract.address.to_string(),
                         receiver: Addr::unchecked(STAKER_ADDRESS).to_string(),
-                        token: Some(ibc_coin),
-                        timeout_height: None,
                         timeout_timestamp: timeout.timestamp().unwrap().nanos(),
                         memo: format!("{{\"ibc_callback\":\"{}\"}}", env.contract.address),
-                    }),
+                    })
+                    .unwrap(),
                     gas_limit: None,
                     reply_on: ReplyOn::Always,
                 }
@@ -99,14 +99,12 @@ fn proper_liquid_stake() {
                 result.messages[3],
                 SubMsg {
                     id: 0,
-                    msg: <MsgSend as Into<CosmosMsg>>::into(MsgSend {
-                        from_address: Addr::unchecked(MOCK_CONTRACT_ADDR).to_string(),
-                        to_address: OSMO3.to_string(),
-                        amount: vec![Coin {
-                            denom: format!("factory/cosmos2contract/{}", LIQUID_STAKE_TOKEN_DENOM),
-                            amount: "1000".to_string(),
-                        }],
-                    }),
+                    msg: tokenfactory::send(
+                        Addr::unchecked(MOCK_CONTRACT_ADDR).to_string(),
+                        Coin::new(1000, liquid_stake_token_denom()),
+                        OSMO3.to_string()
+                    )
+                    .unwrap(),
                     gas_limit: None,
                     reply_on: ReplyOn::Never,
                 }
@@ -203,6 +201,7 @@ fn proper_liquid_stake() {
 }
 
 #[test]
+#[cfg(not(feature = "cw20"))]
 fn proper_liquid_stake_with_ibc_transfer() {
     let mut deps = init();
     let env = mock_env();
@@ -211,6 +210,7 @@ fn proper_liquid_stake_with_ibc_transfer() {
         mint_to: Some(CELESTIA2.to_string()),
         transfer_to_native_chain: None,
         expected_mint_amount: None,
+        native_sender: None,
     };
     let res = execute(deps.as_mut(), mock_env(), info, msg.clone());
 
@@ -218,10 +218,7 @@ fn proper_liquid_stake_with_ibc_transfer() {
         env.block.time.nanos() + IBC_TIMEOUT.nanos(),
     ));
 
-    let ibc_coin = Coin {
-        denom: NATIVE_TOKEN.to_string(),
-        amount: "1000".to_string(),
-    };
+    let ibc_coin = Coin::new(1000, NATIVE_TOKEN);
 
     let ibc_sub_msg_id = env.block.time.nanos() + env.transaction.unwrap().index as u64;
     match res {
@@ -242,14 +239,12 @@ fn proper_liquid_stake_with_ibc_transfer() {
                 result.messages[0],
                 SubMsg {
                     id: 0,
-                    msg: <MsgMint as Into<CosmosMsg>>::into(MsgMint {
-                        sender: MOCK_CONTRACT_ADDR.to_string(),
-                        amount: Some(Coin {
-                            denom: format!("factory/cosmos2contract/{}", LIQUID_STAKE_TOKEN_DENOM),
-                            amount: "1000".to_string(),
-                        }),
-                        mint_to_address: MOCK_CONTRACT_ADDR.to_string(),
-                    }),
+                    msg: tokenfactory::mint(
+                        MOCK_CONTRACT_ADDR.to_string(),
+                        Coin::new(1000, liquid_stake_token_denom()),
+                        MOCK_CONTRACT_ADDR.to_string()
+                    )
+                    .unwrap(),
                     gas_limit: None,
                     reply_on: ReplyOn::Never,
                 }
@@ -261,16 +256,15 @@ fn proper_liquid_stake_with_ibc_transfer() {
                 result.messages[2],
                 SubMsg {
                     id: ibc_sub_msg_id,
-                    msg: <MsgTransfer as Into<CosmosMsg>>::into(MsgTransfer {
+                    msg: Backend::ibc_transfer(IbcTransferMsg {
                         source_channel: CHANNEL_ID.to_string(),
-                        source_port: "transfer".to_string(),
+                        token: ibc_coin,
                         sender: env.contract.address.to_string(),
                         receiver: Addr::unchecked(STAKER_ADDRESS).to_string(),
-                        token: Some(ibc_coin),
-                        timeout_height: None,
                         timeout_timestamp: timeout.timestamp().unwrap().nanos(),
                         memo: format!("{{\"ibc_callback\":\"{}\"}}", env.contract.address),
-                    }),
+                    })
+                    .unwrap(),
                     gas_limit: None,
                     reply_on: ReplyOn::Always,
                 }
@@ -282,19 +276,15 @@ fn proper_liquid_stake_with_ibc_transfer() {
                 result.messages[3],
                 SubMsg {
                     id: ibc_sub_msg_id + 1,
-                    msg: <MsgTransfer as Into<CosmosMsg>>::into(MsgTransfer {
+                    msg: Backend::ibc_transfer(IbcTransferMsg {
                         source_channel: CHANNEL_ID.to_string(),
-                        source_port: "transfer".to_string(),
+                        token: Coin::new(1000, liquid_stake_token_denom()),
                         sender: env.contract.address.to_string(),
                         receiver: CELESTIA2.to_string(),
-                        token: Some(Coin {
-                            amount: "1000".to_string(),
-                            denom: format!("factory/cosmos2contract/{}", LIQUID_STAKE_TOKEN_DENOM),
-                        }),
-                        timeout_height: None,
                         timeout_timestamp: timeout.timestamp().unwrap().nanos(),
                         memo: format!("{{\"ibc_callback\":\"{}\"}}", env.contract.address),
-                    }),
+                    })
+                    .unwrap(),
                     gas_limit: None,
                     reply_on: ReplyOn::Always,
                 }
@@ -403,6 +393,38 @@ fn proper_liquid_stake_with_ibc_transfer() {
     );
 }
 
+#[test]
+fn liquid_stake_emits_milkyway_event() {
+    let mut deps = init();
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(OSMO3, &coins(1000, NATIVE_TOKEN)),
+        ExecuteMsg::LiquidStake {
+            mint_to: None,
+            transfer_to_native_chain: None,
+            expected_mint_amount: None,
+            native_sender: None,
+        },
+    )
+    .unwrap();
+
+    assert_eq!(1, res.events.len());
+    assert_eq!("milkyway-liquid_stake", res.events[0].ty);
+    assert_eq!(
+        vec![
+            attr("sender", OSMO3),
+            attr("mint_to", OSMO3),
+            attr("denom", NATIVE_TOKEN),
+            attr("amount", "1000"),
+            attr("mint_denom", liquid_stake_token_denom()),
+            attr("mint_amount", "1000"),
+            attr("redemption_rate", "1"),
+        ],
+        res.events[0].attributes
+    );
+}
+
 #[test]
 fn liquid_stake_less_than_minimum() {
     let mut deps = init();
@@ -411,6 +433,7 @@ fn liquid_stake_less_than_minimum() {
         mint_to: None,
         transfer_to_native_chain: None,
         expected_mint_amount: None,
+        native_sender: None,
     };
 
     let res = execute(deps.as_mut(), mock_env(), info.clone(), msg);
@@ -441,6 +464,7 @@ fn proper_ibc_liquid_stake() {
         mint_to: Some(OSMO3.to_string()),
         transfer_to_native_chain: None,
         expected_mint_amount: None,
+        native_sender: None,
     };
 
     let res = execute(deps.as_mut(), mock_env(), info.clone(), msg);
@@ -486,6 +510,7 @@ fn mint_amount_divergence() {
         mint_to: None,
         transfer_to_native_chain: None,
         expected_mint_amount: Some(Uint128::from(2_000_000u128)),
+        native_sender: None,
     };
     let res: Result<cosmwasm_std::Response, ContractError> =
         execute(deps.as_mut(), mock_env(), info.clone(), msg.clone());
@@ -495,6 +520,7 @@ fn mint_amount_divergence() {
         mint_to: None,
         transfer_to_native_chain: None,
         expected_mint_amount: Some(Uint128::from(1_000_000u128)),
+        native_sender: None,
     };
     let res = execute(deps.as_mut(), mock_env(), info.clone(), msg.clone());
     if res.is_err() {
@@ -518,6 +544,7 @@ fn zero_liquid_stake_but_native_tokens() {
         mint_to: None,
         transfer_to_native_chain: None,
         expected_mint_amount: None,
+        native_sender: None,
     };
     let res = execute(deps.as_mut(), mock_env(), info, msg.clone());
     assert!(res.is_ok());
@@ -547,6 +574,7 @@ fn transfer_to_native_chain_false_is_handle_correctly() {
         mint_to: None,
         transfer_to_native_chain: None,
         expected_mint_amount: None,
+        native_sender: None,
     };
     let res = execute(deps.as_mut(), env.clone(), info.clone(), msg);
 
@@ -554,10 +582,7 @@ fn transfer_to_native_chain_false_is_handle_correctly() {
         env.block.time.nanos() + IBC_TIMEOUT.nanos(),
     ));
 
-    let ibc_coin = Coin {
-        denom: NATIVE_TOKEN.to_string(),
-        amount: "1000".to_string(),
-    };
+    let ibc_coin = Coin::new(1000, NATIVE_TOKEN);
 
     let ibc_sub_msg_id = env.block.time.nanos() + env.transaction.unwrap().index as u64;
     match res {
@@ -578,14 +603,12 @@ fn transfer_to_native_chain_false_is_handle_correctly() {
                 result.messages[0],
                 SubMsg {
                     id: 0,
-                    msg: <MsgMint as Into<CosmosMsg>>::into(MsgMint {
-                        sender: MOCK_CONTRACT_ADDR.to_string(),
-                        amount: Some(Coin {
-                            denom: format!("factory/cosmos2contract/{}", LIQUID_STAKE_TOKEN_DENOM),
-                            amount: "1000".to_string(),
-                        }),
-                        mint_to_address: MOCK_CONTRACT_ADDR.to_string(),
-                    }),
+                    msg: tokenfactory::mint(
+                        MOCK_CONTRACT_ADDR.to_string(),
+                        Coin::new(1000, liquid_stake_token_denom()),
+                        MOCK_CONTRACT_ADDR.to_string()
+                    )
+                    .unwrap(),
                     gas_limit: None,
                     reply_on: ReplyOn::Never,
                 }
@@ -597,16 +620,15 @@ fn transfer_to_native_chain_false_is_handle_correctly() {
                 result.messages[2],
                 SubMsg {
                     id: ibc_sub_msg_id.clone(),
-                    msg: <MsgTransfer as Into<CosmosMsg>>::into(MsgTransfer {
+                    msg: Backend::ibc_transfer(IbcTransferMsg {
                         source_channel: CHANNEL_ID.to_string(),
-                        source_port: "transfer".to_string(),
+                        token: ibc_coin,
                         sender: env.contract.address.to_string(),
                         receiver: Addr::unchecked(STAKER_ADDRESS).to_string(),
-                        token: Some(ibc_coin),
-                        timeout_height: None,
                         timeout_timestamp: timeout.timestamp().unwrap().nanos(),
                         memo: format!("{{\"ibc_callback\":\"{}\"}}", env.contract.address),
-                    }),
+                    })
+                    .unwrap(),
                     gas_limit: None,
                     reply_on: ReplyOn::Always,
                 }
@@ -618,14 +640,12 @@ fn transfer_to_native_chain_false_is_handle_correctly() {
                 result.messages[3],
                 SubMsg {
                     id: 0,
-                    msg: <MsgSend as Into<CosmosMsg>>::into(MsgSend {
-                        from_address: Addr::unchecked(MOCK_CONTRACT_ADDR).to_string(),
-                        to_address: OSMO3.to_string(),
-                        amount: vec![Coin {
-                            denom: format!("factory/cosmos2contract/{}", LIQUID_STAKE_TOKEN_DENOM),
-                            amount: "1000".to_string(),
-                        }],
-                    }),
+                    msg: tokenfactory::send(
+                        Addr::unchecked(MOCK_CONTRACT_ADDR).to_string(),
+                        Coin::new(1000, liquid_stake_token_denom()),
+                        OSMO3.to_string()
+                    )
+                    .unwrap(),
                     gas_limit: None,
                     reply_on: ReplyOn::Never,
                 }
@@ -638,6 +658,7 @@ fn transfer_to_native_chain_false_is_handle_correctly() {
 }
 
 #[test]
+#[cfg(not(feature = "cw20"))]
 fn transfer_to_native_chain_true_is_handle_correctly() {
     let mut deps = init();
     let env = mock_env();
@@ -656,6 +677,7 @@ fn transfer_to_native_chain_true_is_handle_correctly() {
         mint_to: None,
         transfer_to_native_chain: Some(true),
         expected_mint_amount: None,
+        native_sender: None,
     };
     let res = execute(deps.as_mut(), env.clone(), info, msg);
 
@@ -663,10 +685,7 @@ fn transfer_to_native_chain_true_is_handle_correctly() {
         env.block.time.nanos() + IBC_TIMEOUT.nanos(),
     ));
 
-    let ibc_coin = Coin {
-        denom: NATIVE_TOKEN.to_string(),
-        amount: "1000".to_string(),
-    };
+    let ibc_coin = Coin::new(1000, NATIVE_TOKEN);
 
     let ibc_sub_msg_id = env.block.time.nanos() + env.transaction.unwrap().index as u64;
     match res {
@@ -687,14 +706,12 @@ fn transfer_to_native_chain_true_is_handle_correctly() {
                 result.messages[0],
                 SubMsg {
                     id: 0,
-                    msg: <MsgMint as Into<CosmosMsg>>::into(MsgMint {
-                        sender: MOCK_CONTRACT_ADDR.to_string(),
-                        amount: Some(Coin {
-                            denom: format!("factory/cosmos2contract/{}", LIQUID_STAKE_TOKEN_DENOM),
-                            amount: "1000".to_string(),
-                        }),
-                        mint_to_address: MOCK_CONTRACT_ADDR.to_string(),
-                    }),
+                    msg: tokenfactory::mint(
+                        MOCK_CONTRACT_ADDR.to_string(),
+                        Coin::new(1000, liquid_stake_token_denom()),
+                        MOCK_CONTRACT_ADDR.to_string()
+                    )
+                    .unwrap(),
                     gas_limit: None,
                     reply_on: ReplyOn::Never,
                 }
@@ -706,16 +723,15 @@ fn transfer_to_native_chain_true_is_handle_correctly() {
                 result.messages[2],
                 SubMsg {
                     id: ibc_sub_msg_id,
-                    msg: <MsgTransfer as Into<CosmosMsg>>::into(MsgTransfer {
+                    msg: Backend::ibc_transfer(IbcTransferMsg {
                         source_channel: CHANNEL_ID.to_string(),
-                        source_port: "transfer".to_string(),
+                        token: ibc_coin,
                         sender: env.contract.address.to_string(),
                         receiver: Addr::unchecked(STAKER_ADDRESS).to_string(),
-                        token: Some(ibc_coin),
-                        timeout_height: None,
                         timeout_timestamp: timeout.timestamp().unwrap().nanos(),
                         memo: format!("{{\"ibc_callback\":\"{}\"}}", env.contract.address),
-                    }),
+                    })
+                    .unwrap(),
                     gas_limit: None,
                     reply_on: ReplyOn::Always,
                 }
@@ -727,19 +743,15 @@ fn transfer_to_native_chain_true_is_handle_correctly() {
                 result.messages[3],
                 SubMsg {
                     id: ibc_sub_msg_id + 1,
-                    msg: <MsgTransfer as Into<CosmosMsg>>::into(MsgTransfer {
+                    msg: Backend::ibc_transfer(IbcTransferMsg {
                         source_channel: CHANNEL_ID.to_string(),
-                        source_port: "transfer".to_string(),
-                        token: Some(Coin {
-                            denom: format!("factory/cosmos2contract/{}", LIQUID_STAKE_TOKEN_DENOM),
-                            amount: "1000".to_string(),
-                        }),
+                        token: Coin::new(1000, liquid_stake_token_denom()),
                         sender: env.contract.address.to_string(),
                         receiver: OSMO3.to_string(),
-                        timeout_height: None,
                         timeout_timestamp: timeout.timestamp().unwrap().nanos(),
                         memo: format!("{{\"ibc_callback\":\"{}\"}}", env.contract.address),
-                    }),
+                    })
+                    .unwrap(),
                     gas_limit: None,
                     reply_on: ReplyOn::Always,
                 }
@@ -750,3 +762,54 @@ fn transfer_to_native_chain_true_is_handle_correctly() {
         }
     }
 }
+
+#[test]
+#[cfg(not(feature = "cw20"))]
+fn liquid_stake_from_native_chain() {
+    let mut deps = init();
+    let env = mock_env();
+
+    let intermediate_sender = derive_intermediate_sender(CHANNEL_ID, CELESTIA2, "osmo").unwrap();
+
+    // Only the intermediate sender of the native sender is accepted
+    let err = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(OSMO3, &coins(1000, NATIVE_TOKEN)),
+        ExecuteMsg::LiquidStake {
+            mint_to: None,
+            transfer_to_native_chain: None,
+            expected_mint_amount: None,
+            native_sender: Some(CELESTIA2.to_string()),
+        },
+    )
+    .unwrap_err();
+    assert!(matches!(err, ContractError::Unauthorized { .. }));
+
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(&intermediate_sender, &coins(1000, NATIVE_TOKEN)),
+        ExecuteMsg::LiquidStake {
+            mint_to: None,
+            transfer_to_native_chain: None,
+            expected_mint_amount: None,
+            native_sender: Some(CELESTIA2.to_string()),
+        },
+    )
+    .unwrap();
+    assert!(res.attributes.contains(&attr("native_sender", CELESTIA2)));
+
+    // The minted tokens are delivered back to the native chain sender
+    let lst_transfer = res.messages.last().unwrap();
+    let (channel, receiver, _) = decode_ibc_transfer(&lst_transfer.msg);
+    assert_eq!(CELESTIA2, receiver);
+    assert_eq!(CHANNEL_ID, channel);
+
+    // A failed delivery is not refunded to the intermediate sender
+    let waiting = IBC_WAITING_FOR_REPLY
+        .load(&deps.storage, lst_transfer.id)
+        .unwrap();
+    assert_eq!(CELESTIA2, waiting.receiver);
+    assert_eq!(None, waiting.depositor);
+}
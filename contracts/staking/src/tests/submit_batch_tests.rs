@@ -1,9 +1,13 @@
 use crate::contract::execute;
 use crate::error::ContractError;
 use crate::msg::ExecuteMsg;
+use crate::state::BatchLimits;
+use crate::state::{unstake_requests, KeeperBounty};
 use crate::state::{BATCHES, CONFIG, PENDING_BATCH_ID, STATE};
-use crate::tests::test_helper::{init, OSMO1};
+use crate::tests::test_helper::{init, liquid_unstake, OSMO1};
 use cosmwasm_std::testing::{mock_env, mock_info};
+use cosmwasm_std::testing::{MockApi, MockQuerier, MockStorage};
+use cosmwasm_std::OwnedDeps;
 use cosmwasm_std::Uint128;
 use milky_way::staking::{Batch, BatchStatus};
 
@@ -96,3 +100,195 @@ fn pending_batch_with_to_many_lst_tokens_fails() {
         _ => false,
     })
 }
+
+fn init_with_batch_limits(
+    batch_limits: BatchLimits,
+) -> OwnedDeps<MockStorage, MockApi, MockQuerier> {
+    let mut deps = init();
+
+    let mut state = STATE.load(&deps.storage).unwrap();
+    state.total_liquid_stake_token = Uint128::from(100_000u128);
+    state.total_native_token = Uint128::from(100_000u128);
+    STATE.save(&mut deps.storage, &state).unwrap();
+
+    let mut config = CONFIG.load(&deps.storage).unwrap();
+    config.batch_limits = Some(batch_limits);
+    CONFIG.save(&mut deps.storage, &config).unwrap();
+
+    deps
+}
+
+#[test]
+fn early_submit_batch_on_requests_count() {
+    let mut deps = init_with_batch_limits(BatchLimits {
+        early_submit_liquid_stake: None,
+        early_submit_requests_count: Some(2),
+        max_batch_liquid_stake: None,
+    });
+    let env = mock_env();
+    let contract = env.contract.address.to_string();
+
+    liquid_unstake(deps.as_mut(), mock_env(), "bob", 1_000).unwrap();
+    let err = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(&contract, &[]),
+        ExecuteMsg::SubmitBatch {},
+    )
+    .unwrap_err();
+    assert!(matches!(err, ContractError::BatchNotReady { .. }));
+
+    liquid_unstake(deps.as_mut(), mock_env(), "alice", 1_000).unwrap();
+    execute(
+        deps.as_mut(),
+        env,
+        mock_info(&contract, &[]),
+        ExecuteMsg::SubmitBatch {},
+    )
+    .unwrap();
+
+    let batch = BATCHES.load(&deps.storage, 1).unwrap();
+    assert_eq!(BatchStatus::Submitted, batch.status);
+    assert_eq!(2, PENDING_BATCH_ID.load(&deps.storage).unwrap());
+}
+
+#[test]
+fn unstake_overflow_rolls_into_next_batches() {
+    let mut deps = init_with_batch_limits(BatchLimits {
+        early_submit_liquid_stake: None,
+        early_submit_requests_count: None,
+        max_batch_liquid_stake: Some(Uint128::new(1_000)),
+    });
+    let env = mock_env();
+    let contract = env.contract.address.to_string();
+
+    liquid_unstake(deps.as_mut(), mock_env(), "alice", 400).unwrap();
+    liquid_unstake(deps.as_mut(), mock_env(), "bob", 2_000).unwrap();
+
+    let batch = BATCHES.load(&deps.storage, 1).unwrap();
+    assert_eq!(Uint128::new(1_000), batch.batch_total_liquid_stake);
+    assert_eq!(Some(2), batch.unstake_requests_count);
+    let batch = BATCHES.load(&deps.storage, 2).unwrap();
+    assert_eq!(Uint128::new(1_000), batch.batch_total_liquid_stake);
+    let batch = BATCHES.load(&deps.storage, 3).unwrap();
+    assert_eq!(Uint128::new(400), batch.batch_total_liquid_stake);
+    assert_eq!(Some(1), batch.unstake_requests_count);
+
+    let request = unstake_requests()
+        .load(&deps.storage, (1, "bob".to_string()))
+        .unwrap();
+    assert_eq!(Uint128::new(600), request.amount);
+    let request = unstake_requests()
+        .load(&deps.storage, (3, "bob".to_string()))
+        .unwrap();
+    assert_eq!(Uint128::new(400), request.amount);
+
+    // The full batch can be submitted early and the rolled over
+    // requests are kept in the new pending batch
+    execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(&contract, &[]),
+        ExecuteMsg::SubmitBatch {},
+    )
+    .unwrap();
+    let batch = BATCHES.load(&deps.storage, 2).unwrap();
+    assert_eq!(BatchStatus::Pending, batch.status);
+    assert_eq!(Uint128::new(1_000), batch.batch_total_liquid_stake);
+    assert_eq!(
+        Some(env.block.time.seconds() + CONFIG.load(&deps.storage).unwrap().batch_period),
+        batch.next_batch_action_time
+    );
+}
+
+#[test]
+fn unstake_spanning_too_many_batches_fails() {
+    let mut deps = init_with_batch_limits(BatchLimits {
+        early_submit_liquid_stake: None,
+        early_submit_requests_count: None,
+        max_batch_liquid_stake: Some(Uint128::new(1_000)),
+    });
+
+    let err = liquid_unstake(deps.as_mut(), mock_env(), "bob", 5_001).unwrap_err();
+    assert!(matches!(
+        err,
+        ContractError::TooManyRolledOverBatches { max: 5 }
+    ));
+
+    // Filling exactly the maximum number of batches is allowed
+    let mut deps = init_with_batch_limits(BatchLimits {
+        early_submit_liquid_stake: None,
+        early_submit_requests_count: None,
+        max_batch_liquid_stake: Some(Uint128::new(1_000)),
+    });
+    liquid_unstake(deps.as_mut(), mock_env(), "bob", 5_000).unwrap();
+    let batch = BATCHES.load(&deps.storage, 5).unwrap();
+    assert_eq!(Uint128::new(1_000), batch.batch_total_liquid_stake);
+}
+
+#[test]
+fn submit_batch_pays_keeper_bounty() {
+    let mut deps = init_with_batch_limits(BatchLimits {
+        early_submit_liquid_stake: None,
+        early_submit_requests_count: Some(1),
+        max_batch_liquid_stake: None,
+    });
+    let env = mock_env();
+
+    let mut config = CONFIG.load(&deps.storage).unwrap();
+    config.protocol_fee_config.keeper_bounty = Some(KeeperBounty {
+        max_amount: Uint128::new(100),
+        cooldown: 3_600,
+    });
+    config.protocol_fee_config.treasury_address = None;
+    CONFIG.save(&mut deps.storage, &config).unwrap();
+
+    let mut state = STATE.load(&deps.storage).unwrap();
+    state.total_fees = Uint128::new(150);
+    STATE.save(&mut deps.storage, &state).unwrap();
+
+    liquid_unstake(deps.as_mut(), mock_env(), "bob", 1_000).unwrap();
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("keeper", &[]),
+        ExecuteMsg::SubmitBatch {},
+    )
+    .unwrap();
+    assert!(res
+        .attributes
+        .iter()
+        .any(|a| a.key == "keeper_bounty" && a.value == "100"));
+    assert_eq!(
+        Uint128::new(50),
+        STATE.load(&deps.storage).unwrap().total_fees
+    );
+
+    // No bounty is paid during the cooldown
+    liquid_unstake(deps.as_mut(), mock_env(), "bob", 1_000).unwrap();
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("keeper", &[]),
+        ExecuteMsg::SubmitBatch {},
+    )
+    .unwrap();
+    assert!(!res.attributes.iter().any(|a| a.key == "keeper_bounty"));
+
+    // The bounty is limited by the available fees
+    let mut env = env;
+    env.block.time = env.block.time.plus_seconds(3_600);
+    liquid_unstake(deps.as_mut(), mock_env(), "bob", 1_000).unwrap();
+    let res = execute(
+        deps.as_mut(),
+        env,
+        mock_info("keeper", &[]),
+        ExecuteMsg::SubmitBatch {},
+    )
+    .unwrap();
+    assert!(res
+        .attributes
+        .iter()
+        .any(|a| a.key == "keeper_bounty" && a.value == "50"));
+    assert!(STATE.load(&deps.storage).unwrap().total_fees.is_zero());
+}
@@ -1,12 +1,14 @@
-use crate::contract::instantiate;
-use crate::msg::InstantiateMsg;
+use crate::contract::{execute, instantiate};
+use crate::error::ContractResult;
+use crate::msg::{ExecuteMsg, InstantiateMsg};
 use crate::state::{Config, CONFIG};
 use crate::types::{UnsafeNativeChainConfig, UnsafeProtocolChainConfig, UnsafeProtocolFeeConfig};
 
 use cosmwasm_std::testing::{
     mock_dependencies, mock_env, mock_info, MockApi, MockQuerier, MockStorage,
 };
-use cosmwasm_std::{coins, OwnedDeps, Uint128};
+use cosmwasm_std::{coins, Coin, CosmosMsg, DepsMut, Env, IbcMsg, OwnedDeps, Response, Uint128};
+use osmosis_std::types::ibc::applications::transfer::v1::MsgTransfer;
 
 pub static ADMIN: &str = "admin";
 pub static OSMO1: &str = "osmo12z558dm3ew6avgjdj07mfslx80rp9sh8nt7q3w";
@@ -23,6 +25,10 @@ pub static CHANNEL_ID: &str = "channel-6994";
 pub static NATIVE_TOKEN: &str =
     "ibc/D79E7D83AB399BFFF93433E54FAA480C191248FC556924A2A8351AE2638B3877";
 pub static LIQUID_STAKE_TOKEN_DENOM: &str = "umilkTIA";
+#[cfg(feature = "cw20")]
+pub static CW20_CODE_ID: u64 = 7;
+#[cfg(feature = "cw20")]
+pub static CW20_ADDRESS: &str = "cw20token";
 
 pub fn mock_init_msg() -> InstantiateMsg {
     InstantiateMsg {
@@ -34,6 +40,7 @@ pub fn mock_init_msg() -> InstantiateMsg {
             unbonding_period: 1209600,
             staker_address: STAKER_ADDRESS.to_string(),
             reward_collector_address: CELESTIA2.to_string(),
+            unbonding_receipt_tolerance: None,
         },
         protocol_chain_config: UnsafeProtocolChainConfig {
             account_address_prefix: "osmo".to_string(),
@@ -41,6 +48,10 @@ pub fn mock_init_msg() -> InstantiateMsg {
             ibc_channel_id: CHANNEL_ID.to_string(),
             oracle_address: Some(OSMO4.to_string()),
             minimum_liquid_stake_amount: Uint128::from(100u128),
+            authenticate_ibc_hooks_sender: None,
+            oracle_targets: None,
+            ibc_retry_policy: None,
+            ibc_timeout: None,
         },
         liquid_stake_token_denom: LIQUID_STAKE_TOKEN_DENOM.to_string(),
         monitors: vec![OSMO2.to_string(), OSMO3.to_string()],
@@ -48,9 +59,17 @@ pub fn mock_init_msg() -> InstantiateMsg {
         protocol_fee_config: UnsafeProtocolFeeConfig {
             dao_treasury_fee: Uint128::from(10_000u128),
             treasury_address: Some(OSMO1.to_string()),
+            insurance_fund_fee: None,
+            keeper_bounty: None,
         },
         admin: None,
         oracle_code_id: None,
+        #[cfg(feature = "cw20")]
+        cw20_code_id: Some(CW20_CODE_ID),
+        #[cfg(not(feature = "cw20"))]
+        cw20_code_id: None,
+        denom_metadata: None,
+        adopted_denom: None,
     }
 }
 
@@ -65,9 +84,109 @@ pub fn init() -> OwnedDeps<MockStorage, MockApi, MockQuerier> {
     }
     assert!(res.is_ok());
 
+    // The CW20 token is instantiated by a submessage
+    #[cfg(feature = "cw20")]
+    reply_cw20_instantiated(&mut deps);
+
     let mut config: Config = CONFIG.load(&deps.storage).unwrap();
     config.stopped = false;
     CONFIG.save(&mut deps.storage, &config).unwrap();
 
     deps
 }
+
+/// Denom of the liquid stake token minted by the token backend.
+pub fn liquid_stake_token_denom() -> String {
+    #[cfg(feature = "cw20")]
+    return CW20_ADDRESS.to_string();
+    #[cfg(not(feature = "cw20"))]
+    format!(
+        "factory/{}/{}",
+        cosmwasm_std::testing::MOCK_CONTRACT_ADDR,
+        LIQUID_STAKE_TOKEN_DENOM
+    )
+}
+
+/// Unstakes liquid stake tokens the way the token backend delivers them:
+/// attached as funds, or sent through the CW20 receive hook.
+pub fn liquid_unstake(
+    deps: DepsMut,
+    env: Env,
+    sender: &str,
+    amount: u128,
+) -> ContractResult<Response> {
+    #[cfg(feature = "cw20")]
+    {
+        use crate::msg::{Cw20ReceiveMsg, ReceiveMsg};
+        use cosmwasm_std::to_json_binary;
+
+        execute(
+            deps,
+            env,
+            mock_info(CW20_ADDRESS, &[]),
+            ExecuteMsg::Receive(Cw20ReceiveMsg {
+                sender: sender.to_string(),
+                amount: Uint128::from(amount),
+                msg: to_json_binary(&ReceiveMsg::LiquidUnstake {})?,
+            }),
+        )
+    }
+    #[cfg(not(feature = "cw20"))]
+    execute(
+        deps,
+        env,
+        mock_info(sender, &coins(amount, liquid_stake_token_denom())),
+        ExecuteMsg::LiquidUnstake {
+            native_sender: None,
+        },
+    )
+}
+
+/// Channel, receiver and token of an IBC transfer built by the chain backend.
+pub fn decode_ibc_transfer(msg: &CosmosMsg) -> (String, String, Coin) {
+    match msg {
+        CosmosMsg::Ibc(IbcMsg::Transfer {
+            channel_id,
+            to_address,
+            amount,
+            ..
+        }) => (channel_id.clone(), to_address.clone(), amount.clone()),
+        CosmosMsg::Stargate { value, .. } => {
+            let msg = MsgTransfer::try_from(value.clone()).unwrap();
+            let token = msg.token.unwrap();
+            (
+                msg.source_channel,
+                msg.receiver,
+                Coin::new(token.amount.parse().unwrap(), token.denom),
+            )
+        }
+        _ => panic!("unexpected message {:?}", msg),
+    }
+}
+
+#[cfg(feature = "cw20")]
+pub fn reply_cw20_instantiated(deps: &mut OwnedDeps<MockStorage, MockApi, MockQuerier>) {
+    use crate::contract::{reply, INSTANTIATE_CW20_REPLY_ID};
+    use cosmwasm_std::{Binary, Reply, SubMsgResponse, SubMsgResult};
+    use osmosis_std::types::cosmwasm::wasm::v1::MsgInstantiateContractResponse;
+    use prost::Message;
+
+    reply(
+        deps.as_mut(),
+        mock_env(),
+        Reply {
+            id: INSTANTIATE_CW20_REPLY_ID,
+            result: SubMsgResult::Ok(SubMsgResponse {
+                events: vec![],
+                data: Some(Binary::from(
+                    MsgInstantiateContractResponse {
+                        address: CW20_ADDRESS.to_string(),
+                        data: vec![],
+                    }
+                    .encode_to_vec(),
+                )),
+            }),
+        },
+    )
+    .unwrap();
+}
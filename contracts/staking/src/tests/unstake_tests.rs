@@ -1,20 +1,24 @@
 use crate::contract::execute;
 use crate::contract::query;
+#[cfg(not(feature = "cw20"))]
+use crate::error::ContractError;
 use crate::helpers::derive_intermediate_sender;
 use crate::msg::ExecuteMsg;
 use crate::msg::QueryMsg;
+use crate::msg::UnallocatedBalanceResponse;
 use crate::state::new_unstake_request;
 use crate::state::unstake_requests;
 use crate::state::UnstakeRequest;
-use crate::state::{Config, BATCHES, CONFIG, STATE};
-use crate::tests::test_helper::init;
-use crate::tests::test_helper::LIQUID_STAKE_TOKEN_DENOM;
+use crate::state::{Config, BATCHES, CONFIG, STATE, TOTAL_CLAIMABLE};
+#[cfg(not(feature = "cw20"))]
+use crate::tests::test_helper::{decode_ibc_transfer, CELESTIA2, CHANNEL_ID, OSMO3};
+use crate::tests::test_helper::{init, liquid_stake_token_denom, liquid_unstake};
+use crate::tokenfactory;
 use cosmwasm_std::from_json;
-use cosmwasm_std::testing::{mock_env, mock_info, MOCK_CONTRACT_ADDR};
-use cosmwasm_std::{coins, Addr, CosmosMsg, ReplyOn, SubMsg, Uint128};
+use cosmwasm_std::testing::MOCK_CONTRACT_ADDR;
+use cosmwasm_std::testing::{mock_env, mock_info, MockApi, MockQuerier, MockStorage};
+use cosmwasm_std::{coins, Addr, Coin, OwnedDeps, ReplyOn, SubMsg, Uint128};
 use milky_way::staking::{Batch, BatchStatus};
-use osmosis_std::types::cosmos::base::v1beta1::Coin;
-use osmosis_std::types::osmosis::tokenfactory::v1beta1::MsgBurn;
 
 #[test]
 fn proper_liquid_unstake() {
@@ -26,15 +30,8 @@ fn proper_liquid_unstake() {
     state.total_liquid_stake_token = Uint128::from(100_000u128);
     STATE.save(&mut deps.storage, &state).unwrap();
 
-    let info = mock_info(
-        "bob",
-        &coins(
-            1000,
-            format!("factory/cosmos2contract/{}", LIQUID_STAKE_TOKEN_DENOM),
-        ),
-    );
-    let msg = ExecuteMsg::LiquidUnstake {};
-    let mut res = execute(deps.as_mut(), mock_env(), info.clone(), msg);
+    let info = mock_info("bob", &[]);
+    let mut res = liquid_unstake(deps.as_mut(), mock_env(), "bob", 1000);
     let resp = res.unwrap();
     let attrs = resp.attributes;
 
@@ -72,28 +69,13 @@ fn double_liquid_unstake() {
     state.total_native_token = Uint128::from(10_000u128);
     state.total_liquid_stake_token = Uint128::from(100_000u128);
     STATE.save(&mut deps.storage, &state).unwrap();
-    let msg = ExecuteMsg::LiquidUnstake {};
 
     // Bob unstakes 500
-    let info = mock_info(
-        "bob",
-        &coins(
-            500,
-            format!("factory/cosmos2contract/{}", LIQUID_STAKE_TOKEN_DENOM),
-        ),
-    );
-    let mut res = execute(deps.as_mut(), mock_env(), info, msg.clone());
+    let mut res = liquid_unstake(deps.as_mut(), mock_env(), "bob", 500);
     assert!(res.is_ok());
 
     // Bob unstakes 1_000
-    let info = mock_info(
-        "bob",
-        &coins(
-            1_000,
-            format!("factory/cosmos2contract/{}", LIQUID_STAKE_TOKEN_DENOM),
-        ),
-    );
-    res = execute(deps.as_mut(), mock_env(), info, msg.clone());
+    res = liquid_unstake(deps.as_mut(), mock_env(), "bob", 1_000);
     assert!(res.is_ok());
 
     // Check pending batch
@@ -109,14 +91,8 @@ fn double_liquid_unstake() {
     );
 
     // Alice unstakes 5_000
-    let info = mock_info(
-        "alice",
-        &coins(
-            5_000,
-            format!("factory/cosmos2contract/{}", LIQUID_STAKE_TOKEN_DENOM),
-        ),
-    );
-    res = execute(deps.as_mut(), mock_env(), info.clone(), msg);
+    let info = mock_info("alice", &[]);
+    res = liquid_unstake(deps.as_mut(), mock_env(), "alice", 5_000);
     assert!(res.is_ok());
 
     // Check pending batch
@@ -146,14 +122,12 @@ fn double_liquid_unstake() {
         messages[0],
         SubMsg {
             id: 0,
-            msg: <MsgBurn as Into<CosmosMsg>>::into(MsgBurn {
-                sender: Addr::unchecked(MOCK_CONTRACT_ADDR).to_string(),
-                amount: Some(Coin {
-                    denom: format!("factory/cosmos2contract/{}", LIQUID_STAKE_TOKEN_DENOM),
-                    amount: "6500".to_string(),
-                }),
-                burn_from_address: Addr::unchecked(MOCK_CONTRACT_ADDR).to_string(),
-            }),
+            msg: tokenfactory::burn(
+                MOCK_CONTRACT_ADDR.to_string(),
+                Coin::new(6500, liquid_stake_token_denom()),
+                MOCK_CONTRACT_ADDR.to_string(),
+            )
+            .unwrap(),
             gas_limit: None,
             reply_on: ReplyOn::Never,
         }
@@ -176,7 +150,9 @@ fn invalid_denom_liquid_unstake() {
     STATE.save(&mut deps.storage, &state).unwrap();
 
     let info = mock_info("bob", &coins(1000, "factory/bob/stTIA"));
-    let msg = ExecuteMsg::LiquidUnstake {};
+    let msg = ExecuteMsg::LiquidUnstake {
+        native_sender: None,
+    };
 
     let res = execute(deps.as_mut(), mock_env(), info, msg);
 
@@ -227,6 +203,190 @@ fn receive_unstaked_tokens() {
     execute(deps.as_mut(), env.clone(), info.clone(), msg.clone()).unwrap();
 }
 
+#[test]
+fn receive_unstaked_tokens_from_unauthenticated_sender() {
+    let mut deps = init();
+    let env = mock_env();
+
+    let mut config: Config = CONFIG.load(&deps.storage).unwrap();
+    config.protocol_chain_config.authenticate_ibc_hooks_sender = Some(true);
+    CONFIG.save(&mut deps.storage, &config).unwrap();
+
+    let mut batch: Batch = BATCHES.load(&deps.storage, 1).unwrap();
+    batch.expected_native_unstaked = Some(Uint128::new(100));
+    batch.update_status(BatchStatus::Submitted, Some(env.block.time.seconds() + 1));
+    BATCHES.save(&mut deps.storage, 1, &batch).unwrap();
+
+    let msg = ExecuteMsg::ReceiveUnstakedTokens { batch_id: 1 };
+    let funds = coins(100, config.protocol_chain_config.ibc_token_denom.clone());
+
+    let err = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("bob", &funds),
+        msg.clone(),
+    )
+    .unwrap_err();
+    assert!(matches!(
+        err,
+        crate::error::ContractError::Unauthorized { .. }
+    ));
+
+    let sender = derive_intermediate_sender(
+        &config.protocol_chain_config.ibc_channel_id,
+        config.native_chain_config.staker_address.as_str(),
+        config.protocol_chain_config.account_address_prefix.as_str(),
+    )
+    .unwrap();
+    execute(deps.as_mut(), env, mock_info(&sender, &funds), msg).unwrap();
+}
+
+fn submitted_batch_with_receiver(
+    deps: &mut OwnedDeps<MockStorage, MockApi, MockQuerier>,
+    expected: u128,
+) -> String {
+    let env = mock_env();
+    let config: Config = CONFIG.load(&deps.storage).unwrap();
+
+    let mut batch: Batch = BATCHES.load(&deps.storage, 1).unwrap();
+    batch.expected_native_unstaked = Some(Uint128::new(expected));
+    batch.update_status(BatchStatus::Submitted, Some(env.block.time.seconds() + 1));
+    BATCHES.save(&mut deps.storage, 1, &batch).unwrap();
+
+    derive_intermediate_sender(
+        &config.protocol_chain_config.ibc_channel_id,
+        config.native_chain_config.staker_address.as_str(),
+        config.protocol_chain_config.account_address_prefix.as_str(),
+    )
+    .unwrap()
+}
+
+#[test]
+fn receive_unstaked_tokens_in_tranches() {
+    let mut deps = init();
+    let sender = submitted_batch_with_receiver(&mut deps, 1000);
+    let config: Config = CONFIG.load(&deps.storage).unwrap();
+    let denom = config.protocol_chain_config.ibc_token_denom;
+    let msg = ExecuteMsg::ReceiveUnstakedTokens { batch_id: 1 };
+
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(&sender, &coins(600, &denom)),
+        msg.clone(),
+    )
+    .unwrap();
+    let batch = BATCHES.load(&deps.storage, 1).unwrap();
+    assert_eq!(BatchStatus::PartiallyReceived, batch.status);
+    assert_eq!(Some(Uint128::new(600)), batch.received_native_unstaked);
+
+    // The tranche is held for the batch
+    deps.querier
+        .update_balance(MOCK_CONTRACT_ADDR, coins(600, &denom));
+    let res: UnallocatedBalanceResponse =
+        from_json(query(deps.as_ref(), mock_env(), QueryMsg::UnallocatedBalance {}).unwrap())
+            .unwrap();
+    assert_eq!(Uint128::new(600), res.claimable);
+    assert!(res.unallocated.is_zero());
+
+    // Can't withdraw from a partially received batch
+    assert!(execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("bob", &[]),
+        ExecuteMsg::Withdraw { batch_id: 1 },
+    )
+    .is_err());
+
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(&sender, &coins(400, &denom)),
+        msg.clone(),
+    )
+    .unwrap();
+    let batch = BATCHES.load(&deps.storage, 1).unwrap();
+    assert_eq!(BatchStatus::Received, batch.status);
+    assert_eq!(Some(Uint128::new(1000)), batch.received_native_unstaked);
+    assert_eq!(
+        Uint128::new(1000),
+        TOTAL_CLAIMABLE.load(&deps.storage).unwrap()
+    );
+
+    // The batch has been fully received
+    assert!(execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(&sender, &coins(1, &denom)),
+        msg,
+    )
+    .is_err());
+}
+
+#[test]
+fn receive_unstaked_tokens_within_tolerance() {
+    let mut deps = init();
+    let sender = submitted_batch_with_receiver(&mut deps, 1000);
+    let mut config: Config = CONFIG.load(&deps.storage).unwrap();
+    config.native_chain_config.unbonding_receipt_tolerance = Some(Uint128::new(5));
+    CONFIG.save(&mut deps.storage, &config).unwrap();
+
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(
+            &sender,
+            &coins(997, config.protocol_chain_config.ibc_token_denom),
+        ),
+        ExecuteMsg::ReceiveUnstakedTokens { batch_id: 1 },
+    )
+    .unwrap();
+    assert_eq!(2, res.events.len());
+    assert_eq!("milkyway-receive_unstaked_tokens", res.events[0].ty);
+    assert_eq!("milkyway-unstaked_tokens_shortfall", res.events[1].ty);
+    assert!(res.events[1]
+        .attributes
+        .iter()
+        .any(|a| a.key == "shortfall" && a.value == "3"));
+
+    let batch = BATCHES.load(&deps.storage, 1).unwrap();
+    assert_eq!(BatchStatus::Received, batch.status);
+    assert_eq!(Some(Uint128::new(997)), batch.received_native_unstaked);
+}
+
+#[test]
+fn receive_unstaked_tokens_surplus_is_credited_as_rewards() {
+    let mut deps = init();
+    let sender = submitted_batch_with_receiver(&mut deps, 1000);
+    let config: Config = CONFIG.load(&deps.storage).unwrap();
+
+    let mut state = STATE.load(&deps.storage).unwrap();
+    state.total_liquid_stake_token = Uint128::from(100_000u128);
+    state.total_native_token = Uint128::from(100_000u128);
+    STATE.save(&mut deps.storage, &state).unwrap();
+
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(
+            &sender,
+            &coins(1010, config.protocol_chain_config.ibc_token_denom),
+        ),
+        ExecuteMsg::ReceiveUnstakedTokens { batch_id: 1 },
+    )
+    .unwrap();
+    // oracle update and transfer of the surplus to be staked
+    assert_eq!(2, res.messages.len());
+    assert_eq!(ReplyOn::Always, res.messages[1].reply_on);
+
+    let batch = BATCHES.load(&deps.storage, 1).unwrap();
+    assert_eq!(Some(Uint128::new(1000)), batch.received_native_unstaked);
+
+    let state = STATE.load(&deps.storage).unwrap();
+    assert_eq!(Uint128::new(100_010), state.total_native_token);
+    assert_eq!(Uint128::new(10), state.total_reward_amount);
+}
+
 #[test]
 fn invalid_amount_liquid_unstake() {
     let mut deps = init();
@@ -237,16 +397,9 @@ fn invalid_amount_liquid_unstake() {
     state.total_native_token = Uint128::from(300_000u128);
     STATE.save(&mut deps.storage, &state).unwrap();
 
-    let info = mock_info(
-        "bob",
-        &coins(
-            1_000_000_000,
-            format!("factory/cosmos2contract/{}", LIQUID_STAKE_TOKEN_DENOM),
-        ),
-    );
-    let msg = ExecuteMsg::LiquidUnstake {};
+    let info = mock_info("bob", &[]);
 
-    let res = execute(deps.as_mut(), mock_env(), info.clone(), msg);
+    let res = liquid_unstake(deps.as_mut(), mock_env(), "bob", 1_000_000_000);
     let resp = res.unwrap();
 
     let attrs = resp.attributes;
@@ -298,16 +451,9 @@ fn total_liquid_stake_token_with_zero() {
     state.total_native_token = Uint128::from(300_000u128);
     STATE.save(&mut deps.storage, &state).unwrap();
 
-    let info = mock_info(
-        "bob",
-        &coins(
-            1_000_000_000,
-            format!("factory/cosmos2contract/{}", LIQUID_STAKE_TOKEN_DENOM),
-        ),
-    );
-    let msg = ExecuteMsg::LiquidUnstake {};
+    let info = mock_info("bob", &[]);
 
-    let res = execute(deps.as_mut(), mock_env(), info.clone(), msg);
+    let res = liquid_unstake(deps.as_mut(), mock_env(), "bob", 1_000_000_000);
     let resp = res.unwrap();
 
     let attrs = resp.attributes;
@@ -433,3 +579,74 @@ fn claimable_batches() {
     let unstake_requests = unstake_requests_res.unwrap();
     assert_eq!(unstake_requests.first().unwrap().batch_id, 1);
 }
+
+// The native chain can't send CW20 tokens over IBC
+#[test]
+#[cfg(not(feature = "cw20"))]
+fn liquid_unstake_from_native_chain() {
+    let mut deps = init();
+    let config: Config = CONFIG.load(&deps.storage).unwrap();
+    let lst_denom = liquid_stake_token_denom();
+    let intermediate_sender = derive_intermediate_sender(CHANNEL_ID, CELESTIA2, "osmo").unwrap();
+
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(&intermediate_sender, &coins(1000, &lst_denom)),
+        ExecuteMsg::LiquidUnstake {
+            native_sender: Some(CELESTIA2.to_string()),
+        },
+    )
+    .unwrap();
+    assert!(res
+        .attributes
+        .contains(&cosmwasm_std::attr("native_sender", CELESTIA2)));
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(OSMO3, &coins(1000, &lst_denom)),
+        ExecuteMsg::LiquidUnstake {
+            native_sender: None,
+        },
+    )
+    .unwrap();
+
+    // The request is recorded under the native chain account
+    assert!(unstake_requests().has(&deps.storage, (1, CELESTIA2.to_string())));
+
+    let sender = submitted_batch_with_receiver(&mut deps, 2000);
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(
+            &sender,
+            &coins(2000, &config.protocol_chain_config.ibc_token_denom),
+        ),
+        ExecuteMsg::ReceiveUnstakedTokens { batch_id: 1 },
+    )
+    .unwrap();
+
+    // The unstaked tokens are sent to the native chain account
+    assert_eq!(1, res.messages.len());
+    let (_, receiver, token) = decode_ibc_transfer(&res.messages[0].msg);
+    assert_eq!(CELESTIA2, receiver);
+    assert_eq!(
+        Coin::new(1000, config.protocol_chain_config.ibc_token_denom),
+        token
+    );
+    assert!(!unstake_requests().has(&deps.storage, (1, CELESTIA2.to_string())));
+    assert!(unstake_requests().has(&deps.storage, (1, OSMO3.to_string())));
+
+    // Nothing left to send to the native chain
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(OSMO3, &[]),
+        ExecuteMsg::WithdrawNativeUnstakes {
+            batch_id: 1,
+            limit: None,
+        },
+    )
+    .unwrap_err();
+    assert!(matches!(err, ContractError::NoRequestInBatch {}));
+}
@@ -1,14 +1,15 @@
 use cosmwasm_std::{Addr, Uint128};
 
 use crate::{
-    state::CONFIG,
+    error::ContractError,
+    state::{BatchLimits, KeeperBounty, OracleAdapter, CONFIG},
     tests::test_helper::{
         init, ADMIN, CELESTIA1, CELESTIA2, CELESTIAVAL1, CHANNEL_ID, NATIVE_TOKEN, OSMO1, OSMO3,
         OSMO4,
     },
     types::{
-        UnsafeNativeChainConfig, UnsafeProtocolChainConfig, UnsafeProtocolFeeConfig,
-        MAX_UNBONDING_PERIOD,
+        UnsafeNativeChainConfig, UnsafeOracleTarget, UnsafeProtocolChainConfig,
+        UnsafeProtocolFeeConfig, MAX_ORACLE_TARGETS, MAX_UNBONDING_PERIOD,
     },
 };
 
@@ -25,11 +26,15 @@ fn update_native_chain_config_with_invalid_staker_fails() {
             token_denom: "utia".to_string(),
             unbonding_period: 1209600,
             validators: vec![],
+            unbonding_receipt_tolerance: None,
         }),
         protocol_chain_config: None,
         protocol_fee_config: None,
         batch_period: None,
         monitors: None,
+        resume_contract_limits: None,
+        clear_resume_contract_limits: None,
+        batch_limits: None,
     };
 
     let res = crate::contract::execute(
@@ -54,11 +59,15 @@ fn update_native_chain_config_with_invalid_reward_collector_fails() {
             token_denom: "utia".to_string(),
             unbonding_period: 1209600,
             validators: vec![],
+            unbonding_receipt_tolerance: None,
         }),
         protocol_chain_config: None,
         protocol_fee_config: None,
         batch_period: None,
         monitors: None,
+        resume_contract_limits: None,
+        clear_resume_contract_limits: None,
+        batch_limits: None,
     };
 
     let res = crate::contract::execute(
@@ -83,11 +92,15 @@ fn update_native_chain_config_with_invalid_validator_fails() {
             token_denom: "utia".to_string(),
             unbonding_period: 1209600,
             validators: vec!["osmovaloper1clpqr4nrk4khgkxj78fcwwh6dl3uw4ep88n0y4".to_string()],
+            unbonding_receipt_tolerance: None,
         }),
         protocol_chain_config: None,
         protocol_fee_config: None,
         batch_period: None,
         monitors: None,
+        resume_contract_limits: None,
+        clear_resume_contract_limits: None,
+        batch_limits: None,
     };
 
     let res = crate::contract::execute(
@@ -112,11 +125,15 @@ fn update_native_chain_config_with_invalid_unbonding_period_fails() {
             token_denom: "utia".to_string(),
             unbonding_period: MAX_UNBONDING_PERIOD + 1,
             validators: vec!["osmovaloper1clpqr4nrk4khgkxj78fcwwh6dl3uw4ep88n0y4".to_string()],
+            unbonding_receipt_tolerance: None,
         }),
         protocol_chain_config: None,
         protocol_fee_config: None,
         batch_period: None,
         monitors: None,
+        resume_contract_limits: None,
+        clear_resume_contract_limits: None,
+        batch_limits: None,
     };
 
     let res = crate::contract::execute(
@@ -141,11 +158,15 @@ fn update_native_chain_config_token_denom_without_updating_ibc_denom_fails() {
             token_denom: "tia".to_string(),
             unbonding_period: 1209600,
             validators: vec![],
+            unbonding_receipt_tolerance: None,
         }),
         protocol_chain_config: None,
         protocol_fee_config: None,
         batch_period: None,
         monitors: None,
+        resume_contract_limits: None,
+        clear_resume_contract_limits: None,
+        batch_limits: None,
     };
 
     let res = crate::contract::execute(
@@ -170,6 +191,7 @@ fn update_native_chain_config_properly() {
         token_denom: "utia".to_string(),
         unbonding_period: 1209600,
         validators: vec![CELESTIAVAL1.to_string()],
+        unbonding_receipt_tolerance: None,
     };
     let config_update_msg = crate::msg::ExecuteMsg::UpdateConfig {
         native_chain_config: Some(new_config.clone()),
@@ -177,6 +199,9 @@ fn update_native_chain_config_properly() {
         protocol_fee_config: None,
         batch_period: None,
         monitors: None,
+        resume_contract_limits: None,
+        clear_resume_contract_limits: None,
+        batch_limits: None,
     };
 
     crate::contract::execute(
@@ -231,10 +256,17 @@ fn update_protocol_chain_config_with_invalid_account_address_prefix_fails() {
             ibc_channel_id: CHANNEL_ID.to_string(),
             oracle_address: Some(OSMO4.to_string()),
             minimum_liquid_stake_amount: Uint128::from(100u128),
+            authenticate_ibc_hooks_sender: None,
+            oracle_targets: None,
+            ibc_retry_policy: None,
+            ibc_timeout: None,
         }),
         protocol_fee_config: None,
         batch_period: None,
         monitors: None,
+        resume_contract_limits: None,
+        clear_resume_contract_limits: None,
+        batch_limits: None,
     };
 
     let res = crate::contract::execute(
@@ -258,10 +290,17 @@ fn update_protocol_chain_config_with_invalid_ibc_token_fails() {
             ibc_channel_id: CHANNEL_ID.to_string(),
             oracle_address: Some(OSMO4.to_string()),
             minimum_liquid_stake_amount: Uint128::from(100u128),
+            authenticate_ibc_hooks_sender: None,
+            oracle_targets: None,
+            ibc_retry_policy: None,
+            ibc_timeout: None,
         }),
         protocol_fee_config: None,
         batch_period: None,
         monitors: None,
+        resume_contract_limits: None,
+        clear_resume_contract_limits: None,
+        batch_limits: None,
     };
 
     let res = crate::contract::execute(
@@ -285,10 +324,17 @@ fn update_protocol_chain_config_with_invalid_ibc_channel_id_fails() {
             ibc_channel_id: "".to_string(),
             oracle_address: Some(OSMO4.to_string()),
             minimum_liquid_stake_amount: Uint128::from(100u128),
+            authenticate_ibc_hooks_sender: None,
+            oracle_targets: None,
+            ibc_retry_policy: None,
+            ibc_timeout: None,
         }),
         protocol_fee_config: None,
         batch_period: None,
         monitors: None,
+        resume_contract_limits: None,
+        clear_resume_contract_limits: None,
+        batch_limits: None,
     };
 
     let res = crate::contract::execute(
@@ -312,10 +358,17 @@ fn update_protocol_chain_config_with_invalid_oracle_address_fails() {
             ibc_channel_id: CHANNEL_ID.to_string(),
             oracle_address: Some(CELESTIA1.to_string()),
             minimum_liquid_stake_amount: Uint128::from(100u128),
+            authenticate_ibc_hooks_sender: None,
+            oracle_targets: None,
+            ibc_retry_policy: None,
+            ibc_timeout: None,
         }),
         protocol_fee_config: None,
         batch_period: None,
         monitors: None,
+        resume_contract_limits: None,
+        clear_resume_contract_limits: None,
+        batch_limits: None,
     };
 
     let res = crate::contract::execute(
@@ -327,6 +380,48 @@ fn update_protocol_chain_config_with_invalid_oracle_address_fails() {
     assert!(res.is_err());
 }
 
+#[test]
+fn update_protocol_chain_config_with_too_many_oracle_targets_fails() {
+    let mut deps = init();
+    let info = cosmwasm_std::testing::mock_info(ADMIN, &[]);
+    let config_update_msg = crate::msg::ExecuteMsg::UpdateConfig {
+        native_chain_config: None,
+        protocol_chain_config: Some(UnsafeProtocolChainConfig {
+            account_address_prefix: "osmo".to_string(),
+            ibc_token_denom: NATIVE_TOKEN.to_string(),
+            ibc_channel_id: CHANNEL_ID.to_string(),
+            oracle_address: Some(OSMO4.to_string()),
+            minimum_liquid_stake_amount: Uint128::from(100u128),
+            authenticate_ibc_hooks_sender: None,
+            oracle_targets: Some(vec![
+                UnsafeOracleTarget {
+                    address: OSMO1.to_string(),
+                    adapter: OracleAdapter::UpdateRate,
+                    min_update_interval: None,
+                };
+                MAX_ORACLE_TARGETS
+            ]),
+            ibc_retry_policy: None,
+            ibc_timeout: None,
+        }),
+        protocol_fee_config: None,
+        batch_period: None,
+        monitors: None,
+        resume_contract_limits: None,
+        clear_resume_contract_limits: None,
+        batch_limits: None,
+    };
+
+    let err = crate::contract::execute(
+        deps.as_mut(),
+        cosmwasm_std::testing::mock_env(),
+        info,
+        config_update_msg,
+    )
+    .unwrap_err();
+    assert!(matches!(err, ContractError::ValueTooBig { .. }));
+}
+
 #[test]
 fn update_protocol_chain_with_invalid_ibc_token_fails() {
     let mut deps = init();
@@ -339,6 +434,10 @@ fn update_protocol_chain_with_invalid_ibc_token_fails() {
         ibc_channel_id: "channel-1".to_string(),
         oracle_address: Some(CELESTIA1.to_string()),
         minimum_liquid_stake_amount: Uint128::from(1000u128),
+        authenticate_ibc_hooks_sender: None,
+        oracle_targets: None,
+        ibc_retry_policy: None,
+        ibc_timeout: None,
     };
     let config_update_msg = crate::msg::ExecuteMsg::UpdateConfig {
         native_chain_config: None,
@@ -346,6 +445,9 @@ fn update_protocol_chain_with_invalid_ibc_token_fails() {
         protocol_fee_config: None,
         batch_period: None,
         monitors: None,
+        resume_contract_limits: None,
+        clear_resume_contract_limits: None,
+        batch_limits: None,
     };
 
     let result = crate::contract::execute(
@@ -369,6 +471,10 @@ fn update_protocol_chain_config_properly() {
         ibc_channel_id: "channel-0".to_string(),
         oracle_address: Some(CELESTIA1.to_string()),
         minimum_liquid_stake_amount: Uint128::from(1000u128),
+        authenticate_ibc_hooks_sender: None,
+        oracle_targets: None,
+        ibc_retry_policy: None,
+        ibc_timeout: None,
     };
     let config_update_msg = crate::msg::ExecuteMsg::UpdateConfig {
         native_chain_config: None,
@@ -376,6 +482,9 @@ fn update_protocol_chain_config_properly() {
         protocol_fee_config: None,
         batch_period: None,
         monitors: None,
+        resume_contract_limits: None,
+        clear_resume_contract_limits: None,
+        batch_limits: None,
     };
 
     crate::contract::execute(
@@ -423,9 +532,14 @@ fn update_protocol_fee_config_with_invalid_treasury_address_fails() {
         protocol_fee_config: Some(UnsafeProtocolFeeConfig {
             dao_treasury_fee: Uint128::from(10000u128),
             treasury_address: Some(CELESTIA1.to_string()),
+            insurance_fund_fee: None,
+            keeper_bounty: None,
         }),
         batch_period: None,
         monitors: None,
+        resume_contract_limits: None,
+        clear_resume_contract_limits: None,
+        batch_limits: None,
     };
 
     let res = crate::contract::execute(
@@ -447,9 +561,14 @@ fn update_protocol_fee_config_with_invalid_dao_treasury_fee_fails() {
         protocol_fee_config: Some(UnsafeProtocolFeeConfig {
             dao_treasury_fee: Uint128::new(100_001),
             treasury_address: Some(OSMO3.to_string()),
+            insurance_fund_fee: None,
+            keeper_bounty: None,
         }),
         batch_period: None,
         monitors: None,
+        resume_contract_limits: None,
+        clear_resume_contract_limits: None,
+        batch_limits: None,
     };
 
     let res = crate::contract::execute(
@@ -469,6 +588,8 @@ fn update_protocol_fee_config_properly() {
     let new_config = UnsafeProtocolFeeConfig {
         dao_treasury_fee: Uint128::from(100000u128),
         treasury_address: Some(OSMO3.to_string()),
+        insurance_fund_fee: None,
+        keeper_bounty: None,
     };
     let config_update_msg = crate::msg::ExecuteMsg::UpdateConfig {
         native_chain_config: None,
@@ -476,6 +597,9 @@ fn update_protocol_fee_config_properly() {
         protocol_fee_config: Some(new_config.clone()),
         batch_period: None,
         monitors: None,
+        resume_contract_limits: None,
+        clear_resume_contract_limits: None,
+        batch_limits: None,
     };
 
     crate::contract::execute(
@@ -517,6 +641,9 @@ fn update_batch_period_with_value_bigger_then_unbonding_period_fails() {
         protocol_fee_config: None,
         batch_period: Some(unbonding_period + 1),
         monitors: None,
+        resume_contract_limits: None,
+        clear_resume_contract_limits: None,
+        batch_limits: None,
     };
 
     let result = crate::contract::execute(
@@ -539,6 +666,9 @@ fn update_monitors_with_invalid_address_fails() {
         protocol_fee_config: None,
         batch_period: None,
         monitors: Some(vec![CELESTIA1.to_string()]),
+        resume_contract_limits: None,
+        clear_resume_contract_limits: None,
+        batch_limits: None,
     };
 
     let res = crate::contract::execute(
@@ -560,6 +690,9 @@ fn update_monitors_properly() {
         protocol_fee_config: None,
         batch_period: None,
         monitors: Some(vec![OSMO1.to_string()]),
+        resume_contract_limits: None,
+        clear_resume_contract_limits: None,
+        batch_limits: None,
     };
 
     crate::contract::execute(
@@ -573,3 +706,140 @@ fn update_monitors_properly() {
     let config = CONFIG.load(&deps.storage).unwrap();
     assert_eq!(config.monitors, vec![Addr::unchecked(OSMO1)]);
 }
+
+#[test]
+fn update_protocol_fee_config_with_invalid_insurance_fund_fee_fails() {
+    let mut deps = init();
+    let info = cosmwasm_std::testing::mock_info(ADMIN, &[]);
+    let config_update_msg =
+        |dao_treasury_fee: u128, insurance_fund_fee: u128| crate::msg::ExecuteMsg::UpdateConfig {
+            native_chain_config: None,
+            protocol_chain_config: None,
+            protocol_fee_config: Some(UnsafeProtocolFeeConfig {
+                dao_treasury_fee: Uint128::new(dao_treasury_fee),
+                treasury_address: Some(OSMO3.to_string()),
+                insurance_fund_fee: Some(Uint128::new(insurance_fund_fee)),
+                keeper_bounty: None,
+            }),
+            batch_period: None,
+            monitors: None,
+            resume_contract_limits: None,
+            clear_resume_contract_limits: None,
+            batch_limits: None,
+        };
+
+    let res = crate::contract::execute(
+        deps.as_mut(),
+        cosmwasm_std::testing::mock_env(),
+        info.clone(),
+        config_update_msg(10_000, 50_001),
+    );
+    assert!(matches!(res, Err(ContractError::ValueTooBig { .. })));
+
+    let res = crate::contract::execute(
+        deps.as_mut(),
+        cosmwasm_std::testing::mock_env(),
+        info.clone(),
+        config_update_msg(60_000, 40_000),
+    );
+    assert!(matches!(
+        res,
+        Err(ContractError::InvalidTotalFee { total_fee }) if total_fee == Uint128::new(100_000)
+    ));
+
+    crate::contract::execute(
+        deps.as_mut(),
+        cosmwasm_std::testing::mock_env(),
+        info,
+        config_update_msg(50_000, 40_000),
+    )
+    .unwrap();
+}
+
+#[test]
+fn update_protocol_fee_config_with_keeper_bounty_and_treasury_fails() {
+    let mut deps = init();
+    let info = cosmwasm_std::testing::mock_info(ADMIN, &[]);
+    let config_update_msg =
+        |treasury_address: Option<String>| crate::msg::ExecuteMsg::UpdateConfig {
+            native_chain_config: None,
+            protocol_chain_config: None,
+            protocol_fee_config: Some(UnsafeProtocolFeeConfig {
+                dao_treasury_fee: Uint128::new(10_000),
+                treasury_address,
+                insurance_fund_fee: None,
+                keeper_bounty: Some(KeeperBounty {
+                    max_amount: Uint128::new(100),
+                    cooldown: 3_600,
+                }),
+            }),
+            batch_period: None,
+            monitors: None,
+            resume_contract_limits: None,
+            clear_resume_contract_limits: None,
+            batch_limits: None,
+        };
+
+    let res = crate::contract::execute(
+        deps.as_mut(),
+        cosmwasm_std::testing::mock_env(),
+        info.clone(),
+        config_update_msg(Some(OSMO3.to_string())),
+    );
+    assert!(matches!(
+        res,
+        Err(ContractError::KeeperBountyWithTreasury {})
+    ));
+
+    crate::contract::execute(
+        deps.as_mut(),
+        cosmwasm_std::testing::mock_env(),
+        info,
+        config_update_msg(None),
+    )
+    .unwrap();
+}
+
+#[test]
+fn update_batch_limits_below_minimum_liquid_stake_fails() {
+    let mut deps = init();
+    let info = cosmwasm_std::testing::mock_info(ADMIN, &[]);
+    let config_update_msg = |max_batch_liquid_stake: u128| crate::msg::ExecuteMsg::UpdateConfig {
+        native_chain_config: None,
+        protocol_chain_config: None,
+        protocol_fee_config: None,
+        batch_period: None,
+        monitors: None,
+        resume_contract_limits: None,
+        clear_resume_contract_limits: None,
+        batch_limits: Some(BatchLimits {
+            early_submit_liquid_stake: None,
+            early_submit_requests_count: None,
+            max_batch_liquid_stake: Some(Uint128::new(max_batch_liquid_stake)),
+        }),
+    };
+
+    let minimum_liquid_stake_amount = CONFIG
+        .load(&deps.storage)
+        .unwrap()
+        .protocol_chain_config
+        .minimum_liquid_stake_amount
+        .u128();
+    for max_batch_liquid_stake in [0, minimum_liquid_stake_amount - 1] {
+        let res = crate::contract::execute(
+            deps.as_mut(),
+            cosmwasm_std::testing::mock_env(),
+            info.clone(),
+            config_update_msg(max_batch_liquid_stake),
+        );
+        assert!(matches!(res, Err(ContractError::InvalidMaxBatchSize {})));
+    }
+
+    crate::contract::execute(
+        deps.as_mut(),
+        cosmwasm_std::testing::mock_env(),
+        info,
+        config_update_msg(minimum_liquid_stake_amount),
+    )
+    .unwrap();
+}
@@ -1,12 +1,19 @@
-use crate::contract::{execute, query};
-use crate::msg::{ExecuteMsg, QueryMsg};
-use crate::state::{new_unstake_request, UnstakeRequest, BATCHES, CONFIG, STATE};
-use crate::tests::test_helper::{init, ADMIN, NATIVE_TOKEN, OSMO1};
-use cosmwasm_std::testing::{mock_env, mock_info, MOCK_CONTRACT_ADDR};
-use cosmwasm_std::{from_json, Addr, CosmosMsg, ReplyOn, SubMsg, Uint128};
+use crate::chain::{Backend, Chain};
+use crate::contract::execute;
+use crate::contract::query;
+use crate::helpers::derive_intermediate_sender;
+use crate::msg::ExecuteMsg;
+use crate::msg::QueryMsg;
+use crate::state::{new_unstake_request, UnstakeRequest};
+use crate::state::{BATCHES, BATCH_CLAIMABLE, CONFIG, STATE, TOTAL_CLAIMABLE};
+use crate::tests::test_helper::ADMIN;
+use crate::tests::test_helper::{init, NATIVE_TOKEN, OSMO1, OSMO2, OSMO3};
+use cosmwasm_std::testing::MOCK_CONTRACT_ADDR;
+use cosmwasm_std::testing::{mock_env, mock_info};
+use cosmwasm_std::{coins, Uint128};
+use cosmwasm_std::{from_json, Addr, Coin, ReplyOn, SubMsg};
 use milky_way::staking::Batch;
-use osmosis_std::types::cosmos::bank::v1beta1::MsgSend;
-use osmosis_std::types::cosmos::base::v1beta1::Coin;
+use milky_way::staking::BatchStatus;
 
 #[test]
 fn withdraw() {
@@ -80,10 +87,7 @@ fn withdraw() {
     assert!(resp.is_empty());
 
     let config = CONFIG.load(&deps.storage).unwrap();
-    let coin = Coin {
-        denom: config.protocol_chain_config.ibc_token_denom.clone(),
-        amount: "40000".to_string(),
-    };
+    let coin = Coin::new(40000, config.protocol_chain_config.ibc_token_denom.clone());
 
     // check the MsgSend
     let coins = vec![coin];
@@ -91,11 +95,12 @@ fn withdraw() {
         messages[0],
         SubMsg {
             id: 0,
-            msg: <MsgSend as Into<CosmosMsg>>::into(MsgSend {
-                from_address: Addr::unchecked(MOCK_CONTRACT_ADDR).to_string(),
-                to_address: "bob".to_string(),
-                amount: coins,
-            }),
+            msg: Backend::bank_send(
+                Addr::unchecked(MOCK_CONTRACT_ADDR).to_string(),
+                "bob".to_string(),
+                coins
+            )
+            .unwrap(),
             gas_limit: None,
             reply_on: ReplyOn::Never,
         }
@@ -121,10 +126,7 @@ fn withdraw() {
     assert!(resp.is_empty());
 
     let config = CONFIG.load(&deps.storage).unwrap();
-    let coin = Coin {
-        denom: config.protocol_chain_config.ibc_token_denom.clone(),
-        amount: "90000".to_string(),
-    };
+    let coin = Coin::new(90000, config.protocol_chain_config.ibc_token_denom.clone());
 
     // check the MsgSend
     let coins = vec![coin];
@@ -132,11 +134,12 @@ fn withdraw() {
         messages[0],
         SubMsg {
             id: 0,
-            msg: <MsgSend as Into<CosmosMsg>>::into(MsgSend {
-                from_address: Addr::unchecked(MOCK_CONTRACT_ADDR).to_string(),
-                to_address: "tom".to_string(),
-                amount: coins,
-            }),
+            msg: Backend::bank_send(
+                Addr::unchecked(MOCK_CONTRACT_ADDR).to_string(),
+                "tom".to_string(),
+                coins
+            )
+            .unwrap(),
             gas_limit: None,
             reply_on: ReplyOn::Never,
         }
@@ -198,10 +201,8 @@ fn withdraw_slashing() {
     assert!(resp.is_empty());
 
     let config = CONFIG.load(&deps.storage).unwrap();
-    let coin = Coin {
-        denom: config.protocol_chain_config.ibc_token_denom.clone(),
-        amount: "304615".to_string(), //304615.384... = 304615
-    };
+    // 304615.384... = 304615
+    let coin = Coin::new(304615, config.protocol_chain_config.ibc_token_denom.clone());
 
     // check the MsgSend
     let coins = vec![coin];
@@ -209,11 +210,12 @@ fn withdraw_slashing() {
         messages[0],
         SubMsg {
             id: 0,
-            msg: <MsgSend as Into<CosmosMsg>>::into(MsgSend {
-                from_address: Addr::unchecked(MOCK_CONTRACT_ADDR).to_string(),
-                to_address: "bob".to_string(),
-                amount: coins,
-            }),
+            msg: Backend::bank_send(
+                Addr::unchecked(MOCK_CONTRACT_ADDR).to_string(),
+                "bob".to_string(),
+                coins
+            )
+            .unwrap(),
             gas_limit: None,
             reply_on: ReplyOn::Never,
         }
@@ -239,10 +241,8 @@ fn withdraw_slashing() {
     assert!(resp.is_empty());
 
     let config = CONFIG.load(&deps.storage).unwrap();
-    let coin = Coin {
-        denom: config.protocol_chain_config.ibc_token_denom.clone(),
-        amount: "685384".to_string(), //685,384.615... = 685384
-    };
+    // 685,384.615... = 685384
+    let coin = Coin::new(685384, config.protocol_chain_config.ibc_token_denom.clone());
 
     // check the MsgSend
     let coins = vec![coin];
@@ -250,11 +250,12 @@ fn withdraw_slashing() {
         messages[0],
         SubMsg {
             id: 0,
-            msg: <MsgSend as Into<CosmosMsg>>::into(MsgSend {
-                from_address: Addr::unchecked(MOCK_CONTRACT_ADDR).to_string(),
-                to_address: "tom".to_string(),
-                amount: coins,
-            }),
+            msg: Backend::bank_send(
+                Addr::unchecked(MOCK_CONTRACT_ADDR).to_string(),
+                "tom".to_string(),
+                coins
+            )
+            .unwrap(),
             gas_limit: None,
             reply_on: ReplyOn::Never,
         }
@@ -290,16 +291,103 @@ fn fee_withdraw() {
         res.unwrap().messages[0],
         SubMsg {
             id: 0,
-            msg: <MsgSend as Into<CosmosMsg>>::into(MsgSend {
-                from_address: Addr::unchecked(MOCK_CONTRACT_ADDR).to_string(),
-                to_address: OSMO1.to_string(),
-                amount: vec![Coin {
-                    denom: NATIVE_TOKEN.to_string(),
-                    amount: "1000".to_string()
-                }],
-            }),
+            msg: Backend::bank_send(
+                Addr::unchecked(MOCK_CONTRACT_ADDR).to_string(),
+                OSMO1.to_string(),
+                vec![Coin::new(1000, NATIVE_TOKEN.to_string())]
+            )
+            .unwrap(),
             gas_limit: None,
             reply_on: ReplyOn::Never,
         }
     );
 }
+
+#[test]
+fn withdraw_sweeps_rounding_dust() {
+    let mut deps = init();
+    let config = CONFIG.load(&deps.storage).unwrap();
+    for user in [OSMO1, OSMO2, OSMO3] {
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(user, &coins(1000, &config.liquid_stake_token_denom)),
+            ExecuteMsg::LiquidUnstake {
+                native_sender: None,
+            },
+        )
+        .unwrap();
+    }
+
+    let mut batch = BATCHES.load(&deps.storage, 1).unwrap();
+    batch.expected_native_unstaked = Some(Uint128::new(1000));
+    batch.update_status(BatchStatus::Submitted, None);
+    BATCHES.save(&mut deps.storage, 1, &batch).unwrap();
+    let sender = derive_intermediate_sender(
+        &config.protocol_chain_config.ibc_channel_id,
+        config.native_chain_config.staker_address.as_str(),
+        config.protocol_chain_config.account_address_prefix.as_str(),
+    )
+    .unwrap();
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(&sender, &coins(1000, NATIVE_TOKEN)),
+        ExecuteMsg::ReceiveUnstakedTokens { batch_id: 1 },
+    )
+    .unwrap();
+    assert_eq!(
+        Uint128::new(1000),
+        BATCH_CLAIMABLE.load(&deps.storage, 1).unwrap()
+    );
+    assert_eq!(
+        Uint128::new(1000),
+        TOTAL_CLAIMABLE.load(&deps.storage).unwrap()
+    );
+
+    // Each request receives 333 tokens, the last one leaves 1 token of dust
+    for user in [OSMO1, OSMO2] {
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(user, &[]),
+            ExecuteMsg::Withdraw { batch_id: 1 },
+        )
+        .unwrap();
+        assert!(res
+            .events
+            .iter()
+            .all(|e| e.ty != "milkyway-sweep_batch_dust"));
+    }
+    assert_eq!(
+        Uint128::new(334),
+        BATCH_CLAIMABLE.load(&deps.storage, 1).unwrap()
+    );
+    assert_eq!(
+        Uint128::new(334),
+        TOTAL_CLAIMABLE.load(&deps.storage).unwrap()
+    );
+
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(OSMO3, &[]),
+        ExecuteMsg::Withdraw { batch_id: 1 },
+    )
+    .unwrap();
+    let event = res
+        .events
+        .iter()
+        .find(|e| e.ty == "milkyway-sweep_batch_dust")
+        .unwrap();
+    assert!(event
+        .attributes
+        .iter()
+        .any(|a| a.key == "amount" && a.value == "1"));
+    assert!(!BATCH_CLAIMABLE.has(&deps.storage, 1));
+    assert!(TOTAL_CLAIMABLE.load(&deps.storage).unwrap().is_zero());
+    assert_eq!(
+        Uint128::new(1),
+        STATE.load(&deps.storage).unwrap().total_fees
+    );
+}
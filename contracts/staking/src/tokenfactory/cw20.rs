@@ -0,0 +1,139 @@
+use crate::types::DenomMetadata;
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{to_json_binary, Coin, CosmosMsg, StdError, SubMsg, Uint128, WasmMsg};
+
+/// The CW20 tokens can't be sent through the ICS20 transfer module.
+pub const IBC_TRANSFER_SUPPORTED: bool = false;
+
+// Types for the cw20-base contract
+#[cw_serde]
+pub struct Cw20InstantiateMsg {
+    pub name: String,
+    pub symbol: String,
+    pub decimals: u8,
+    pub initial_balances: Vec<Cw20Coin>,
+    pub mint: Option<MinterResponse>,
+}
+
+#[cw_serde]
+pub struct Cw20Coin {
+    pub address: String,
+    pub amount: Uint128,
+}
+
+#[cw_serde]
+pub struct MinterResponse {
+    pub minter: String,
+    pub cap: Option<Uint128>,
+}
+
+#[cw_serde]
+pub enum Cw20ExecuteMsg {
+    Transfer { recipient: String, amount: Uint128 },
+    Burn { amount: Uint128 },
+    Mint { recipient: String, amount: Uint128 },
+    UpdateMinter { new_minter: Option<String> },
+}
+
+/// Instantiates the CW20 liquid stake token with the contract as its only minter,
+/// the token address is stored when the reply is received.
+pub fn create_denom(
+    sender: String,
+    subdenom: String,
+    metadata: Option<&DenomMetadata>,
+    code_id: u64,
+    reply_id: u64,
+) -> Result<SubMsg, StdError> {
+    Ok(SubMsg::reply_on_success(
+        WasmMsg::Instantiate {
+            admin: Some(sender.clone()),
+            code_id,
+            msg: to_json_binary(&Cw20InstantiateMsg {
+                name: subdenom.clone(),
+                symbol: metadata.map_or(subdenom.clone(), |m| m.symbol.clone()),
+                decimals: metadata.map_or(6, |m| m.exponent as u8),
+                initial_balances: vec![],
+                mint: Some(MinterResponse {
+                    minter: sender,
+                    cap: None,
+                }),
+            })?,
+            funds: vec![],
+            label: format!("{} Token", subdenom),
+        },
+        reply_id,
+    ))
+}
+
+pub fn mint(_sender: String, amount: Coin, mint_to_address: String) -> Result<CosmosMsg, StdError> {
+    execute(
+        amount.denom,
+        &Cw20ExecuteMsg::Mint {
+            recipient: mint_to_address,
+            amount: amount.amount,
+        },
+    )
+}
+
+pub fn burn(
+    sender: String,
+    amount: Coin,
+    burn_from_address: String,
+) -> Result<CosmosMsg, StdError> {
+    if burn_from_address != sender {
+        return Err(StdError::generic_err(
+            "on cw20 burn from address must be equal to the sender",
+        ));
+    }
+
+    execute(
+        amount.denom,
+        &Cw20ExecuteMsg::Burn {
+            amount: amount.amount,
+        },
+    )
+}
+
+/// The symbol and decimals of a CW20 token can only be set when it is instantiated.
+pub fn set_denom_metadata(
+    _sender: String,
+    _denom: String,
+    _metadata: DenomMetadata,
+) -> Result<CosmosMsg, StdError> {
+    Err(StdError::generic_err(
+        "the cw20 backend doesn't support setting the denom metadata",
+    ))
+}
+
+/// The minter of the CW20 token takes the role of the denom admin.
+pub fn change_admin(
+    _sender: String,
+    denom: String,
+    new_admin: String,
+) -> Result<CosmosMsg, StdError> {
+    execute(
+        denom,
+        &Cw20ExecuteMsg::UpdateMinter {
+            new_minter: Some(new_admin),
+        },
+    )
+}
+
+pub fn send(_sender: String, amount: Coin, to_address: String) -> Result<CosmosMsg, StdError> {
+    execute(
+        amount.denom,
+        &Cw20ExecuteMsg::Transfer {
+            recipient: to_address,
+            amount: amount.amount,
+        },
+    )
+}
+
+fn execute(contract_addr: String, msg: &Cw20ExecuteMsg) -> Result<CosmosMsg, StdError> {
+    Ok(WasmMsg::Execute {
+        contract_addr,
+        msg: to_json_binary(msg)?,
+        funds: vec![],
+    }
+    .into())
+}
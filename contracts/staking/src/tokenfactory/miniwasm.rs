@@ -1,7 +1,15 @@
+use crate::chain::{Backend, Chain};
+use crate::types::DenomMetadata;
 use cosmwasm_std::{Binary, Coin, CosmosMsg, StdError};
-use initia_proto::miniwasm::tokenfactory::v1::{MsgBurn, MsgCreateDenom, MsgMint};
+use initia_proto::cosmos::bank::v1beta1::{DenomUnit, Metadata};
+use initia_proto::miniwasm::tokenfactory::v1::{
+    MsgBurn, MsgChangeAdmin, MsgCreateDenom, MsgMint, MsgSetDenomMetadata,
+};
 use initia_proto::traits::MessageExt;
 
+/// The tokenfactory denoms are bank tokens and can be sent over IBC.
+pub const IBC_TRANSFER_SUPPORTED: bool = true;
+
 pub fn create_denom(sender: String, subdenom: String) -> Result<CosmosMsg, StdError> {
     let bytes = MsgCreateDenom { sender, subdenom }
         .to_bytes()
@@ -55,3 +63,62 @@ pub fn burn(
         value: Binary::from(bytes),
     })
 }
+
+pub fn change_admin(
+    sender: String,
+    denom: String,
+    new_admin: String,
+) -> Result<CosmosMsg, StdError> {
+    let bytes = MsgChangeAdmin {
+        sender,
+        denom,
+        new_admin,
+    }
+    .to_bytes()
+    .map_err(|_| StdError::generic_err("Failed to serialize MsgChangeAdmin"))?;
+    Ok(CosmosMsg::Stargate {
+        type_url: "/miniwasm.tokenfactory.v1.MsgChangeAdmin".to_string(),
+        value: Binary::from(bytes),
+    })
+}
+
+pub fn send(sender: String, amount: Coin, to_address: String) -> Result<CosmosMsg, StdError> {
+    Backend::bank_send(sender, to_address, vec![amount])
+}
+
+pub fn set_denom_metadata(
+    sender: String,
+    denom: String,
+    metadata: DenomMetadata,
+) -> Result<CosmosMsg, StdError> {
+    let bytes = MsgSetDenomMetadata {
+        sender,
+        metadata: Some(Metadata {
+            description: metadata.description.unwrap_or_default(),
+            denom_units: vec![
+                DenomUnit {
+                    denom: denom.clone(),
+                    exponent: 0,
+                    aliases: vec![],
+                },
+                DenomUnit {
+                    denom: metadata.display.clone(),
+                    exponent: metadata.exponent,
+                    aliases: vec![],
+                },
+            ],
+            base: denom,
+            display: metadata.display,
+            name: metadata.symbol.clone(),
+            symbol: metadata.symbol,
+            uri: metadata.uri.unwrap_or_default(),
+            uri_hash: String::new(),
+        }),
+    }
+    .to_bytes()
+    .map_err(|_| StdError::generic_err("Failed to serialize MsgSetDenomMetadata"))?;
+    Ok(CosmosMsg::Stargate {
+        type_url: "/miniwasm.tokenfactory.v1.MsgSetDenomMetadata".to_string(),
+        value: Binary::from(bytes),
+    })
+}
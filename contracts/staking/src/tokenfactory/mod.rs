@@ -1,9 +1,14 @@
-#[cfg(feature = "miniwasm")]
+#[cfg(feature = "cw20")]
+mod cw20;
+#[cfg(feature = "cw20")]
+pub use self::cw20::*;
+
+#[cfg(all(feature = "miniwasm", not(feature = "cw20")))]
 mod miniwasm;
-#[cfg(feature = "miniwasm")]
+#[cfg(all(feature = "miniwasm", not(feature = "cw20")))]
 pub use miniwasm::*;
 
-#[cfg(not(feature = "miniwasm"))]
+#[cfg(not(any(feature = "miniwasm", feature = "cw20")))]
 pub use osmosis::*;
-#[cfg(not(feature = "miniwasm"))]
+#[cfg(not(any(feature = "miniwasm", feature = "cw20")))]
 mod osmosis;
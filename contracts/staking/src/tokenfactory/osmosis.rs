@@ -1,5 +1,13 @@
+use crate::chain::{Backend, Chain};
+use crate::types::DenomMetadata;
 use cosmwasm_std::{Coin, CosmosMsg, StdError};
-use osmosis_std::types::osmosis::tokenfactory::v1beta1::{MsgBurn, MsgCreateDenom, MsgMint};
+use osmosis_std::types::cosmos::bank::v1beta1::{DenomUnit, Metadata};
+use osmosis_std::types::osmosis::tokenfactory::v1beta1::{
+    MsgBurn, MsgChangeAdmin, MsgCreateDenom, MsgMint, MsgSetDenomMetadata,
+};
+
+/// The tokenfactory denoms are bank tokens and can be sent over IBC.
+pub const IBC_TRANSFER_SUPPORTED: bool = true;
 
 pub fn create_denom(sender: String, subdenom: String) -> Result<CosmosMsg, StdError> {
     Ok(MsgCreateDenom { sender, subdenom }.into())
@@ -32,3 +40,52 @@ pub fn burn(
     }
     .into())
 }
+
+pub fn change_admin(
+    sender: String,
+    denom: String,
+    new_admin: String,
+) -> Result<CosmosMsg, StdError> {
+    Ok(MsgChangeAdmin {
+        sender,
+        denom,
+        new_admin,
+    }
+    .into())
+}
+
+pub fn send(sender: String, amount: Coin, to_address: String) -> Result<CosmosMsg, StdError> {
+    Backend::bank_send(sender, to_address, vec![amount])
+}
+
+pub fn set_denom_metadata(
+    sender: String,
+    denom: String,
+    metadata: DenomMetadata,
+) -> Result<CosmosMsg, StdError> {
+    Ok(MsgSetDenomMetadata {
+        sender,
+        metadata: Some(Metadata {
+            description: metadata.description.unwrap_or_default(),
+            denom_units: vec![
+                DenomUnit {
+                    denom: denom.clone(),
+                    exponent: 0,
+                    aliases: vec![],
+                },
+                DenomUnit {
+                    denom: metadata.display.clone(),
+                    exponent: metadata.exponent,
+                    aliases: vec![],
+                },
+            ],
+            base: denom,
+            display: metadata.display,
+            name: metadata.symbol.clone(),
+            symbol: metadata.symbol,
+            uri: metadata.uri.unwrap_or_default(),
+            uri_hash: String::new(),
+        }),
+    }
+    .into())
+}
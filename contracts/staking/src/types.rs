@@ -5,13 +5,33 @@ use milky_way::utils::{validate_address, validate_address_prefix, validate_addre
 use crate::{
     error::{ContractError, ContractResult},
     helpers::{validate_denom, validate_ibc_denom},
-    state::{NativeChainConfig, ProtocolChainConfig, ProtocolFeeConfig},
+    state::{
+        IbcRetryPolicy, KeeperBounty, NativeChainConfig, OracleAdapter, OracleTarget,
+        ProtocolChainConfig, ProtocolFeeConfig,
+    },
 };
 
 const MAX_TREASURY_FEE: Uint128 = Uint128::new(100_000);
+const MAX_INSURANCE_FUND_FEE: Uint128 = Uint128::new(50_000);
 /// The maximum allowed unbonding period is 42 days,
 /// which is twice the typical staking period of a Cosmos SDK-based chain.
 pub const MAX_UNBONDING_PERIOD: u64 = 3_628_800;
+/// Maximum number of contracts to which the rates are pushed,
+/// including the `oracle_address`.
+pub const MAX_ORACLE_TARGETS: usize = 10;
+/// Maximum number of contracts subscribed to the protocol events.
+pub const MAX_HOOK_SUBSCRIPTIONS: usize = 10;
+/// Maximum number of batches, including the pending one, across which
+/// a single unstake request is split when exceeding the maximum batch size.
+pub const MAX_ROLLED_OVER_BATCHES: u64 = 5;
+/// Maximum timeout in seconds of the IBC transfers, 7 days.
+pub const MAX_IBC_TIMEOUT: u64 = 604_800;
+/// Maximum number of times a failed IBC transfer is re-sent.
+pub const MAX_IBC_RETRY_ATTEMPTS: u32 = 10;
+/// Maximum initial backoff of the IBC transfer retries, 1 day.
+/// The backoff is doubled after each attempt, so a transfer is never
+/// re-sent later than 512 days after its previous attempt.
+pub const MAX_IBC_RETRY_BACKOFF: u64 = 86_400;
 
 /// Config related to the fees collected by the contract to
 /// operate the liquid staking protocol.
@@ -22,6 +42,16 @@ pub struct UnsafeProtocolFeeConfig {
     /// Address where the collected fees are sent.
     /// If this value is None, the fees are sent to the treasury.
     pub treasury_address: Option<String>,
+
+    /// Share of the collected fees routed to the insurance fund,
+    /// fee percentage=x/100000. Can't exceed 50000, and together with
+    /// `dao_treasury_fee` must stay below 100000.
+    pub insurance_fund_fee: Option<Uint128>,
+
+    /// Bounty paid from the collected fees to the caller of
+    /// [crate::msg::ExecuteMsg::SubmitBatch]. The fees are only held by the
+    /// contract without a `treasury_address`, so both can't be set.
+    pub keeper_bounty: Option<KeeperBounty>,
 }
 
 impl UnsafeProtocolFeeConfig {
@@ -32,6 +62,22 @@ impl UnsafeProtocolFeeConfig {
         if self.dao_treasury_fee > MAX_TREASURY_FEE {
             return Err(ContractError::InvalidDaoTreasuryFee {});
         }
+        if let Some(insurance_fund_fee) = self.insurance_fund_fee {
+            if insurance_fund_fee > MAX_INSURANCE_FUND_FEE {
+                return Err(ContractError::ValueTooBig {
+                    field_name: "insurance_fund_fee".to_string(),
+                    value: insurance_fund_fee,
+                    max: MAX_INSURANCE_FUND_FEE,
+                });
+            }
+            let total_fee = self.dao_treasury_fee + insurance_fund_fee;
+            if total_fee >= MAX_TREASURY_FEE {
+                return Err(ContractError::InvalidTotalFee { total_fee });
+            }
+        }
+        if self.keeper_bounty.is_some() && self.treasury_address.is_some() {
+            return Err(ContractError::KeeperBountyWithTreasury {});
+        }
 
         Ok(ProtocolFeeConfig {
             dao_treasury_fee: self.dao_treasury_fee,
@@ -40,6 +86,8 @@ impl UnsafeProtocolFeeConfig {
                 .as_ref()
                 .map(|a| validate_address(a, &config.account_address_prefix))
                 .transpose()?,
+            insurance_fund_fee: self.insurance_fund_fee,
+            keeper_bounty: self.keeper_bounty.clone(),
         })
     }
 }
@@ -70,6 +118,11 @@ pub struct UnsafeNativeChainConfig {
 
     /// Address where the staking rewards are withdrawn.
     pub reward_collector_address: String,
+
+    /// Maximum shortfall accepted when receiving the unstaked tokens of a batch,
+    /// a batch receiving at least `expected_native_unstaked` minus this amount
+    /// is considered fully received. If `None`, the full amount is required.
+    pub unbonding_receipt_tolerance: Option<Uint128>,
 }
 
 impl UnsafeNativeChainConfig {
@@ -93,6 +146,7 @@ impl UnsafeNativeChainConfig {
                 &self.reward_collector_address,
                 &self.account_address_prefix,
             )?,
+            unbonding_receipt_tolerance: self.unbonding_receipt_tolerance,
         })
     }
 }
@@ -113,6 +167,31 @@ pub struct UnsafeProtocolChainConfig {
 
     /// The redemption / purchase rate oracle address
     pub oracle_address: Option<String>,
+
+    /// Policy applied when retrying the failed IBC transfers.
+    pub ibc_retry_policy: Option<IbcRetryPolicy>,
+
+    /// Timeout in seconds of the IBC transfers sent by the contract,
+    /// at most 7 days. If `None`, the default timeout is used.
+    pub ibc_timeout: Option<u64>,
+
+    /// If true, rewards and unstaked tokens are only accepted from the
+    /// ibc-hooks intermediate sender derived from the `reward_collector_address`
+    /// and `staker_address` on `ibc_channel_id`.
+    pub authenticate_ibc_hooks_sender: Option<bool>,
+
+    /// Contracts to which the rates are pushed, in addition to `oracle_address`.
+    pub oracle_targets: Option<Vec<UnsafeOracleTarget>>,
+}
+
+/// Contract to which the rates are pushed.
+#[cw_serde]
+pub struct UnsafeOracleTarget {
+    pub address: String,
+    pub adapter: OracleAdapter,
+    /// Minimum time in seconds between two updates of the target.
+    /// If `None`, the target is updated every time the rates change.
+    pub min_update_interval: Option<u64>,
 }
 
 impl UnsafeProtocolChainConfig {
@@ -127,6 +206,24 @@ impl UnsafeProtocolChainConfig {
         if !channel_id_correct {
             return Err(ContractError::IbcChannelConfigWrong {});
         }
+        if self
+            .ibc_timeout
+            .map_or(false, |timeout| timeout == 0 || timeout > MAX_IBC_TIMEOUT)
+        {
+            return Err(ContractError::InvalidIbcTimeout {});
+        }
+        if let Some(policy) = &self.ibc_retry_policy {
+            policy.validate()?;
+        }
+        let oracle_targets_count = self.oracle_targets.as_ref().map_or(0, Vec::len)
+            + usize::from(self.oracle_address.is_some());
+        if oracle_targets_count > MAX_ORACLE_TARGETS {
+            return Err(ContractError::ValueTooBig {
+                field_name: "oracle_targets".to_string(),
+                value: Uint128::from(oracle_targets_count as u128),
+                max: Uint128::from(MAX_ORACLE_TARGETS as u128),
+            });
+        }
 
         Ok(ProtocolChainConfig {
             account_address_prefix: validate_address_prefix(&self.account_address_prefix)?,
@@ -142,6 +239,28 @@ impl UnsafeProtocolChainConfig {
                 .as_ref()
                 .map(|a| validate_address(a, &self.account_address_prefix))
                 .transpose()?,
+            authenticate_ibc_hooks_sender: self.authenticate_ibc_hooks_sender,
+            ibc_retry_policy: self.ibc_retry_policy.clone(),
+            ibc_timeout: self.ibc_timeout,
+            oracle_targets: self
+                .oracle_targets
+                .as_ref()
+                .map(|targets| {
+                    targets
+                        .iter()
+                        .map(|target| {
+                            Ok(OracleTarget {
+                                address: validate_address(
+                                    &target.address,
+                                    &self.account_address_prefix,
+                                )?,
+                                adapter: target.adapter.clone(),
+                                min_update_interval: target.min_update_interval,
+                            })
+                        })
+                        .collect::<ContractResult<Vec<_>>>()
+                })
+                .transpose()?,
         })
     }
 }
@@ -151,3 +270,122 @@ pub struct BatchExpectedAmount {
     pub batch_id: u64,
     pub amount: Uint128,
 }
+
+/// Amount of native tokens slashed from the expected amount of a batch.
+#[cw_serde]
+pub struct BatchSlashAmount {
+    pub batch_id: u64,
+    pub amount: Uint128,
+}
+
+/// Memo attached to the IBC transfers sent by the contract.
+#[cw_serde]
+pub struct IbcTransferMemo {
+    /// Contract notified by ibc-hooks of the ack or timeout of the transfer.
+    pub ibc_callback: String,
+
+    /// Packet forward middleware instructions.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub forward: Option<PacketForward>,
+}
+
+/// Packet forward middleware instructions, used to forward the transfer
+/// from the receiving chain to another chain.
+#[cw_serde]
+pub struct PacketForward {
+    /// Receiver on the final chain.
+    pub receiver: String,
+
+    /// Port used to forward the transfer (e.g. "transfer").
+    pub port: String,
+
+    /// Channel used to forward the transfer on the receiving chain.
+    pub channel: String,
+
+    /// Timeout of the forwarded transfer (e.g. "10m").
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub timeout: Option<String>,
+
+    /// Number of retries of the forwarded transfer.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub retries: Option<u8>,
+}
+
+/// Bank metadata of the liquid stake token, displayed by wallets and explorers.
+#[cw_serde]
+pub struct DenomMetadata {
+    /// Denom shown to the users (e.g. "milkTIA").
+    pub display: String,
+
+    /// Ticker of the token (e.g. "milkTIA").
+    pub symbol: String,
+
+    /// Exponent of the display denom, the base denom has exponent 0.
+    pub exponent: u32,
+
+    /// Description of the token.
+    pub description: Option<String>,
+
+    /// URI of a document with additional information about the token.
+    pub uri: Option<String>,
+}
+
+impl DenomMetadata {
+    pub fn validate(&self) -> ContractResult<()> {
+        if self.display.is_empty() || self.symbol.is_empty() {
+            return Err(ContractError::InvalidDenomMetadata {
+                reason: "display and symbol can't be empty".to_string(),
+            });
+        }
+        if self.exponent == 0 || self.exponent > 18 {
+            return Err(ContractError::InvalidDenomMetadata {
+                reason: "exponent must be between 1 and 18".to_string(),
+            });
+        }
+        Ok(())
+    }
+}
+
+/// Liquid stake token of a previous deployment adopted by the contract,
+/// along with the totals of the previous deployment.
+#[cw_serde]
+pub struct AdoptedDenom {
+    /// Denom of the liquid stake token, or address of the CW20 token.
+    pub denom: String,
+
+    pub total_native_token: Uint128,
+    pub total_liquid_stake_token: Uint128,
+    pub total_reward_amount: Uint128,
+    pub total_fees: Uint128,
+
+    /// ID of the pending batch of the previous deployment.
+    pub pending_batch_id: u64,
+}
+
+impl AdoptedDenom {
+    pub fn validate(&self) -> ContractResult<()> {
+        if self.denom.is_empty() {
+            return Err(ContractError::InvalidAdoptedDenom {
+                reason: "denom can't be empty".to_string(),
+            });
+        }
+        if self.pending_batch_id == 0 {
+            return Err(ContractError::InvalidAdoptedDenom {
+                reason: "pending_batch_id must be greater than zero".to_string(),
+            });
+        }
+        Ok(())
+    }
+}
+
+/// Unstake request imported from a previous deployment.
+#[cw_serde]
+pub struct ImportedUnstakeRequest {
+    pub batch_id: u64,
+    pub user: String,
+    pub amount: Uint128,
+
+    /// True if the request has been made by a native chain account
+    /// through ibc-hooks.
+    pub native: bool,
+}
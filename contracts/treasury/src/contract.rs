@@ -4,6 +4,8 @@ use cosmwasm_std::{
     to_json_binary, to_json_string, Binary, Deps, DepsMut, Env, MessageInfo, Response, StdError,
 };
 use cw2::set_contract_version;
+use milky_way::events::MilkyWayEvent;
+use milky_way::migration::migration_in_progress;
 use semver::Version;
 
 use crate::error::{ContractError, ContractResult};
@@ -15,7 +17,7 @@ use crate::execute::{
 use crate::helpers::validate_swap_routes;
 use crate::migrations;
 use crate::msg::{ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg};
-use crate::query::query_config;
+use crate::query::{query_config, query_migration_status};
 use crate::state::{Config, State, ADMIN, CONFIG, STATE};
 
 pub const CONTRACT_NAME: &str = env!("CARGO_PKG_NAME");
@@ -35,7 +37,7 @@ pub fn instantiate(
         .map(|admin_str| deps.api.addr_validate(&admin_str))
         .transpose()?
         .unwrap_or(info.sender.clone());
-    ADMIN.set(deps.branch(), Some(admin))?;
+    ADMIN.set(deps.branch(), Some(admin.clone()))?;
 
     // Init State
     let state = State {
@@ -62,7 +64,13 @@ pub fn instantiate(
 
     Ok(Response::new()
         .add_attribute("action", "instantiate")
-        .add_attribute("owner", info.sender)
+        .add_attribute("owner", info.sender.clone())
+        .add_event(
+            MilkyWayEvent::Instantiate {
+                admin: admin.to_string(),
+            }
+            .into(),
+        )
         .add_attribute("trader", config.trader)
         .add_attribute(
             "allowed_swap_routes",
@@ -77,6 +85,10 @@ pub fn execute(
     info: MessageInfo,
     msg: ExecuteMsg,
 ) -> Result<Response, ContractError> {
+    if migration_in_progress(deps.storage)? {
+        return Err(ContractError::Migrating {});
+    }
+
     match msg {
         ExecuteMsg::TransferOwnership { new_owner } => {
             execute_transfer_ownership(deps, env, info, new_owner)
@@ -120,6 +132,7 @@ pub fn execute(
 pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> ContractResult<Binary> {
     match msg {
         QueryMsg::Config {} => to_json_binary(&query_config(deps)?),
+        QueryMsg::MigrationStatus {} => to_json_binary(&query_migration_status(deps)?),
     }
     .map_err(ContractError::from)
 }
@@ -155,5 +168,11 @@ pub fn migrate(deps: DepsMut, env: Env, msg: MigrateMsg) -> ContractResult<Respo
         } => migrations::v1_0_0::migrate(deps, env, native_chain_config, protocol_chain_config)?,
     };
 
-    Ok(migration_response)
+    Ok(migration_response.add_event(
+        MilkyWayEvent::Migrate {
+            from_version: current_version.version,
+            to_version: CONTRACT_VERSION.to_string(),
+        }
+        .into(),
+    ))
 }
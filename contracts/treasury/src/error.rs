@@ -37,4 +37,7 @@ pub enum ContractError {
 
     #[error("Invalid swap route: {index}, reason: {reason}")]
     InvalidSwapRoute { index: usize, reason: String },
+
+    #[error("Contract is being migrated")]
+    Migrating {},
 }
@@ -1,6 +1,8 @@
 use cosmwasm_std::{
-    attr, to_json_string, CosmosMsg, DepsMut, Env, MessageInfo, Response, Timestamp,
+    attr, to_json_string, Addr, CosmosMsg, DepsMut, Env, Event, MessageInfo, Response, Timestamp,
+    Uint128,
 };
+use milky_way::events::MilkyWayEvent;
 use osmosis_std::types::{
     cosmos::base::v1beta1::Coin, ibc::applications::transfer::v1::MsgTransfer,
 };
@@ -37,8 +39,15 @@ pub fn execute_transfer_ownership(
 
     Ok(Response::new()
         .add_attribute("action", "transfer_ownership")
-        .add_attribute("new_owner", new_owner)
-        .add_attribute("previous_owner", info.sender))
+        .add_attribute("new_owner", new_owner.clone())
+        .add_attribute("previous_owner", info.sender.clone())
+        .add_event(
+            MilkyWayEvent::TransferOwnership {
+                previous_owner: info.sender.to_string(),
+                new_owner,
+            }
+            .into(),
+        ))
 }
 
 // Revoke transfer ownership, callable by the owner
@@ -55,7 +64,14 @@ pub fn execute_revoke_ownership_transfer(
 
     STATE.save(deps.storage, &state)?;
 
-    Ok(Response::new().add_attribute("action", "revoke_ownership_transfer"))
+    Ok(Response::new()
+        .add_attribute("action", "revoke_ownership_transfer")
+        .add_event(
+            MilkyWayEvent::RevokeOwnershipTransfer {
+                owner: info.sender.to_string(),
+            }
+            .into(),
+        ))
 }
 
 pub fn execute_accept_ownership(
@@ -90,7 +106,13 @@ pub fn execute_accept_ownership(
             ADMIN.set(deps, Some(pending_owner))?;
             Ok(Response::new()
                 .add_attribute("action", "accept_ownership")
-                .add_attribute("new_owner", info.sender))
+                .add_attribute("new_owner", info.sender.clone())
+                .add_event(
+                    MilkyWayEvent::AcceptOwnership {
+                        new_owner: info.sender.to_string(),
+                    }
+                    .into(),
+                ))
         }
         None => Err(ContractError::NoPendingOwner {}),
     }
@@ -148,12 +170,21 @@ pub fn execute_spend_funds(
     ];
 
     if channel_id.clone().is_some() {
-        attributes.push(attr("channel_id", channel_id.unwrap().clone()));
+        attributes.push(attr("channel_id", channel_id.clone().unwrap()));
     }
 
     let res = Response::new()
         .add_message(msg_send)
-        .add_attributes(attributes);
+        .add_attributes(attributes)
+        .add_event(
+            MilkyWayEvent::SpendFunds {
+                receiver,
+                denom: amount.denom,
+                amount: amount.amount,
+                channel_id,
+            }
+            .into(),
+        );
     Ok(res)
 }
 
@@ -194,12 +225,21 @@ pub fn execute_swap_exact_amount_in(
         token_out_min_amount: token_out_min_amount.to_string(),
     };
 
+    let event = swap_event(
+        &info.sender,
+        &swap_routes,
+        token_in.amount,
+        token_out_min_amount.into(),
+        true,
+    );
+
     Ok(Response::new()
         .add_attribute("action", "swap_exact_amount_in")
         .add_attribute("sender", info.sender)
         .add_attribute("routes", to_json_string(&swap_routes)?)
         .add_attribute("token_in", token_in.to_string())
         .add_attribute("token_out_min_amount", token_out_min_amount.to_string())
+        .add_event(event)
         .add_message(message))
 }
 
@@ -240,15 +280,45 @@ pub fn execute_swap_exact_amount_out(
         token_in_max_amount: token_in_max_amount.to_string(),
     };
 
+    let event = swap_event(
+        &info.sender,
+        &swap_routes,
+        token_out.amount,
+        token_in_max_amount.into(),
+        false,
+    );
+
     Ok(Response::new()
         .add_attribute("action", "swap_exact_amount_out")
         .add_attribute("sender", info.sender)
         .add_attribute("routes", to_json_string(&swap_routes)?)
         .add_attribute("token_out", token_out.to_string())
         .add_attribute("token_in_max_amount", token_in_max_amount.to_string())
+        .add_event(event)
         .add_message(message))
 }
 
+// The amount is the exact side of the swap and the limit the bound on the
+// other side.
+fn swap_event(
+    sender: &Addr,
+    swap_routes: &[SwapRoute],
+    amount: Uint128,
+    limit: Uint128,
+    exact_amount_in: bool,
+) -> Event {
+    MilkyWayEvent::Swap {
+        sender: sender.to_string(),
+        pool_ids: swap_routes.iter().map(|route| route.pool_id).collect(),
+        token_in_denom: swap_routes[0].token_in_denom.clone(),
+        token_out_denom: swap_routes.last().unwrap().token_out_denom.clone(),
+        amount,
+        limit,
+        exact_amount_in,
+    }
+    .into()
+}
+
 pub fn execute_update_config(
     deps: DepsMut,
     info: MessageInfo,
@@ -261,7 +331,13 @@ pub fn execute_update_config(
 
     let mut response = Response::new()
         .add_attribute("action", "update_config")
-        .add_attribute("sender", info.sender);
+        .add_attribute("sender", info.sender.clone())
+        .add_event(
+            MilkyWayEvent::UpdateConfig {
+                sender: info.sender.to_string(),
+            }
+            .into(),
+        );
 
     let mut config = CONFIG.load(deps.storage)?;
     if let Some(native_chain_config) = native_chain_config {
@@ -1,5 +1,6 @@
 use cosmwasm_schema::{cw_serde, QueryResponses};
 use cosmwasm_std::{Addr, Coin};
+use milky_way::migration::MigrationStatus;
 
 use crate::{
     state::SwapRoute,
@@ -67,6 +68,9 @@ pub enum ExecuteMsg {
 pub enum QueryMsg {
     #[returns(ConfigResponse)]
     Config {},
+    /// Returns the progress of the last storage migration.
+    #[returns(Option<MigrationStatus>)]
+    MigrationStatus {},
 }
 
 #[cw_serde]
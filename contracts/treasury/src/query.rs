@@ -1,4 +1,5 @@
 use cosmwasm_std::Deps;
+use milky_way::migration::{MigrationStatus, MIGRATION_STATUS};
 
 use crate::{
     error::ContractResult,
@@ -18,3 +19,7 @@ pub fn query_config(deps: Deps) -> ContractResult<ConfigResponse> {
         allowed_swap_routes: config.allowed_swap_routes,
     })
 }
+
+pub fn query_migration_status(deps: Deps) -> ContractResult<Option<MigrationStatus>> {
+    Ok(MIGRATION_STATUS.may_load(deps.storage)?)
+}
@@ -1,15 +1,21 @@
 use cosmwasm_std::{
-    testing::{mock_dependencies, mock_env},
+    from_json,
+    testing::{mock_dependencies, mock_env, mock_info},
     Addr,
 };
-use cw2::set_contract_version;
+use cw2::{get_contract_version, set_contract_version};
+use milky_way::migration::{MigrationStatus, MIGRATION_STATUS};
 
 use crate::{
-    contract::{migrate, CONTRACT_NAME},
-    migrations::states::v0_4_20,
-    msg::MigrateMsg,
+    contract::{execute, migrate, query, CONTRACT_NAME},
+    error::ContractError,
+    migrations::{
+        states::v0_4_20,
+        v1_0_0::{FROM_VERSION, TO_VERSION},
+    },
+    msg::{ExecuteMsg, MigrateMsg, QueryMsg},
     state::CONFIG,
-    tests::test_helper::TRADER,
+    tests::test_helper::{init, ADMIN, TRADER},
     types::UnsafeProtocolChainConfig,
 };
 
@@ -90,4 +96,55 @@ fn migrate_successfully() {
         "celestia",
         config.native_chain_config.account_address_prefix
     );
+
+    let status: Option<MigrationStatus> =
+        from_json(query(deps.as_ref(), mock_env(), QueryMsg::MigrationStatus {}).unwrap()).unwrap();
+    assert_eq!(
+        Some(MigrationStatus {
+            from_version: FROM_VERSION.to_string(),
+            to_version: TO_VERSION.to_string(),
+            step: "config".to_string(),
+            step_index: 1,
+            steps: 1,
+            cursor: None,
+            migrated: 1,
+            completed: true,
+        }),
+        status
+    );
+    assert_eq!(
+        TO_VERSION,
+        get_contract_version(&deps.storage).unwrap().version
+    );
+}
+
+#[test]
+fn execute_fails_while_migrating() {
+    let mut deps = init();
+    MIGRATION_STATUS
+        .save(
+            &mut deps.storage,
+            &MigrationStatus {
+                from_version: "1.0.0".to_string(),
+                to_version: "1.1.0".to_string(),
+                step: "config".to_string(),
+                step_index: 0,
+                steps: 1,
+                cursor: None,
+                migrated: 0,
+                completed: false,
+            },
+        )
+        .unwrap();
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(ADMIN, &[]),
+        ExecuteMsg::TransferOwnership {
+            new_owner: TRADER.to_string(),
+        },
+    )
+    .unwrap_err();
+    assert_eq!(ContractError::Migrating {}, err);
 }
@@ -0,0 +1,622 @@
+use cosmwasm_std::{Decimal, Event, Uint128};
+
+/// Prefix of the events emitted by the MilkyWay contracts.
+/// The chain prefixes the contract events with `wasm-`, so the
+/// events are indexed as `wasm-milkyway-<name>`.
+pub const EVENT_PREFIX: &str = "milkyway";
+
+/// Typed events emitted by the MilkyWay contracts.
+///
+/// Each variant is emitted as a `wasm-milkyway-<name>` event where the name is
+/// the snake case name of the variant and the attribute keys are the names of
+/// its fields. Amounts are integers, rates are decimals, optional fields are
+/// omitted when `None` and lists are comma separated.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum MilkyWayEvent {
+    /// A contract has been instantiated.
+    Instantiate { admin: String },
+    /// A contract has been migrated.
+    Migrate {
+        from_version: String,
+        to_version: String,
+    },
+    /// The ownership transfer of a contract has been started.
+    TransferOwnership {
+        previous_owner: String,
+        new_owner: String,
+    },
+    /// The pending ownership transfer of a contract has been revoked.
+    RevokeOwnershipTransfer { owner: String },
+    /// The pending ownership transfer of a contract has been accepted.
+    AcceptOwnership { new_owner: String },
+    /// The configuration of a contract has been updated.
+    UpdateConfig { sender: String },
+
+    /// Native tokens have been liquid staked.
+    LiquidStake {
+        sender: String,
+        native_sender: Option<String>,
+        mint_to: String,
+        denom: String,
+        amount: Uint128,
+        mint_denom: String,
+        mint_amount: Uint128,
+        redemption_rate: Decimal,
+    },
+    /// Liquid stake tokens have been added to the unstake request of a batch.
+    LiquidUnstake {
+        sender: String,
+        user: String,
+        batch_id: u64,
+        amount: Uint128,
+    },
+    /// A batch has been submitted for unbonding.
+    SubmitBatch {
+        batch_id: u64,
+        batch_total_liquid_stake: Uint128,
+        expected_native_unstaked: Uint128,
+        keeper: String,
+        keeper_bounty: Uint128,
+    },
+    /// The unstaked tokens of a batch have been withdrawn.
+    Withdraw {
+        user: String,
+        batch_id: u64,
+        denom: String,
+        amount: Uint128,
+    },
+    /// Staking rewards have been received.
+    ReceiveRewards {
+        denom: String,
+        amount: Uint128,
+        fee: Uint128,
+        insurance_fund_amount: Uint128,
+        amount_after_fees: Uint128,
+        redemption_rate: Decimal,
+    },
+    /// Unstaked tokens of a batch have been received.
+    ReceiveUnstakedTokens {
+        batch_id: u64,
+        amount: Uint128,
+        total_received: Uint128,
+        status: String,
+    },
+    /// A validator has been added to the validator set.
+    AddValidator { validator: String },
+    /// A validator has been removed from the validator set.
+    RemoveValidator { validator: String },
+    /// The contract has been stopped.
+    CircuitBreaker { sender: String },
+    /// The contract has been resumed.
+    ResumeContract {
+        total_native_token: Uint128,
+        total_liquid_stake_token: Uint128,
+        total_reward_amount: Uint128,
+        forced: bool,
+    },
+    /// A value provided to resume the contract exceeded its configured limit,
+    /// emitted when the contract is resumed with `force`.
+    ResumeContractLimitExceeded {
+        field: String,
+        reference: String,
+        value: String,
+        /// `None` if the deviation is unbounded, i.e. the reference is zero.
+        deviation: Option<Decimal>,
+        max_deviation: Decimal,
+    },
+    /// The expected native amount of a batch has been slashed.
+    SlashBatch {
+        batch_id: u64,
+        expected_native_unstaked: Uint128,
+    },
+    /// A validator slash has been recorded.
+    RecordSlash {
+        slash_id: u64,
+        validator: String,
+        amount: Uint128,
+        delegated_amount: Uint128,
+        insurance_covered_amount: Uint128,
+    },
+    /// Protocol fees have been withdrawn.
+    FeeWithdraw {
+        receiver: String,
+        denom: String,
+        amount: Uint128,
+    },
+    /// A batch has been received with less than its expected native amount.
+    UnstakedTokensShortfall {
+        batch_id: u64,
+        expected: Uint128,
+        received: Uint128,
+        shortfall: Uint128,
+    },
+    /// The rounding dust of a fully withdrawn batch has been credited to the fees.
+    SweepBatchDust { batch_id: u64, amount: Uint128 },
+    /// An IBC transfer has been sent.
+    IbcTransferSent { channel: String, sequence: u64 },
+    /// The lifecycle of an IBC transfer has been completed.
+    IbcTransferCompleted {
+        channel: String,
+        sequence: u64,
+        status: String,
+    },
+    /// Failed IBC transfers have been recovered.
+    RecoverIbcTransfers {
+        /// Receiver of the recovered packets, `None` if they have been
+        /// re-sent to their original receivers.
+        receiver: Option<String>,
+        packets: u64,
+    },
+    /// Failed IBC transfers have been retried.
+    RetryIbcTransfers {
+        /// Retried packets as `channel_id/sequence`.
+        retried_packets: Vec<String>,
+        /// Packets moved to the dead letters as `channel_id/sequence`.
+        dead_letter_packets: Vec<String>,
+    },
+    /// A destination chain has been registered.
+    SetDestinationChain {
+        bech32_prefix: String,
+        channel_id: String,
+    },
+    /// A destination chain has been removed.
+    RemoveDestinationChain { bech32_prefix: String },
+    /// The redemption rate oracle contract has been instantiated.
+    InstantiateOracle { address: String },
+    /// The bank metadata of the liquid stake token has been set.
+    SetDenomMetadata {
+        denom: String,
+        display: String,
+        symbol: String,
+        exponent: u32,
+    },
+    /// The transfer of the liquid stake token admin has been scheduled.
+    TransferDenomAdmin { new_admin: String, min_time: u64 },
+    /// The scheduled transfer of the liquid stake token admin has been revoked.
+    RevokeDenomAdminTransfer { new_admin: String },
+    /// The liquid stake token admin has been transferred.
+    CompleteDenomAdminTransfer { denom: String, new_admin: String },
+    /// The contract has adopted the liquid stake token of a previous deployment.
+    AdoptDenom {
+        denom: String,
+        total_native_token: Uint128,
+        total_liquid_stake_token: Uint128,
+        pending_batch_id: u64,
+    },
+    /// A page of batches and unstake requests of a previous deployment has been imported.
+    ImportBatches {
+        batches: u64,
+        unstake_requests: u64,
+        finished: bool,
+    },
+    /// The CW20 liquid stake token contract has been instantiated.
+    InstantiateToken { address: String },
+
+    /// An oracle update has failed.
+    OracleUpdateFailed { target: String, error: String },
+    /// A contract has been subscribed to the callbacks of protocol events.
+    SubscribeHooks {
+        address: String,
+        events: Vec<String>,
+    },
+    /// The hook subscription of a contract has been removed.
+    UnsubscribeHooks { address: String },
+    /// A hook callback has failed.
+    HookFailed { subscriber: String, error: String },
+    /// The rates have been posted again to the oracle after a failed update.
+    PushRates {
+        redemption_rate: Decimal,
+        purchase_rate: Decimal,
+    },
+    /// Rates of a liquid stake token have been posted to the oracle.
+    PostRates {
+        denom: String,
+        purchase_rate: Decimal,
+        redemption_rate: Decimal,
+    },
+    /// A liquid stake token contract has been allowed to post rates to the oracle.
+    AddLstContract { address: String },
+    /// A liquid stake token contract can't post rates to the oracle anymore.
+    RemoveLstContract { address: String },
+    /// The admin of the oracle has been updated.
+    UpdateAdmin { admin: String },
+
+    /// Funds have been sent from the treasury.
+    SpendFunds {
+        receiver: String,
+        denom: String,
+        amount: Uint128,
+        channel_id: Option<String>,
+    },
+    /// The treasury has swapped tokens.
+    Swap {
+        sender: String,
+        pool_ids: Vec<u64>,
+        token_in_denom: String,
+        token_out_denom: String,
+        amount: Uint128,
+        limit: Uint128,
+        exact_amount_in: bool,
+    },
+}
+
+impl MilkyWayEvent {
+    /// Name of the event without the prefixes.
+    pub fn name(&self) -> &'static str {
+        match self {
+            MilkyWayEvent::Instantiate { .. } => "instantiate",
+            MilkyWayEvent::Migrate { .. } => "migrate",
+            MilkyWayEvent::TransferOwnership { .. } => "transfer_ownership",
+            MilkyWayEvent::RevokeOwnershipTransfer { .. } => "revoke_ownership_transfer",
+            MilkyWayEvent::AcceptOwnership { .. } => "accept_ownership",
+            MilkyWayEvent::UpdateConfig { .. } => "update_config",
+            MilkyWayEvent::LiquidStake { .. } => "liquid_stake",
+            MilkyWayEvent::LiquidUnstake { .. } => "liquid_unstake",
+            MilkyWayEvent::SubmitBatch { .. } => "submit_batch",
+            MilkyWayEvent::Withdraw { .. } => "withdraw",
+            MilkyWayEvent::ReceiveRewards { .. } => "receive_rewards",
+            MilkyWayEvent::ReceiveUnstakedTokens { .. } => "receive_unstaked_tokens",
+            MilkyWayEvent::AddValidator { .. } => "add_validator",
+            MilkyWayEvent::RemoveValidator { .. } => "remove_validator",
+            MilkyWayEvent::CircuitBreaker { .. } => "circuit_breaker",
+            MilkyWayEvent::ResumeContract { .. } => "resume_contract",
+            MilkyWayEvent::ResumeContractLimitExceeded { .. } => "resume_contract_limit_exceeded",
+            MilkyWayEvent::SlashBatch { .. } => "slash_batch",
+            MilkyWayEvent::RecordSlash { .. } => "record_slash",
+            MilkyWayEvent::FeeWithdraw { .. } => "fee_withdraw",
+            MilkyWayEvent::UnstakedTokensShortfall { .. } => "unstaked_tokens_shortfall",
+            MilkyWayEvent::SweepBatchDust { .. } => "sweep_batch_dust",
+            MilkyWayEvent::IbcTransferSent { .. } => "ibc_transfer_sent",
+            MilkyWayEvent::IbcTransferCompleted { .. } => "ibc_transfer_completed",
+            MilkyWayEvent::RecoverIbcTransfers { .. } => "recover_ibc_transfers",
+            MilkyWayEvent::RetryIbcTransfers { .. } => "retry_ibc_transfers",
+            MilkyWayEvent::SetDestinationChain { .. } => "set_destination_chain",
+            MilkyWayEvent::RemoveDestinationChain { .. } => "remove_destination_chain",
+            MilkyWayEvent::InstantiateOracle { .. } => "instantiate_oracle",
+            MilkyWayEvent::InstantiateToken { .. } => "instantiate_token",
+            MilkyWayEvent::AdoptDenom { .. } => "adopt_denom",
+            MilkyWayEvent::ImportBatches { .. } => "import_batches",
+            MilkyWayEvent::SetDenomMetadata { .. } => "set_denom_metadata",
+            MilkyWayEvent::TransferDenomAdmin { .. } => "transfer_denom_admin",
+            MilkyWayEvent::RevokeDenomAdminTransfer { .. } => "revoke_denom_admin_transfer",
+            MilkyWayEvent::CompleteDenomAdminTransfer { .. } => "complete_denom_admin_transfer",
+            MilkyWayEvent::OracleUpdateFailed { .. } => "oracle_update_failed",
+            MilkyWayEvent::SubscribeHooks { .. } => "subscribe_hooks",
+            MilkyWayEvent::UnsubscribeHooks { .. } => "unsubscribe_hooks",
+            MilkyWayEvent::HookFailed { .. } => "hook_failed",
+            MilkyWayEvent::PushRates { .. } => "push_rates",
+            MilkyWayEvent::PostRates { .. } => "post_rates",
+            MilkyWayEvent::AddLstContract { .. } => "add_lst_contract",
+            MilkyWayEvent::RemoveLstContract { .. } => "remove_lst_contract",
+            MilkyWayEvent::UpdateAdmin { .. } => "update_admin",
+            MilkyWayEvent::SpendFunds { .. } => "spend_funds",
+            MilkyWayEvent::Swap { .. } => "swap",
+        }
+    }
+
+    /// Type of the event, the chain adds the `wasm-` prefix.
+    pub fn event_type(&self) -> String {
+        format!("{}-{}", EVENT_PREFIX, self.name())
+    }
+}
+
+fn join<T: ToString>(values: &[T]) -> String {
+    values
+        .iter()
+        .map(|v| v.to_string())
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+impl From<MilkyWayEvent> for Event {
+    fn from(value: MilkyWayEvent) -> Self {
+        let event = Event::new(value.event_type());
+        match value {
+            MilkyWayEvent::Instantiate { admin } => event.add_attribute("admin", admin),
+            MilkyWayEvent::Migrate {
+                from_version,
+                to_version,
+            } => event
+                .add_attribute("from_version", from_version)
+                .add_attribute("to_version", to_version),
+            MilkyWayEvent::TransferOwnership {
+                previous_owner,
+                new_owner,
+            } => event
+                .add_attribute("previous_owner", previous_owner)
+                .add_attribute("new_owner", new_owner),
+            MilkyWayEvent::RevokeOwnershipTransfer { owner } => event.add_attribute("owner", owner),
+            MilkyWayEvent::AcceptOwnership { new_owner } => {
+                event.add_attribute("new_owner", new_owner)
+            }
+            MilkyWayEvent::UpdateConfig { sender } => event.add_attribute("sender", sender),
+            MilkyWayEvent::LiquidStake {
+                sender,
+                native_sender,
+                mint_to,
+                denom,
+                amount,
+                mint_denom,
+                mint_amount,
+                redemption_rate,
+            } => {
+                let event = event.add_attribute("sender", sender);
+                let event = match native_sender {
+                    Some(native_sender) => event.add_attribute("native_sender", native_sender),
+                    None => event,
+                };
+                event
+                    .add_attribute("mint_to", mint_to)
+                    .add_attribute("denom", denom)
+                    .add_attribute("amount", amount)
+                    .add_attribute("mint_denom", mint_denom)
+                    .add_attribute("mint_amount", mint_amount)
+                    .add_attribute("redemption_rate", redemption_rate.to_string())
+            }
+            MilkyWayEvent::LiquidUnstake {
+                sender,
+                user,
+                batch_id,
+                amount,
+            } => event
+                .add_attribute("sender", sender)
+                .add_attribute("user", user)
+                .add_attribute("batch_id", batch_id.to_string())
+                .add_attribute("amount", amount),
+            MilkyWayEvent::SubmitBatch {
+                batch_id,
+                batch_total_liquid_stake,
+                expected_native_unstaked,
+                keeper,
+                keeper_bounty,
+            } => event
+                .add_attribute("batch_id", batch_id.to_string())
+                .add_attribute("batch_total_liquid_stake", batch_total_liquid_stake)
+                .add_attribute("expected_native_unstaked", expected_native_unstaked)
+                .add_attribute("keeper", keeper)
+                .add_attribute("keeper_bounty", keeper_bounty),
+            MilkyWayEvent::Withdraw {
+                user,
+                batch_id,
+                denom,
+                amount,
+            } => event
+                .add_attribute("user", user)
+                .add_attribute("batch_id", batch_id.to_string())
+                .add_attribute("denom", denom)
+                .add_attribute("amount", amount),
+            MilkyWayEvent::ReceiveRewards {
+                denom,
+                amount,
+                fee,
+                insurance_fund_amount,
+                amount_after_fees,
+                redemption_rate,
+            } => event
+                .add_attribute("denom", denom)
+                .add_attribute("amount", amount)
+                .add_attribute("fee", fee)
+                .add_attribute("insurance_fund_amount", insurance_fund_amount)
+                .add_attribute("amount_after_fees", amount_after_fees)
+                .add_attribute("redemption_rate", redemption_rate.to_string()),
+            MilkyWayEvent::ReceiveUnstakedTokens {
+                batch_id,
+                amount,
+                total_received,
+                status,
+            } => event
+                .add_attribute("batch_id", batch_id.to_string())
+                .add_attribute("amount", amount)
+                .add_attribute("total_received", total_received)
+                .add_attribute("status", status),
+            MilkyWayEvent::AddValidator { validator }
+            | MilkyWayEvent::RemoveValidator { validator } => {
+                event.add_attribute("validator", validator)
+            }
+            MilkyWayEvent::CircuitBreaker { sender } => event.add_attribute("sender", sender),
+            MilkyWayEvent::ResumeContract {
+                total_native_token,
+                total_liquid_stake_token,
+                total_reward_amount,
+                forced,
+            } => event
+                .add_attribute("total_native_token", total_native_token)
+                .add_attribute("total_liquid_stake_token", total_liquid_stake_token)
+                .add_attribute("total_reward_amount", total_reward_amount)
+                .add_attribute("forced", forced.to_string()),
+            MilkyWayEvent::ResumeContractLimitExceeded {
+                field,
+                reference,
+                value,
+                deviation,
+                max_deviation,
+            } => event
+                .add_attribute("field", field)
+                .add_attribute("reference", reference)
+                .add_attribute("value", value)
+                .add_attribute(
+                    "deviation",
+                    deviation
+                        .map(|d| d.to_string())
+                        .unwrap_or_else(|| "unbounded".to_string()),
+                )
+                .add_attribute("max_deviation", max_deviation.to_string()),
+            MilkyWayEvent::SlashBatch {
+                batch_id,
+                expected_native_unstaked,
+            } => event
+                .add_attribute("batch_id", batch_id.to_string())
+                .add_attribute("expected_native_unstaked", expected_native_unstaked),
+            MilkyWayEvent::RecordSlash {
+                slash_id,
+                validator,
+                amount,
+                delegated_amount,
+                insurance_covered_amount,
+            } => event
+                .add_attribute("slash_id", slash_id.to_string())
+                .add_attribute("validator", validator)
+                .add_attribute("amount", amount)
+                .add_attribute("delegated_amount", delegated_amount)
+                .add_attribute("insurance_covered_amount", insurance_covered_amount),
+            MilkyWayEvent::UnstakedTokensShortfall {
+                batch_id,
+                expected,
+                received,
+                shortfall,
+            } => event
+                .add_attribute("batch_id", batch_id.to_string())
+                .add_attribute("expected", expected)
+                .add_attribute("received", received)
+                .add_attribute("shortfall", shortfall),
+            MilkyWayEvent::SweepBatchDust { batch_id, amount } => event
+                .add_attribute("batch_id", batch_id.to_string())
+                .add_attribute("amount", amount),
+            MilkyWayEvent::FeeWithdraw {
+                receiver,
+                denom,
+                amount,
+            } => event
+                .add_attribute("receiver", receiver)
+                .add_attribute("denom", denom)
+                .add_attribute("amount", amount),
+            MilkyWayEvent::IbcTransferSent { channel, sequence } => event
+                .add_attribute("channel", channel)
+                .add_attribute("sequence", sequence.to_string()),
+            MilkyWayEvent::IbcTransferCompleted {
+                channel,
+                sequence,
+                status,
+            } => event
+                .add_attribute("channel", channel)
+                .add_attribute("sequence", sequence.to_string())
+                .add_attribute("status", status),
+            MilkyWayEvent::RecoverIbcTransfers { receiver, packets } => {
+                let event = event.add_attribute("packets", packets.to_string());
+                match receiver {
+                    Some(receiver) => event.add_attribute("receiver", receiver),
+                    None => event,
+                }
+            }
+            MilkyWayEvent::RetryIbcTransfers {
+                retried_packets,
+                dead_letter_packets,
+            } => event
+                .add_attribute("retried_packets", join(&retried_packets))
+                .add_attribute("dead_letter_packets", join(&dead_letter_packets)),
+            MilkyWayEvent::SetDestinationChain {
+                bech32_prefix,
+                channel_id,
+            } => event
+                .add_attribute("bech32_prefix", bech32_prefix)
+                .add_attribute("channel_id", channel_id),
+            MilkyWayEvent::RemoveDestinationChain { bech32_prefix } => {
+                event.add_attribute("bech32_prefix", bech32_prefix)
+            }
+            MilkyWayEvent::InstantiateOracle { address }
+            | MilkyWayEvent::InstantiateToken { address } => {
+                event.add_attribute("address", address)
+            }
+            MilkyWayEvent::AdoptDenom {
+                denom,
+                total_native_token,
+                total_liquid_stake_token,
+                pending_batch_id,
+            } => event
+                .add_attribute("denom", denom)
+                .add_attribute("total_native_token", total_native_token)
+                .add_attribute("total_liquid_stake_token", total_liquid_stake_token)
+                .add_attribute("pending_batch_id", pending_batch_id.to_string()),
+            MilkyWayEvent::ImportBatches {
+                batches,
+                unstake_requests,
+                finished,
+            } => event
+                .add_attribute("batches", batches.to_string())
+                .add_attribute("unstake_requests", unstake_requests.to_string())
+                .add_attribute("finished", finished.to_string()),
+            MilkyWayEvent::SetDenomMetadata {
+                denom,
+                display,
+                symbol,
+                exponent,
+            } => event
+                .add_attribute("denom", denom)
+                .add_attribute("display", display)
+                .add_attribute("symbol", symbol)
+                .add_attribute("exponent", exponent.to_string()),
+            MilkyWayEvent::TransferDenomAdmin {
+                new_admin,
+                min_time,
+            } => event
+                .add_attribute("new_admin", new_admin)
+                .add_attribute("min_time", min_time.to_string()),
+            MilkyWayEvent::RevokeDenomAdminTransfer { new_admin } => {
+                event.add_attribute("new_admin", new_admin)
+            }
+            MilkyWayEvent::CompleteDenomAdminTransfer { denom, new_admin } => event
+                .add_attribute("denom", denom)
+                .add_attribute("new_admin", new_admin),
+            MilkyWayEvent::OracleUpdateFailed { target, error } => event
+                .add_attribute("target", target)
+                .add_attribute("error", error),
+            MilkyWayEvent::SubscribeHooks { address, events } => event
+                .add_attribute("address", address)
+                .add_attribute("events", join(&events)),
+            MilkyWayEvent::UnsubscribeHooks { address } => event.add_attribute("address", address),
+            MilkyWayEvent::HookFailed { subscriber, error } => event
+                .add_attribute("subscriber", subscriber)
+                .add_attribute("error", error),
+            MilkyWayEvent::PushRates {
+                redemption_rate,
+                purchase_rate,
+            } => event
+                .add_attribute("redemption_rate", redemption_rate.to_string())
+                .add_attribute("purchase_rate", purchase_rate.to_string()),
+            MilkyWayEvent::PostRates {
+                denom,
+                purchase_rate,
+                redemption_rate,
+            } => event
+                .add_attribute("denom", denom)
+                .add_attribute("purchase_rate", purchase_rate.to_string())
+                .add_attribute("redemption_rate", redemption_rate.to_string()),
+            MilkyWayEvent::AddLstContract { address }
+            | MilkyWayEvent::RemoveLstContract { address } => {
+                event.add_attribute("address", address)
+            }
+            MilkyWayEvent::UpdateAdmin { admin } => event.add_attribute("admin", admin),
+            MilkyWayEvent::SpendFunds {
+                receiver,
+                denom,
+                amount,
+                channel_id,
+            } => {
+                let event = event
+                    .add_attribute("receiver", receiver)
+                    .add_attribute("denom", denom)
+                    .add_attribute("amount", amount);
+                match channel_id {
+                    Some(channel_id) => event.add_attribute("channel_id", channel_id),
+                    None => event,
+                }
+            }
+            MilkyWayEvent::Swap {
+                sender,
+                pool_ids,
+                token_in_denom,
+                token_out_denom,
+                amount,
+                limit,
+                exact_amount_in,
+            } => event
+                .add_attribute("sender", sender)
+                .add_attribute("pool_ids", join(&pool_ids))
+                .add_attribute("token_in_denom", token_in_denom)
+                .add_attribute("token_out_denom", token_out_denom)
+                .add_attribute("amount", amount)
+                .add_attribute("limit", limit)
+                .add_attribute("exact_amount_in", exact_amount_in.to_string()),
+        }
+    }
+}
@@ -1,2 +1,4 @@
+pub mod events;
+pub mod migration;
 pub mod staking;
 pub mod utils;
@@ -0,0 +1,208 @@
+//! Resumable storage migrations executed in pages of at most `limit` entries.
+//!
+//! A [`Migration`] runs a list of [`MigrationStep`]s in order. Each step
+//! migrates the entries after its cursor and the progress is saved in
+//! [`MIGRATION_STATUS`], so the migration is resumed by the next execution
+//! until all the steps are completed and the invariants hold.
+
+use cosmwasm_std::{from_json, to_json_string, Deps, DepsMut, Env, StdError, Storage};
+use cw2::{assert_contract_version, set_contract_version, VersionError};
+use cw_storage_plus::Item;
+use schemars::JsonSchema;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+
+/// Progress of the last migration.
+pub const MIGRATION_STATUS: Item<MigrationStatus> = Item::new("migration_status");
+
+#[derive(Serialize, Deserialize, Clone, Debug, Eq, PartialEq, JsonSchema)]
+pub struct MigrationStatus {
+    pub from_version: String,
+    pub to_version: String,
+    /// Name of the step being executed, or of the last step once completed.
+    pub step: String,
+    /// Index of the step being executed.
+    pub step_index: u32,
+    /// Number of steps of the migration.
+    pub steps: u32,
+    /// JSON encoded cursor of the last entry migrated by the current step.
+    pub cursor: Option<String>,
+    /// Number of entries migrated by all the executions.
+    pub migrated: u64,
+    pub completed: bool,
+}
+
+/// Result of the execution of a step.
+pub struct StepProgress<C> {
+    /// Cursor of the last migrated entry, `None` if nothing has been migrated.
+    pub cursor: Option<C>,
+    /// Number of migrated entries.
+    pub migrated: usize,
+    /// True if there are no entries left to migrate.
+    pub completed: bool,
+}
+
+/// A step of a migration, e.g. the conversion of the entries of a map.
+pub trait MigrationStep<E> {
+    /// Position of the step in the migrated entries, usually a storage key.
+    type Cursor: Serialize + DeserializeOwned;
+
+    fn name(&self) -> &'static str;
+
+    /// Migrates at most `limit` entries after `cursor`.
+    fn migrate(
+        &self,
+        deps: DepsMut,
+        env: &Env,
+        cursor: Option<Self::Cursor>,
+        limit: usize,
+    ) -> Result<StepProgress<Self::Cursor>, E>;
+}
+
+/// [`MigrationStep`] with the cursor encoded as JSON, so the steps can be stored together.
+trait EncodedStep<E> {
+    fn name(&self) -> &'static str;
+
+    fn migrate(
+        &self,
+        deps: DepsMut,
+        env: &Env,
+        cursor: Option<&str>,
+        limit: usize,
+    ) -> Result<(Option<String>, usize, bool), E>;
+}
+
+impl<E, S> EncodedStep<E> for S
+where
+    E: From<StdError>,
+    S: MigrationStep<E>,
+{
+    fn name(&self) -> &'static str {
+        MigrationStep::name(self)
+    }
+
+    fn migrate(
+        &self,
+        deps: DepsMut,
+        env: &Env,
+        cursor: Option<&str>,
+        limit: usize,
+    ) -> Result<(Option<String>, usize, bool), E> {
+        let cursor = cursor.map(from_json).transpose()?;
+        let progress = MigrationStep::migrate(self, deps, env, cursor, limit)?;
+        let cursor = progress
+            .cursor
+            .map(|cursor| to_json_string(&cursor))
+            .transpose()?;
+        Ok((cursor, progress.migrated, progress.completed))
+    }
+}
+
+type Invariant<'a, E> = Box<dyn Fn(Deps) -> Result<(), E> + 'a>;
+
+pub struct Migration<'a, E> {
+    contract_name: &'a str,
+    from_version: &'a str,
+    to_version: &'a str,
+    steps: Vec<Box<dyn EncodedStep<E> + 'a>>,
+    invariants: Vec<Invariant<'a, E>>,
+}
+
+impl<'a, E> Migration<'a, E>
+where
+    E: From<StdError> + From<VersionError>,
+{
+    pub fn new(contract_name: &'a str, from_version: &'a str, to_version: &'a str) -> Self {
+        Migration {
+            contract_name,
+            from_version,
+            to_version,
+            steps: vec![],
+            invariants: vec![],
+        }
+    }
+
+    /// Adds a step, executed after the previously added ones.
+    pub fn step(mut self, step: impl MigrationStep<E> + 'a) -> Self {
+        self.steps.push(Box::new(step));
+        self
+    }
+
+    /// Adds a check executed once all the steps are completed, the
+    /// migration fails if it doesn't hold.
+    pub fn invariant(mut self, check: impl Fn(Deps) -> Result<(), E> + 'a) -> Self {
+        self.invariants.push(Box::new(check));
+        self
+    }
+
+    /// Executes the steps from the saved progress, migrating at most `limit`
+    /// entries. The contract version is updated once the migration is completed.
+    pub fn run(
+        &self,
+        mut deps: DepsMut,
+        env: &Env,
+        limit: Option<usize>,
+    ) -> Result<MigrationStatus, E> {
+        let mut status = match MIGRATION_STATUS.may_load(deps.storage)? {
+            Some(status) if !status.completed => {
+                if status.from_version != self.from_version || status.to_version != self.to_version
+                {
+                    return Err(StdError::generic_err(format!(
+                        "Migration from {} to {} is in progress",
+                        status.from_version, status.to_version
+                    ))
+                    .into());
+                }
+                status
+            }
+            _ => {
+                assert_contract_version(deps.storage, self.contract_name, self.from_version)?;
+                MigrationStatus {
+                    from_version: self.from_version.to_string(),
+                    to_version: self.to_version.to_string(),
+                    step: String::new(),
+                    step_index: 0,
+                    steps: self.steps.len() as u32,
+                    cursor: None,
+                    migrated: 0,
+                    completed: false,
+                }
+            }
+        };
+
+        let mut limit = limit.unwrap_or(usize::MAX);
+        while let Some(step) = self.steps.get(status.step_index as usize) {
+            status.step = step.name().to_string();
+            let (cursor, migrated, completed) =
+                step.migrate(deps.branch(), env, status.cursor.as_deref(), limit)?;
+            status.migrated += migrated as u64;
+            if completed {
+                status.step_index += 1;
+                status.cursor = None;
+                limit = limit.saturating_sub(migrated);
+            } else {
+                if cursor.is_some() {
+                    status.cursor = cursor;
+                }
+                break;
+            }
+        }
+
+        if status.step_index as usize == self.steps.len() {
+            for check in &self.invariants {
+                check(deps.as_ref())?;
+            }
+            status.completed = true;
+            set_contract_version(deps.storage, self.contract_name, self.to_version)?;
+        }
+        MIGRATION_STATUS.save(deps.storage, &status)?;
+
+        Ok(status)
+    }
+}
+
+/// Returns true if a migration has been started and not completed yet.
+pub fn migration_in_progress(storage: &dyn Storage) -> Result<bool, StdError> {
+    Ok(MIGRATION_STATUS
+        .may_load(storage)?
+        .map_or(false, |status| !status.completed))
+}
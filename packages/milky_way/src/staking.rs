@@ -6,6 +6,9 @@ use serde::{Deserialize, Serialize};
 pub enum BatchStatus {
     Pending,
     Submitted,
+    /// Part of the unstaked tokens has been received, the batch is waiting
+    /// for the remaining tranches.
+    PartiallyReceived,
     Received,
 }
 
@@ -14,6 +17,7 @@ impl BatchStatus {
         match self {
             BatchStatus::Pending => "pending",
             BatchStatus::Submitted => "submitted",
+            BatchStatus::PartiallyReceived => "partially_received",
             BatchStatus::Received => "received",
         }
     }
@@ -66,6 +70,10 @@ impl Batch {
                 self.status = new_status;
                 self.next_batch_action_time = next_action;
             }
+            // Keeps the estimated time of the remaining tranches
+            BatchStatus::PartiallyReceived => {
+                self.status = new_status;
+            }
             BatchStatus::Received => {
                 self.status = new_status;
                 self.next_batch_action_time = None;